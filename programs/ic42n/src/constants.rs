@@ -1,14 +1,22 @@
-pub const RESOLVED_GAME_VERSION: u8 = 2;
+pub const RESOLVED_GAME_VERSION: u8 = 4;
 
 pub const FEE_BPS_DENOM: u64 = 10_000;
 
+/// Slots that must pass after an epoch's last slot before it can be resolved.
+pub const EPOCH_SETTLE_SLOTS: u64 = 32;
+
 // Max number of tickets a player can receive as reward as one time
 pub const MAX_TICKETS_PER_GRANT: u32 = 5; // adjust as needed
 
 // Max number of tickets a player can have at once
 pub const MAX_TICKETS_PER_PLAYER: u32 = 100; // adjust as needed
 
-/// How many recent bet pubkeys to keep in the profile
+/// Change tickets a new profile starts with; written to `Config.welcome_tickets`
+/// by `initialize` and `upgrade_config`.
+pub const DEFAULT_WELCOME_TICKETS: u8 = 1;
+
+/// Max (and default) number of recent bet pubkeys kept in a profile.
+/// Profiles pick their own ring size up to this at creation.
 pub const RECENT_BETS_CAP: usize = 40;
 
 pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
@@ -18,4 +26,42 @@ pub const TIER1_MAX: u64 = 1_000_000_000;      // 1 SOL
 pub const TIER2_MIN: u64 = 1_000_000_000;      // 1 SOL
 pub const TIER2_MAX: u64 = 10_000_000_000;     // 10 SOL
 pub const TIER3_MIN: u64 = 10_000_000_000;      // 10 SOL
-pub const TIER3_MAX: u64 = 100_000_000_000;    // 100 SOL
\ No newline at end of file
+pub const TIER3_MAX: u64 = 100_000_000_000;    // 100 SOL
+
+// ─────────────────────────────
+// XP / levels
+// ─────────────────────────────
+
+/// Wager size that earns the base XP amount (0.01 SOL, the smallest tier bet).
+pub const XP_BASE_WAGER_LAMPORTS: u64 = 10_000_000;
+
+/// XP earned per doubling of the wager over `XP_BASE_WAGER_LAMPORTS`.
+pub const XP_PER_DOUBLING: u32 = 10;
+
+/// Minimum XP required for each level, starting at level 1.
+/// A profile's level is the number of thresholds its XP has reached.
+pub const LEVEL_XP_THRESHOLDS: [u32; 12] = [
+    0,       // 1
+    100,     // 2
+    300,     // 3
+    700,     // 4
+    1_500,   // 5
+    3_000,   // 6
+    6_000,   // 7
+    12_000,  // 8
+    25_000,  // 9
+    50_000,  // 10
+    100_000, // 11
+    200_000, // 12
+];
+
+// ─────────────────────────────
+// Admin handoffs
+// ─────────────────────────────
+
+/// Epochs the authority must wait before accepting a proposed fee vault
+/// without the vault's own signature.
+pub const FEE_VAULT_ACCEPT_DELAY_EPOCHS: u64 = 2;
+
+/// Upper bound for `Config::dispute_window_slots` (~one epoch).
+pub const MAX_DISPUTE_WINDOW_SLOTS: u64 = 432_000;
\ No newline at end of file
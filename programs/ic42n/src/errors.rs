@@ -121,6 +121,7 @@ pub enum IC42NErrorCode {
     // ─────────────────────────────
     // Merkle / Claim System
     // ─────────────────────────────
+    #[msg("Claim bitmap length does not match total_winners")]
     InvalidBitmapLen,
     InsufficientPrizePool,
     ProofTooLong,
@@ -136,13 +137,234 @@ pub enum IC42NErrorCode {
     #[msg("Already claimed")]
     AlreadyClaimed,
 
+    #[msg("Same index used twice in one batch")]
     InvalidIndex,
 
     #[msg("Claim not allowed")]
     ClaimNotAllowed,
 
+    #[msg("Claim index falls outside the claim bitmap")]
     BitmapOutOfBounds,
+
+    #[msg("Claim index is not below total_winners")]
     InvalidClaimIndex,
     TooManyClaims,
     ProfileLockedActiveGame,
+
+    // Everything below was added after the first deployment. New variants
+    // go at the end so deployed error codes keep their numbers.
+
+    // ─────────────────────────────
+    // Authority / fee vault handoff
+    // ─────────────────────────────
+    #[msg("No pending authority")]
+    NoPendingAuthority,
+
+    #[msg("No pending fee vault")]
+    NoPendingFeeVault,
+
+    #[msg("Fee vault handoff not yet acceptable")]
+    FeeVaultHandoffLocked,
+
+    // ─────────────────────────────
+    // Live feed
+    // ─────────────────────────────
+    #[msg("LiveFeed pot doesn't match its per-number stakes")]
+    LiveFeedAccountingMismatch,
+
+    #[msg("Feed has an unresolved epoch with bets; resolve it before resetting")]
+    UnresolvedEpochBlocksReset,
+
+    #[msg("Tier chain was cancelled; predictions can only be refunded")]
+    ChainCancelled,
+
+    // ─────────────────────────────
+    // Betting
+    // ─────────────────────────────
+    #[msg("Betting paused for this tier")]
+    TierPaused,
+
+    #[msg("Changing the prediction type is not allowed")]
+    TypeSwitchNotAllowed,
+
+    #[msg("Epoch bet limit reached")]
+    EpochBetLimitReached,
+
+    #[msg("Bet would exceed the per-number lamports cap")]
+    NumberConcentrationCap,
+
+    #[msg("Choice does not match the prediction's selections")]
+    ChoiceMismatch,
+
+    #[msg("Prediction total exceeds the tier's exposure cap")]
+    ExposureCapExceeded,
+
+    #[msg("Number changes are disabled for this tier")]
+    ChangesDisabledForTier,
+
+    #[msg("Prediction's game already resolved; claim it instead")]
+    PredictionGameResolved,
+
+    // ─────────────────────────────
+    // Tier activation
+    // ─────────────────────────────
+    #[msg("Activating this tier would exceed the max active tiers")]
+    MaxActiveTiersExceeded,
+
+    #[msg("Tier needs a positive curve_factor to be activated")]
+    CannotActivateWithoutCurve,
+
+    // ─────────────────────────────
+    // Referrals
+    // ─────────────────────────────
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+
+    // ─────────────────────────────
+    // Leaderboard
+    // ─────────────────────────────
+    #[msg("Leaderboard disabled for this tier")]
+    LeaderboardDisabled,
+
+    // ─────────────────────────────
+    // Profiles
+    // ─────────────────────────────
+    #[msg("Recent bets ring size exceeds the maximum")]
+    InvalidRecentBetsCap,
+
+    #[msg("Profile still has unclaimed predictions")]
+    HasUnclaimedPredictions,
+
+    // ─────────────────────────────
+    // Game Resolution
+    // ─────────────────────────────
+    #[msg("Results URI must be UTF-8 and start with ar://, ipfs:// or https://")]
+    InvalidResultsUri,
+
+    #[msg("Winner count exceeds bets on winning number")]
+    WinnerCountExceedsBets,
+
+    #[msg("Winning number requires a rollover")]
+    ShouldHaveRolledOver,
+
+    #[msg("Pot below resolve threshold; use the rollover path")]
+    PotBelowResolveThreshold,
+
+    #[msg("RNG slot is outside the epoch or too old")]
+    StaleRngSlot,
+
+    #[msg("Dispute window must be at most MAX_DISPUTE_WINDOW_SLOTS")]
+    InvalidDisputeWindow,
+
+    #[msg("Dispute window has ended; resolution is final")]
+    DisputeWindowClosed,
+
+    #[msg("A reverted game can only be re-committed with winners")]
+    RevertedGameNeedsWinners,
+
+    #[msg("Max rollovers exceeded")]
+    MaxRolloversExceeded,
+
+    #[msg("Results URI extension exceeds the maximum length")]
+    ResultsUriTooLong,
+
+    #[msg("Invalid fee rounding mode")]
+    InvalidFeeRounding,
+
+    // ─────────────────────────────
+    // Merkle / Claim System
+    // ─────────────────────────────
+    InvalidMerkleScheme,
+
+    #[msg("Proof length does not match the winner tree depth")]
+    InvalidProofLength,
+
+    #[msg("Claim index does not match the prediction's assigned leaf index")]
+    LeafIndexMismatch,
+
+    #[msg("Prediction does not cover the winning number or covers the blocked number")]
+    IneligibleWinner,
+
+    #[msg("Claimed totals exceed the game's prize pool or winner count")]
+    ClaimAccountingOverflow,
+
+    #[msg("Claims are paused")]
+    ClaimsPaused,
+
+    #[msg("Game claims are frozen")]
+    GameFrozen,
+
+    #[msg("Claim window closed")]
+    ClaimWindowClosed,
+
+    #[msg("Claim window still open")]
+    ClaimWindowStillOpen,
+
+    #[msg("Game is in its dispute window; claims are not open yet")]
+    GameInDisputeWindow,
+
+    #[msg("Prediction is still active or has an unclaimed payout")]
+    PredictionStillActive,
+
+    // ─────────────────────────────
+    // Treasury / fees / game close
+    // ─────────────────────────────
+    #[msg("Payout would leave the treasury below its rent-exempt minimum")]
+    TreasuryBelowRentExempt,
+
+    #[msg("Withdrawals are paused")]
+    WithdrawPaused,
+
+    #[msg("Treasury has no surplus over its obligations")]
+    NoTreasurySurplus,
+
+    #[msg("Protocol fee already collected")]
+    FeeAlreadyCollected,
+
+    #[msg("Protocol fee is not collectable yet")]
+    FeeNotCollectable,
+
+    #[msg("No house seed to recover")]
+    NoHouseSeed,
+
+    #[msg("Game still holds claimable winnings, an owed fee or a house seed")]
+    GameNotClosable,
+
+    #[msg("Game is not final or still owes winnings, its fee or a house seed")]
+    GameStillClaimable,
+
+    // ─────────────────────────────
+    // Emergency
+    // ─────────────────────────────
+    #[msg("Emergency refunds are not open")]
+    EmergencyRefundsClosed,
+
+    // ─────────────────────────────
+    // Fallback RNG
+    // ─────────────────────────────
+    #[msg("RNG slot and hash must be the SlotHashes entry ending the epoch")]
+    FallbackRngMismatch,
+
+    // ─────────────────────────────
+    // Treasury obligations
+    // ─────────────────────────────
+    #[msg("Treasury does not track its obligations yet; run upgrade_treasury")]
+    TreasuryNotUpgraded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deployed_error_codes_keep_their_numbers() {
+        assert_eq!(u32::from(IC42NErrorCode::EpochMismatch), 6000);
+        assert_eq!(u32::from(IC42NErrorCode::MathOverflow), 6011);
+        assert_eq!(u32::from(IC42NErrorCode::InactiveTier), 6021);
+        assert_eq!(u32::from(IC42NErrorCode::ProfileLockedActiveGame), 6066);
+        assert_eq!(u32::from(IC42NErrorCode::NoPendingAuthority), 6067);
+    }
 }
\ No newline at end of file
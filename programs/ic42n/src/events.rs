@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+#[repr(u8)]
+pub enum TicketAwardSource {
+    Auto = 1,   // Tier-driven award after resolution
+    Manual = 2, // Admin grant
+}
+impl TicketAwardSource {
+    pub fn as_u8(self) -> u8 { self as u8 }
+}
+
+/// Emitted whenever tickets are credited to a player profile.
+#[event]
+pub struct TicketsAwarded {
+    pub player: Pubkey,
+    /// Tier the award was computed for (0 for manual grants).
+    pub tier: u8,
+    /// Tickets actually credited after the per-player cap.
+    pub count: u32,
+    /// See `TicketAwardSource`.
+    pub source: u8,
+}
+
+
+/// Emitted at the end of `complete_resolve_game` and `complete_rollover_game`.
+/// Rollovers use the same shape with `total_winners = 0`.
+#[event]
+pub struct GameResolved {
+    pub epoch: u64,
+    pub tier: u8,
+    pub winning_number: u8,
+    pub total_winners: u32,
+    pub protocol_fee_lamports: u64,
+    pub net_prize_pool: u64,
+    pub carry_out_lamports: u64,
+    pub merkle_root: [u8; 32],
+    /// See `RolloverReason` (0 = paid out, chain ended).
+    pub rollover_reason: u8,
+}
\ No newline at end of file
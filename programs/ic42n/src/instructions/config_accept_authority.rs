@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        constraint = config.has_pending_authority() @ IC42NErrorCode::NoPendingAuthority,
+        constraint = config.pending_authority == new_authority.key() @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Must be `config.pending_authority`.
+    pub new_authority: Signer<'info>,
+}
+
+/// Second step of an authority transfer: the proposed key signs to take over.
+pub fn accept_authority_handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let cfg = &mut ctx.accounts.config;
+
+    // Fee vault may have rotated since the proposal
+    require!(
+        cfg.pending_authority != cfg.fee_vault,
+        IC42NErrorCode::AuthorityCannotEqualFeeVault
+    );
+
+    cfg.authority = cfg.pending_authority;
+    cfg.pending_authority = Pubkey::default();
+
+    msg!("Authority accepted: {}", cfg.authority);
+    Ok(())
+}
\ No newline at end of file
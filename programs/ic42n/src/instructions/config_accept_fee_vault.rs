@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct AcceptFeeVault<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        constraint = config.has_pending_fee_vault() @ IC42NErrorCode::NoPendingFeeVault
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Either the pending fee vault, or the authority once the delay has passed.
+    pub signer: Signer<'info>,
+}
+
+/// Second step of a fee vault rotation: promotes `pending_fee_vault`
+/// to `config.fee_vault` and clears the pending slot.
+pub fn accept_fee_vault_handler(ctx: Context<AcceptFeeVault>) -> Result<()> {
+    let cfg = &mut ctx.accounts.config;
+    let signer = ctx.accounts.signer.key();
+    let current_epoch = Clock::get()?.epoch;
+
+    require!(
+        signer == cfg.pending_fee_vault || signer == cfg.authority,
+        IC42NErrorCode::Unauthorized
+    );
+    require!(
+        cfg.can_accept_fee_vault(&signer, current_epoch),
+        IC42NErrorCode::FeeVaultHandoffLocked
+    );
+
+    // Authority may have rotated since the proposal
+    require!(
+        cfg.pending_fee_vault != cfg.authority,
+        IC42NErrorCode::AuthorityCannotEqualFeeVault
+    );
+
+    cfg.fee_vault = cfg.pending_fee_vault;
+    cfg.pending_fee_vault = Pubkey::default();
+    cfg.pending_fee_vault_epoch = 0;
+
+    msg!("Fee vault accepted: {}", cfg.fee_vault);
+    Ok(())
+}
\ No newline at end of file
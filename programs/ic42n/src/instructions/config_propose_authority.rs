@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::instructions::config_update::validate_authority_target;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+/// First step of an authority transfer.
+///
+/// Records `new_authority` as pending. The current authority keeps full
+/// control and may overwrite the proposal until `accept_authority` runs.
+/// Proposing `Pubkey::default()` cancels it.
+pub fn propose_authority_handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let config_key = ctx.accounts.config.key();
+    let cfg = &mut ctx.accounts.config;
+
+    if new_authority != Pubkey::default() {
+        validate_authority_target(new_authority, ctx.program_id, &config_key, &cfg.fee_vault)?;
+    }
+
+    cfg.pending_authority = new_authority;
+
+    msg!("Authority proposed: {}", new_authority);
+    Ok(())
+}
\ No newline at end of file
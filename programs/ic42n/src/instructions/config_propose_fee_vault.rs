@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::instructions::config_update::validate_fee_vault_target;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct ProposeFeeVault<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+/// First step of a fee vault rotation.
+///
+/// Records `new_fee_vault` as pending. `config.fee_vault` is unchanged until
+/// `accept_fee_vault` promotes it. Proposing again replaces the pending
+/// vault and restarts the delay; proposing `Pubkey::default()` cancels it.
+pub fn propose_fee_vault_handler(ctx: Context<ProposeFeeVault>, new_fee_vault: Pubkey) -> Result<()> {
+    let config_key = ctx.accounts.config.key();
+    let cfg = &mut ctx.accounts.config;
+
+    if new_fee_vault == Pubkey::default() {
+        cfg.pending_fee_vault = Pubkey::default();
+        cfg.pending_fee_vault_epoch = 0;
+        return Ok(());
+    }
+
+    validate_fee_vault_target(new_fee_vault, ctx.program_id, &config_key, &cfg.authority)?;
+
+    cfg.pending_fee_vault = new_fee_vault;
+    cfg.pending_fee_vault_epoch = Clock::get()?.epoch;
+
+    msg!("Fee vault proposed: {}", new_fee_vault);
+    Ok(())
+}
\ No newline at end of file
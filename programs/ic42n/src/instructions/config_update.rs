@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::constants::{FEE_BPS_DENOM, MAX_TICKETS_PER_PLAYER};
+use crate::constants::{FEE_BPS_DENOM, MAX_DISPUTE_WINDOW_SLOTS, MAX_TICKETS_PER_PLAYER};
 use crate::errors::IC42NErrorCode;
-use crate::state::config::Config;
+use crate::state::config::{Config, FeeRounding};
+use crate::state::{MerkleScheme, ResolvedGame};
+use crate::state::tiers::TierFeeSettings;
 
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
@@ -53,6 +55,88 @@ pub struct TierUpdateArgs {
 
     /// Ticket reward count for this tier (optional).
     pub tickets_rewarded: Option<u8>,
+
+    /// Per-number live lamports cap for this tier (optional). 0 disables.
+    pub max_lamports_per_number: Option<u64>,
+
+    /// Enables (1) or disables (0) the per-epoch leaderboard for this tier (optional).
+    pub leaderboard_enabled: Option<u8>,
+
+    /// Pauses (1) or resumes (0) betting on this tier only (optional).
+    pub paused: Option<u8>,
+
+    /// Tier-specific fee schedule (optional). Setting any of these gives the
+    /// tier its own schedule, seeded from the global fees; `use_global_fees = 1`
+    /// drops it again.
+    pub base_fee_bps: Option<u16>,
+    pub min_fee_bps: Option<u16>,
+    pub rollover_fee_step_bps: Option<u16>,
+    pub use_global_fees: Option<u8>,
+
+    /// Max winners per game for this tier (optional). 0 = global max.
+    pub max_winners: Option<u32>,
+
+    /// Betting cutoff in slots for this tier (optional). 0 = global cutoff.
+    /// Applies from the tier's next LiveFeed epoch.
+    pub bet_cutoff_slots: Option<u64>,
+
+    /// Max total lamports of one prediction in this tier (optional). 0 disables.
+    pub max_total_exposure_lamports: Option<u64>,
+
+    /// Allows (1) or forbids (0) `change_prediction_number` on this tier (optional).
+    pub allow_changes: Option<u8>,
+
+    /// Tickets a number change costs on this tier (optional). 0 = 1 ticket.
+    pub change_ticket_cost: Option<u8>,
+}
+
+
+/// Fee invariants shared by the global schedule and per-tier schedules.
+pub fn validate_fee_schedule(base_fee_bps: u16, min_fee_bps: u16, step_bps: u16) -> Result<()> {
+    require!(base_fee_bps <= FEE_BPS_DENOM as u16, IC42NErrorCode::InvalidFee);
+    require!(min_fee_bps  <= FEE_BPS_DENOM as u16, IC42NErrorCode::InvalidMinimumFee);
+    require!(step_bps     <= FEE_BPS_DENOM as u16, IC42NErrorCode::InvalidFeeStep);
+
+    // key invariant
+    require!(min_fee_bps <= base_fee_bps, IC42NErrorCode::InvalidFeeConfig);
+
+    // Step should never exceed the base fee
+    // (prevents "first rollover drops straight to min" surprises)
+    require!(step_bps <= base_fee_bps, IC42NErrorCode::InvalidFeeStep);
+
+    Ok(())
+}
+
+/// Rejects authority targets that nobody could sign for, or that would
+/// collapse the authority / fee vault separation.
+pub fn validate_authority_target(
+    new_auth: Pubkey,
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    fee_vault: &Pubkey,
+) -> Result<()> {
+    require!(new_auth != Pubkey::default(), IC42NErrorCode::InvalidAuthorityTarget);
+    require!(new_auth != system_program::ID, IC42NErrorCode::InvalidAuthorityTarget);
+    require!(new_auth != *program_id, IC42NErrorCode::InvalidAuthorityTarget);
+    require!(new_auth != *config_key, IC42NErrorCode::InvalidAuthorityTarget);
+    require!(new_auth != *fee_vault, IC42NErrorCode::InvalidAuthorityTarget);
+    Ok(())
+}
+
+/// Rejects fee vault targets that could never receive fees safely
+/// (default key, system program, this program, the config PDA, the authority).
+pub fn validate_fee_vault_target(
+    new_vault: Pubkey,
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    authority: &Pubkey,
+) -> Result<()> {
+    require!(new_vault != Pubkey::default(), IC42NErrorCode::InvalidFeeVault);
+    require!(new_vault != system_program::ID, IC42NErrorCode::InvalidFeeVault);
+    require!(new_vault != *program_id, IC42NErrorCode::InvalidFeeVault);
+    require!(new_vault != *config_key, IC42NErrorCode::InvalidFeeVault);
+    require!(new_vault != *authority, IC42NErrorCode::InvalidFeeVault);
+    Ok(())
 }
 
 
@@ -61,6 +145,14 @@ pub struct TierUpdateArgs {
 /// - Only callable by the `authority` stored in `Config`.
 /// - Any argument set to `None` is left unchanged.
 /// - `tier_updates` may be an empty vector (no tier changes).
+/// - `new_authority` only proposes the new authority (same as
+///   `propose_authority`); it takes effect once the new key calls
+///   `accept_authority`.
+/// - `pause_withdraw` is a legacy alias that sets both `pause_claims` and
+///   `pause_fee_withdrawals`.
+/// - `new_fee_vault` is deprecated: it rotates the vault in one step with no
+///   confirmation from the new address. Prefer `propose_fee_vault` followed
+///   by `accept_fee_vault`.
 pub fn update_config_handler(
     ctx: Context<UpdateConfig>,
     pause_bet: Option<u8>,
@@ -72,6 +164,26 @@ pub fn update_config_handler(
     new_rollover_fee_step_bps: Option<u16>,
     new_cutoff_slots: Option<u64>,
     new_primary_roll_over_number: Option<u8>,
+    new_max_rollovers: Option<u8>,
+    new_max_predictions_per_epoch: Option<u16>,
+    emergency_refunds_open: Option<u8>,
+    new_merkle_scheme: Option<u8>,
+    new_claim_window_epochs: Option<u16>,
+    new_resolver: Option<Pubkey>,
+    new_min_resolve_lamports: Option<u64>,
+    new_rng_max_age_slots: Option<u64>,
+    allow_type_switch: Option<u8>,
+    new_dispute_window_slots: Option<u64>,
+    new_fee_delay_slots: Option<u64>,
+    strict_cutoff: Option<u8>,
+    new_welcome_tickets: Option<u8>,
+    pause_claims: Option<u8>,
+    pause_fee_withdrawals: Option<u8>,
+    new_min_winners_for_fee: Option<u32>,
+    new_fee_rounding: Option<u8>,
+    new_max_active_tiers: Option<u8>,
+    new_max_fee_lamports: Option<u64>,
+    verbose_logging: Option<u8>,
     tier_updates: Vec<TierUpdateArgs>,
 ) -> Result<()> {
     let cfg = &mut ctx.accounts.config;
@@ -82,32 +194,46 @@ pub fn update_config_handler(
     if let Some(pause) = pause_bet {
         cfg.pause_bet = if pause == 1 { 1 } else { 0 };
     }
-    if let Some(pause) = pause_withdraw {
-        cfg.pause_withdraw = if pause == 1 { 1 } else { 0 };
+    cfg.apply_withdraw_pauses(pause_withdraw, pause_claims, pause_fee_withdrawals);
+    if let Some(open) = emergency_refunds_open {
+        cfg.emergency_refunds_open = if open == 1 { 1 } else { 0 };
+    }
+    if let Some(allow) = allow_type_switch {
+        cfg.allow_type_switch = if allow == 1 { 1 } else { 0 };
+    }
+    if let Some(strict) = strict_cutoff {
+        cfg.strict_cutoff = if strict == 1 { 1 } else { 0 };
+    }
+    if let Some(verbose) = verbose_logging {
+        cfg.verbose_logging = if verbose == 1 { 1 } else { 0 };
+    }
+    if let Some(tickets) = new_welcome_tickets {
+        require!(
+            tickets as u32 <= MAX_TICKETS_PER_PLAYER,
+            IC42NErrorCode::InvalidTicketAmount
+        );
+        cfg.welcome_tickets = tickets;
     }
 
     // ─────────────────────────────────────────────
-    // Authority rotation
+    // Authority rotation (proposal only; see `accept_authority`)
     // ─────────────────────────────────────────────
     if let Some(new_auth) = new_authority {
-        require!(new_auth != Pubkey::default(), IC42NErrorCode::InvalidAuthorityTarget);
-        require!(new_auth != system_program::ID, IC42NErrorCode::InvalidAuthorityTarget);
-        require!(new_auth != *ctx.program_id, IC42NErrorCode::InvalidAuthorityTarget);
-        require!(new_auth != cfg.key(), IC42NErrorCode::InvalidAuthorityTarget);
-        require!(new_auth != cfg.fee_vault, IC42NErrorCode::InvalidAuthorityTarget);
-        cfg.authority = new_auth;
+        validate_authority_target(new_auth, ctx.program_id, &cfg.key(), &cfg.fee_vault)?;
+        cfg.pending_authority = new_auth;
     }
 
     // ─────────────────────────────────────────────
-    // Fee vault update
+    // Fee vault update (deprecated single-step path)
     // ─────────────────────────────────────────────
     if let Some(new_vault) = new_fee_vault {
-        require!(new_vault != Pubkey::default(), IC42NErrorCode::InvalidFeeVault);
-        require!(new_vault != system_program::ID, IC42NErrorCode::InvalidFeeVault);
-        require!(new_vault != *ctx.program_id, IC42NErrorCode::InvalidFeeVault);
-        require!(new_vault != cfg.key(), IC42NErrorCode::InvalidFeeVault);
-        require!(new_vault != cfg.authority, IC42NErrorCode::InvalidFeeVault);
+        msg!("new_fee_vault is deprecated; use propose_fee_vault + accept_fee_vault");
+        validate_fee_vault_target(new_vault, ctx.program_id, &cfg.key(), &cfg.authority)?;
         cfg.fee_vault = new_vault;
+
+        // A direct rotation supersedes any pending handoff
+        cfg.pending_fee_vault = Pubkey::default();
+        cfg.pending_fee_vault_epoch = 0;
     }
 
     // ─────────────────────────────────────────────
@@ -123,16 +249,101 @@ pub fn update_config_handler(
         cfg.primary_roll_over_number = roll_over_number;
     }
 
+    // 0 disables the cap
+    if let Some(max_rollovers) = new_max_rollovers {
+        cfg.max_rollovers = max_rollovers;
+    }
+
+    // 0 disables the cap
+    if let Some(max_predictions) = new_max_predictions_per_epoch {
+        cfg.max_predictions_per_epoch = max_predictions;
+    }
+
+    // Only affects games resolved after this update
+    if let Some(scheme) = new_merkle_scheme {
+        require!(
+            scheme <= MerkleScheme::Sorted.as_u8(),
+            IC42NErrorCode::InvalidMerkleScheme
+        );
+        cfg.merkle_scheme = scheme;
+    }
+
+    // 0 disables claim expiry; only affects games resolved after this update
+    if let Some(window) = new_claim_window_epochs {
+        cfg.claim_window_epochs = window;
+    }
+
+    // ─────────────────────────────────────────────
+    // Resolver role (default key clears it)
+    // ─────────────────────────────────────────────
+    if let Some(resolver) = new_resolver {
+        if resolver != Pubkey::default() {
+            require!(resolver != system_program::ID, IC42NErrorCode::InvalidAuthorityTarget);
+            require!(resolver != *ctx.program_id, IC42NErrorCode::InvalidAuthorityTarget);
+            require!(resolver != cfg.key(), IC42NErrorCode::InvalidAuthorityTarget);
+        }
+        cfg.resolver = resolver;
+    }
+
+    if let Some(min_pot) = new_min_resolve_lamports {
+        cfg.min_resolve_lamports = min_pot;
+    }
+
+    if let Some(max_age) = new_rng_max_age_slots {
+        cfg.rng_max_age_slots = max_age;
+    }
+
+    // 0 disables the window; only affects games resolved after this update
+    if let Some(window) = new_dispute_window_slots {
+        require!(window <= MAX_DISPUTE_WINDOW_SLOTS, IC42NErrorCode::InvalidDisputeWindow);
+        cfg.dispute_window_slots = window;
+    }
+
+    // Only affects games resolved after this update
+    if let Some(delay) = new_fee_delay_slots {
+        cfg.fee_delay_slots = delay;
+    }
+
+    // 0 always charges the fee; only affects games resolved after this update
+    if let Some(min_winners) = new_min_winners_for_fee {
+        cfg.min_winners_for_fee = min_winners;
+    }
+
+    // Only affects games resolved after this update
+    if let Some(rounding) = new_fee_rounding {
+        require!(
+            rounding <= FeeRounding::Ceil.as_u8(),
+            IC42NErrorCode::InvalidFeeRounding
+        );
+        cfg.fee_rounding = rounding;
+    }
+
+    // Only limits later activations; tiers already active stay active
+    if let Some(max_active) = new_max_active_tiers {
+        cfg.max_active_tiers = max_active;
+    }
+
+    // Only affects games resolved after this update
+    if let Some(max_fee) = new_max_fee_lamports {
+        cfg.max_fee_lamports = max_fee;
+    }
+
     // ─────────────────────────────────────────────
     // Tier updates (patch in-place)
     // ─────────────────────────────────────────────
     for update in tier_updates.into_iter() {
-        let tier = cfg
+        let idx = cfg
             .tiers
-            .iter_mut()
-            .find(|t| t.tier_id == update.tier_id)
+            .iter()
+            .position(|t| t.tier_id == update.tier_id)
             .ok_or(IC42NErrorCode::UnknownTier)?;
 
+        if update.active == Some(1) {
+            cfg.assert_can_activate(update.tier_id)?;
+        }
+
+        let tier = &mut cfg.tiers[idx];
+
         if let Some(active) = update.active {
             require!(active <= 1, IC42NErrorCode::InvalidTierFlag);
             tier.active = active;
@@ -164,6 +375,11 @@ pub fn update_config_handler(
             );
         }
 
+        // Activation takes effect together with a curve passed in this call
+        if update.active == Some(1) {
+            tier.check_activatable()?;
+        }
+
         // Ticket config (use effective_bps so max validation matches caller intent)
         let mut effective_bps = tier.ticket_reward_bps;
         if let Some(bps) = update.ticket_reward_bps {
@@ -186,6 +402,74 @@ pub fn update_config_handler(
             );
             tier.tickets_per_recipient = tickets;
         }
+
+        if let Some(cap) = update.max_lamports_per_number {
+            tier.max_lamports_per_number = cap;
+        }
+
+        if let Some(enabled) = update.leaderboard_enabled {
+            require!(enabled <= 1, IC42NErrorCode::InvalidTierFlag);
+            tier.leaderboard_enabled = enabled;
+        }
+
+        if let Some(paused) = update.paused {
+            require!(paused <= 1, IC42NErrorCode::InvalidTierFlag);
+            tier.paused = paused;
+        }
+
+        if let Some(max_winners) = update.max_winners {
+            require!(
+                max_winners as usize <= ResolvedGame::MAX_WINNERS_PER_GAME,
+                IC42NErrorCode::TooManyWinners
+            );
+            cfg.tier_max_winners[idx] = max_winners;
+        }
+
+        if let Some(cutoff_slots) = update.bet_cutoff_slots {
+            require!(
+                cutoff_slots == 0 || cutoff_slots > 20,
+                IC42NErrorCode::InvalidCutOffNumber
+            );
+            cfg.tier_bet_cutoff_slots[idx] = cutoff_slots;
+        }
+
+        if let Some(cap) = update.max_total_exposure_lamports {
+            cfg.tier_max_total_exposure[idx] = cap;
+        }
+
+        if let Some(allow) = update.allow_changes {
+            require!(allow <= 1, IC42NErrorCode::InvalidTierFlag);
+            cfg.tier_changes_disabled[idx] = 1 - allow;
+        }
+
+        if let Some(cost) = update.change_ticket_cost {
+            cfg.tier_change_ticket_cost[idx] = cost;
+        }
+
+        // Tier fee schedule (validated with the same invariants as the global one)
+        if let Some(use_global) = update.use_global_fees {
+            require!(use_global <= 1, IC42NErrorCode::InvalidTierFlag);
+            if use_global == 1 {
+                cfg.tier_fees[idx] = TierFeeSettings::default();
+            }
+        }
+
+        if update.base_fee_bps.is_some()
+            || update.min_fee_bps.is_some()
+            || update.rollover_fee_step_bps.is_some()
+        {
+            let current = cfg.fee_settings_for(update.tier_id)?;
+            let fees = TierFeeSettings {
+                enabled: 1,
+                base_fee_bps: update.base_fee_bps.unwrap_or(current.base_fee_bps),
+                min_fee_bps: update.min_fee_bps.unwrap_or(current.min_fee_bps),
+                rollover_fee_step_bps: update
+                    .rollover_fee_step_bps
+                    .unwrap_or(current.rollover_fee_step_bps),
+            };
+            validate_fee_schedule(fees.base_fee_bps, fees.min_fee_bps, fees.rollover_fee_step_bps)?;
+            cfg.tier_fees[idx] = fees;
+        }
     }
 
     // ─────────────────────────────────────────────
@@ -194,27 +478,16 @@ pub fn update_config_handler(
     let effective_base_fee = new_fee_bps.unwrap_or(cfg.base_fee_bps);
     let effective_min_fee  = new_min_fee_bps.unwrap_or(cfg.min_fee_bps);
     let effective_step_fee = new_rollover_fee_step_bps.unwrap_or(cfg.rollover_fee_step_bps);
-    let effective_authority = new_authority.unwrap_or(cfg.authority);
     let effective_fee_vault = new_fee_vault.unwrap_or(cfg.fee_vault);
 
-    require!(effective_authority != effective_fee_vault, IC42NErrorCode::AuthorityCannotEqualFeeVault);
-    require!(effective_base_fee <= FEE_BPS_DENOM as u16, IC42NErrorCode::InvalidFee);
-    require!(effective_min_fee  <= FEE_BPS_DENOM as u16, IC42NErrorCode::InvalidMinimumFee);
-    require!(effective_step_fee <= FEE_BPS_DENOM as u16, IC42NErrorCode::InvalidFeeStep);
-
-    // key invariant
-    require!(effective_min_fee <= effective_base_fee, IC42NErrorCode::InvalidFeeConfig);
-
-    // Step should never exceed the base fee
-    // (prevents "first rollover drops straight to min" surprises)
-    require!(effective_step_fee <= effective_base_fee, IC42NErrorCode::InvalidFeeStep);
+    require!(cfg.authority != effective_fee_vault, IC42NErrorCode::AuthorityCannotEqualFeeVault);
+    validate_fee_schedule(effective_base_fee, effective_min_fee, effective_step_fee)?;
 
     // ----- apply ONLY the fields that were provided -----
     if let Some(v) = new_fee_bps { cfg.base_fee_bps = v; }
     if let Some(v) = new_min_fee_bps { cfg.min_fee_bps = v; }
     if let Some(v) = new_rollover_fee_step_bps { cfg.rollover_fee_step_bps = v; }
 
-    if let Some(v) = new_authority { cfg.authority = v; }
     if let Some(v) = new_fee_vault { cfg.fee_vault = v; }
     
     Ok(())
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::errors::IC42NErrorCode;
+use crate::state::config::Config;
+use crate::utils::transfers::grow_account;
+
+/// Byte offset of `Config.authority` (discriminator + pause_bet + pause_withdraw).
+const CONFIG_AUTHORITY_OFFSET: usize = 8 + 1 + 1;
+
+/// Grows a `SIZE_V1` (or partially upgraded) Config to `SIZE_V2`.
+///
+/// Authority only. The new tail is zero-initialized; a V1 Config then gets
+/// `Config::set_upgrade_defaults` for the fields whose default isn't zero.
+/// A Config already at `SIZE_V2` is left untouched.
+#[derive(Accounts)]
+pub struct UpgradeConfig<'info> {
+    /// CHECK: A V1 layout can't be deserialized as `Config` until grown;
+    /// the PDA seeds, owner, discriminator and stored authority are verified instead.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+        owner = crate::ID @ IC42NErrorCode::Unauthorized,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn upgrade_config_handler(ctx: Context<UpgradeConfig>) -> Result<()> {
+    let config = ctx.accounts.config.to_account_info();
+    let old_len = config.data_len();
+
+    {
+        let data = config.try_borrow_data()?;
+        require!(data.len() >= 8 + Config::SIZE_V1, IC42NErrorCode::InvalidInput);
+        require!(data[..8] == *Config::DISCRIMINATOR, IC42NErrorCode::InvalidInput);
+
+        let stored_authority = Pubkey::try_from(
+            &data[CONFIG_AUTHORITY_OFFSET..CONFIG_AUTHORITY_OFFSET + 32],
+        )
+        .map_err(|_| error!(IC42NErrorCode::InvalidInput))?;
+        require_keys_eq!(
+            stored_authority,
+            ctx.accounts.authority.key(),
+            IC42NErrorCode::Unauthorized
+        );
+    }
+
+    grow_account(
+        &config,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + Config::SIZE_V2,
+    )?;
+
+    // Don't rely on realloc for zeroed memory
+    let mut data = config.try_borrow_mut_data()?;
+    if data.len() > old_len {
+        data[old_len..].fill(0);
+    }
+
+    if old_len == 8 + Config::SIZE_V1 {
+        let mut cfg = Config::deserialize(&mut &data[8..])?;
+        cfg.set_upgrade_defaults();
+        cfg.serialize(&mut &mut data[8..])?;
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+
+/// Max predictions updated in a single `assign_leaf_indices` call.
+pub const MAX_LEAF_ASSIGNMENTS: usize = 20;
+
+/// Records each winner's Merkle leaf index on its Prediction.
+///
+/// Worker step run after `complete_resolve_game` (ideally inside the dispute
+/// window): `remaining_accounts[i]` is the (writable) Prediction whose leaf
+/// sits at `indices[i]` in the committed winner tree. Claims on games at
+/// `LEAF_INDEX_VERSION` must use exactly that index. Predictions still on
+/// the version 2 layout must go through `migrate_prediction` first.
+///
+/// Indices may be reassigned until the prediction claims, e.g. after
+/// `revert_resolution` re-commits a different tree.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct AssignLeafIndices<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = resolved_game.bump,
+        constraint = resolved_game.epoch == epoch @ IC42NErrorCode::EpochMismatch,
+        constraint = resolved_game.tier == tier   @ IC42NErrorCode::TierMismatch,
+    )]
+    pub resolved_game: Account<'info, ResolvedGame>,
+
+    /// Account allowed to resolve games: `config.authority` or `config.resolver`.
+    #[account(constraint = config.can_resolve(&authority.key()) @ IC42NErrorCode::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+pub fn assign_leaf_indices_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssignLeafIndices<'info>>,
+    _epoch: u64,
+    tier: u8,
+    indices: Vec<u32>,
+) -> Result<()> {
+    let game = &ctx.accounts.resolved_game;
+    let predictions = ctx.remaining_accounts;
+
+    require!(!indices.is_empty(), IC42NErrorCode::InvalidInput);
+    require!(indices.len() <= MAX_LEAF_ASSIGNMENTS, IC42NErrorCode::InvalidInput);
+    require_eq!(predictions.len(), indices.len(), IC42NErrorCode::InvalidInput);
+
+    require!(
+        game.status == GameStatus::Resolved as u8,
+        IC42NErrorCode::GameNotResolved
+    );
+    require!(game.total_winners > 0, IC42NErrorCode::ClaimNotAllowed);
+
+    for (i, (&index, info)) in indices.iter().zip(predictions.iter()).enumerate() {
+        require!(index < game.total_winners, IC42NErrorCode::InvalidClaimIndex);
+
+        // One leaf per index within the batch
+        require!(
+            !indices[..i].contains(&index),
+            IC42NErrorCode::InvalidIndex
+        );
+
+        require!(info.is_writable, IC42NErrorCode::InvalidInput);
+        let mut pred: Account<'info, Prediction> = Account::try_from(info)?;
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                Prediction::SEED_PREFIX,
+                pred.player.as_ref(),
+                game.first_epoch_in_chain.to_le_bytes().as_ref(),
+                &[tier],
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(info.key(), expected, IC42NErrorCode::InvalidInput);
+        require!(pred.tier == tier, IC42NErrorCode::TierMismatch);
+        require!(
+            pred.game_epoch == game.first_epoch_in_chain,
+            IC42NErrorCode::EpochMismatch
+        );
+
+        // Only winners have a leaf, and a settled prediction keeps its index
+        require!(pred.mask_has(game.winning_number), IC42NErrorCode::ClaimNotAllowed);
+        require!(pred.has_claimed == 0, IC42NErrorCode::AlreadyClaimed);
+
+        pred.leaf_index = index;
+        pred.leaf_index_assigned = 1;
+        pred.exit(ctx.program_id)?;
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
 use crate::state::*;
 
+/// Closes one ResolvedGame, rent to the authority.
+///
+/// Refused until the game is `is_closable`: final, with every claim, the
+/// deferred fee and any house seed settled. The Merkle root outlives every
+/// valid claim, and an unfinished game can't be closed and re-initialized.
 #[derive(Accounts)]
 #[instruction(epoch: u64, tier: u8)]
 pub struct CloseGame<'info> {
@@ -24,9 +30,16 @@ pub struct CloseGame<'info> {
 
 
 pub fn close_resolved_game_handler(
-    _ctx: Context<CloseGame>,
+    ctx: Context<CloseGame>,
     _epoch: u64,
     _tier: u8,
 ) -> Result<()> {
+    let current_epoch = Clock::get()?.epoch;
+
+    require!(
+        ctx.accounts.resolved_game.is_closable(current_epoch),
+        IC42NErrorCode::GameStillClaimable
+    );
+
     Ok(())
 }
\ No newline at end of file
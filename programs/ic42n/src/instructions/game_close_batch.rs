@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+
+/// Closes every finished ResolvedGame of one epoch, rent to the authority.
+///
+/// `remaining_accounts` are the (writable) ResolvedGame PDAs of the epoch's
+/// tiers. Addresses that hold no game (the tier never resolved that epoch)
+/// are skipped; every game that exists must satisfy `is_closable`.
+#[derive(Accounts)]
+pub struct CloseGamesBatch<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, address = config.authority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn close_resolved_games_batch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseGamesBatch<'info>>,
+    epoch: u64,
+) -> Result<()> {
+    let games = ctx.remaining_accounts;
+    require!(!games.is_empty(), IC42NErrorCode::InvalidInput);
+    require!(games.len() <= 5, IC42NErrorCode::InvalidInput);
+
+    let current_epoch = Clock::get()?.epoch;
+    let mut closed: u8 = 0;
+
+    for info in games.iter() {
+        // Never-initialized tier for this epoch
+        if info.owner != ctx.program_id || info.data_is_empty() {
+            continue;
+        }
+        require!(info.is_writable, IC42NErrorCode::InvalidInput);
+
+        let game: Account<'info, ResolvedGame> = Account::try_from(info)?;
+        require_eq!(game.epoch, epoch, IC42NErrorCode::EpochMismatch);
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[game.tier]],
+            ctx.program_id,
+        );
+        require_keys_eq!(info.key(), expected, IC42NErrorCode::InvalidInput);
+
+        require!(game.is_closable(current_epoch), IC42NErrorCode::GameNotClosable);
+
+        game.close(ctx.accounts.authority.to_account_info())?;
+        closed += 1;
+    }
+
+    msg!("Closed {} resolved games for epoch {}", closed, epoch);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::treasury::Treasury;
+use crate::utils::transfers::require_treasury_can_pay;
+
+/// Moves a resolved game's owed protocol fee from the treasury to the fee vault.
+///
+/// `complete_resolve_game` only records the fee; it can be collected once the
+/// game's dispute window has passed and it has been resolved for
+/// `config.fee_delay_slots`, and not while it is frozen. `fee_collected`
+/// makes this a one-shot transfer.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct CollectProtocolFee<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = resolved_game.bump,
+        constraint = resolved_game.epoch == epoch @ IC42NErrorCode::EpochMismatch,
+        constraint = resolved_game.tier == tier   @ IC42NErrorCode::TierMismatch,
+    )]
+    pub resolved_game: Account<'info, ResolvedGame>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        address = config.fee_vault @ IC42NErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// Account allowed to resolve games: `config.authority` or `config.resolver`.
+    #[account(constraint = config.can_resolve(&authority.key()) @ IC42NErrorCode::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+pub fn collect_protocol_fee_handler(
+    ctx: Context<CollectProtocolFee>,
+    _epoch: u64,
+    _tier: u8,
+) -> Result<()> {
+    let game      = &mut ctx.accounts.resolved_game;
+    let treasury  = &mut ctx.accounts.treasury;
+    let fee_vault = &mut ctx.accounts.fee_vault;
+
+    let slot = Clock::get()?.slot;
+
+    require!(
+        !ctx.accounts.config.are_fee_withdrawals_paused(),
+        IC42NErrorCode::WithdrawPaused
+    );
+
+    require!(game.fee_collected == 0, IC42NErrorCode::FeeAlreadyCollected);
+    game.assert_not_frozen()?;
+    require!(game.can_collect_fee(slot), IC42NErrorCode::FeeNotCollectable);
+
+    let fee = game.protocol_fee_lamports;
+
+    let treasury_balance = **treasury.to_account_info().lamports.borrow();
+    require_treasury_can_pay(treasury_balance, fee)?;
+
+    // Flag before moving lamports so the fee can only ever leave once
+    game.fee_collected = 1;
+
+    treasury.total_fees_withdrawn = treasury
+        .total_fees_withdrawn
+        .checked_add(fee)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+    treasury.release_obligation(fee);
+
+    **treasury.to_account_info().try_borrow_mut_lamports()? -= fee;
+    **fee_vault.to_account_info().try_borrow_mut_lamports()? += fee;
+
+    Ok(())
+}
\ No newline at end of file
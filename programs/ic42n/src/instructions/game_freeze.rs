@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+
+/// Holds (or releases) claims on a single game whose result is disputed,
+/// without pausing claims protocol-wide.
+///
+/// A frozen game can still be reverted and re-committed, even after its
+/// dispute window; the freeze stays on until `unfreeze_game`, so claims only
+/// reopen once the authority is satisfied with the committed result. Its fee
+/// and sweep are held too, and unfreezing restarts the claim window so the
+/// freeze doesn't eat into it.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct FreezeGame<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = resolved_game.bump,
+        constraint = resolved_game.epoch == epoch @ IC42NErrorCode::EpochMismatch,
+        constraint = resolved_game.tier == tier   @ IC42NErrorCode::TierMismatch,
+    )]
+    pub resolved_game: Account<'info, ResolvedGame>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn freeze_game_handler(ctx: Context<FreezeGame>, _epoch: u64, _tier: u8) -> Result<()> {
+    ctx.accounts.resolved_game.frozen = 1;
+    Ok(())
+}
+
+pub fn unfreeze_game_handler(ctx: Context<FreezeGame>, _epoch: u64, _tier: u8) -> Result<()> {
+    let game = &mut ctx.accounts.resolved_game;
+    let current_epoch = Clock::get()?.epoch;
+
+    game.frozen = 0;
+    game.extend_claim_deadline(ctx.accounts.config.claim_deadline_for(current_epoch));
+    Ok(())
+}
\ No newline at end of file
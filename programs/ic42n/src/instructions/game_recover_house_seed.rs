@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::treasury::Treasury;
+use crate::utils::transfers::require_treasury_can_pay;
+
+/// Returns the house seed of a game that resolved without winners to the
+/// fee vault. The seed was left out of the carry at resolve, so this never
+/// touches player lamports.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct RecoverHouseSeed<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = resolved_game.bump,
+        constraint = resolved_game.epoch == epoch @ IC42NErrorCode::EpochMismatch,
+        constraint = resolved_game.tier == tier   @ IC42NErrorCode::TierMismatch,
+    )]
+    pub resolved_game: Account<'info, ResolvedGame>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        address = config.fee_vault @ IC42NErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn recover_house_seed_handler(
+    ctx: Context<RecoverHouseSeed>,
+    _epoch: u64,
+    _tier: u8,
+) -> Result<()> {
+    let game      = &mut ctx.accounts.resolved_game;
+    let treasury  = &mut ctx.accounts.treasury;
+    let fee_vault = &mut ctx.accounts.fee_vault;
+
+    require!(
+        !ctx.accounts.config.are_fee_withdrawals_paused(),
+        IC42NErrorCode::WithdrawPaused
+    );
+
+    require!(
+        game.status == GameStatus::Resolved as u8,
+        IC42NErrorCode::GameNotResolved
+    );
+
+    let seed = game.house_seed_lamports;
+    require!(seed > 0, IC42NErrorCode::NoHouseSeed);
+
+    let treasury_balance = **treasury.to_account_info().lamports.borrow();
+    require_treasury_can_pay(treasury_balance, seed)?;
+
+    // Zero before moving lamports so the seed can only leave once
+    game.house_seed_lamports = 0;
+    treasury.release_obligation(seed);
+
+    **treasury.to_account_info().try_borrow_mut_lamports()? -= seed;
+    **fee_vault.to_account_info().try_borrow_mut_lamports()? += seed;
+
+    Ok(())
+}
\ No newline at end of file
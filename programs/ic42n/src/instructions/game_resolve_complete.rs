@@ -1,22 +1,32 @@
 use anchor_lang::prelude::*;
 use crate::errors::IC42NErrorCode;
+use crate::events::GameResolved;
 use crate::state::*;
 use crate::state::treasury::Treasury;
 use crate::constants::*;
-use crate::utils::resolve::get_next_rollover_number;
+use crate::utils::math::bps_of;
+use crate::utils::resolve::{get_next_rollover_number, is_rollover_number, validate_results_uri_with_ext};
 
 ///Cannot resolve the same epoch twice:
 // ResolvedGame PDA is created once via InitResolvedGame,
 // and this instruction requires status == Resolving and then sets it to Resolved.
 #[derive(Accounts)]
-#[instruction(epoch: u64, tier: u8)]
+#[instruction(
+    epoch: u64,
+    tier: u8,
+    protocol_fee_lamports: u64,
+    net_prize_pool: u64,
+    total_winners: u32,
+    merkle_root: [u8; 32],
+    results_uri: [u8; 128],
+    results_uri_ext: Vec<u8>,
+)]
 pub struct CompleteResolveGame<'info> {
     /// Global config (for authority + fee_bps etc.)
     #[account(
         mut,
         seeds = [Config::SEED],
         bump = config.bump,
-        has_one = authority @ IC42NErrorCode::Unauthorized
     )]
     pub config: Account<'info, Config>,
 
@@ -28,11 +38,16 @@ pub struct CompleteResolveGame<'info> {
     )]
     pub live_feed: Account<'info, LiveFeed>,
 
-    /// ResolvedGame PDA for this epoch & tier – MUST already exist
+    /// ResolvedGame PDA for this epoch & tier – MUST already exist.
+    /// Grown from `BASE_SIZE` to fit exactly `total_winners` bitmap bits,
+    /// plus the `results_uri_ext` tail stored after the struct.
     #[account(
         mut,
         seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
         bump = resolved_game.bump,
+        realloc = 8 + ResolvedGame::size_for_winners(total_winners) + results_uri_ext.len(),
+        realloc::payer = authority,
+        realloc::zero = false,
         // Belt & suspenders: make sure stored epoch/tier match args
         constraint = resolved_game.epoch == epoch @ IC42NErrorCode::EpochMismatch,
         constraint = resolved_game.tier == tier   @ IC42NErrorCode::TierMismatch,
@@ -47,17 +62,12 @@ pub struct CompleteResolveGame<'info> {
     )]
     pub treasury: Account<'info, Treasury>,
 
-    /// Fee vault where protocol fees are accumulated
-    #[account(
-        mut,
-        address = config.fee_vault @ IC42NErrorCode::InvalidFeeVault
-    )]
-    pub fee_vault: SystemAccount<'info>,
-
-    /// Authority account that is allowed to resolve games
-    #[account(mut, address = config.authority @ IC42NErrorCode::Unauthorized)]
+    /// Account allowed to resolve games: `config.authority` or `config.resolver`.
+    #[account(mut, constraint = config.can_resolve(&authority.key()) @ IC42NErrorCode::Unauthorized)]
     pub authority: Signer<'info>,
-    // No system_program needed anymore – we’re not init-ing anything here.
+
+    /// Needed to fund the bitmap realloc
+    pub system_program: Program<'info, System>,
 }
 
 
@@ -69,7 +79,7 @@ pub struct CompleteResolveGame<'info> {
 ///
 /// RULES:
 ///   - If there ARE winners:
-///        • Charge protocol fee from the gross pot
+///        • Charge protocol fee from the gross pot (owed; see `collect_protocol_fee`)
 ///        • Remaining net pot = prize pool for claims
 ///        • No carry-over (current simple model)
 ///
@@ -78,8 +88,13 @@ pub struct CompleteResolveGame<'info> {
 ///        • The **entire gross pot** rolls over to the next epoch
 ///        • All bets + lamports are treated as carry-over
 ///
+///   - Fewer winners than `config.min_winners_for_fee`: paid out as above, but
+///     no fee is taken (the full gross pot is the prize pool).
+///
+///   - `config.max_fee_lamports` caps the fee; the rest goes to the prize pool.
+///
 /// SECURITY:
-///   - Only canonical Config PDA + authority can call this
+///   - Only canonical Config PDA + authority/resolver can call this
 ///   - Cannot resolve same epoch twice (ResolvedGame PDA uses `init` + seeds)
 ///   - Fee and net pot are recomputed on-chain and must match Lambda’s values
 /// ---------------------------------------------------------------------------
@@ -96,14 +111,40 @@ pub fn complete_resolve_game_handler(
     total_winners: u32,
     merkle_root: [u8; 32],
     results_uri: [u8; 128],
+
+    // Continuation of `results_uri` when it doesn't fit in 128 bytes
+    results_uri_ext: Vec<u8>,
 ) -> Result<()> {
+    // Results URI must be a non-empty, well-formed pointer. Its tail goes
+    // after the struct, which is sized by `total_winners` on either path.
+    validate_results_uri_with_ext(&results_uri, &results_uri_ext)?;
+    {
+        let game_info = ctx.accounts.resolved_game.to_account_info();
+        let mut data = game_info.try_borrow_mut_data()?;
+        ResolvedGame::write_results_uri_ext(&mut data, total_winners, &results_uri_ext)?;
+    }
+    ctx.accounts.resolved_game.results_uri_ext_len = results_uri_ext.len() as u8;
+
     // Shorthand for accounts
     let config    = &mut ctx.accounts.config;
     let live      = &mut ctx.accounts.live_feed;
     let treasury  = &mut ctx.accounts.treasury;
-    let fee_vault = &mut ctx.accounts.fee_vault;
     let game      = &mut ctx.accounts.resolved_game;
 
+    // A reverted game already split its pot and reset the LiveFeed;
+    // only its winner set is committed again.
+    if game.is_reverted() {
+        return recommit_reverted_game(
+            config,
+            game,
+            protocol_fee_lamports,
+            net_prize_pool,
+            total_winners,
+            merkle_root,
+            results_uri,
+        );
+    }
+
     // -----------------------------------------------------------------------
     // 0) Must have real action this epoch
     // -----------------------------------------------------------------------
@@ -129,10 +170,7 @@ pub fn complete_resolve_game_handler(
     // Tier must be valid + active in Config
     let tier_cfg = config.get_tier_settings(tier)?;
     require!(tier_cfg.is_active(), IC42NErrorCode::InactiveTier);
-
-    // Results URI must not be empty (all zero bytes)
-    let has_nonzero_uri_byte = results_uri.iter().any(|b| *b != 0);
-    require!(has_nonzero_uri_byte, IC42NErrorCode::EmptyResultsUri);
+    require!(!live.is_chain_cancelled(), IC42NErrorCode::ChainCancelled);
 
     // ResolvedGame must be in a RESOLVING state (single-writer lock)
     require!(
@@ -140,21 +178,47 @@ pub fn complete_resolve_game_handler(
         IC42NErrorCode::GameNotInResolvingState
     );
 
+    // 0 / secondary rollover numbers never pay out: the two resolution paths
+    // must not overlap, so winners on such a number are rejected outright.
+    let rolls_over = is_rollover_number(game.winning_number, live.secondary_rollover_number);
+    require!(
+        !rolls_over || total_winners == 0,
+        IC42NErrorCode::ShouldHaveRolledOver
+    );
+
+    // Each winning bet maps to at most one winner, so Lambda can never report
+    // more winners than there are bets covering the winning number.
+    let bets_on_winning_number = *live
+        .bets_per_number
+        .get(game.winning_number as usize)
+        .ok_or(IC42NErrorCode::InvalidWinningNumber)?;
+    require!(
+        total_winners as u64 <= bets_on_winning_number,
+        IC42NErrorCode::WinnerCountExceedsBets
+    );
+    require!(
+        total_winners <= config.max_winners_for(tier)?,
+        IC42NErrorCode::TooManyWinners
+    );
+
+    // The pot about to be committed must match the per-number stakes
+    live.reconcile()?;
+
     // -----------------------------------------------------------------------
     // 2) Recompute fee + net pot on-chain
     // -----------------------------------------------------------------------
     let gross_pot = live.total_lamports;
-    let fee_bps   = live.current_fee_bps as u64;
 
     let (expected_fee, expected_net) = if total_winners == 0 {
         // No winners → the protocol taker fee = 0, full pot carries over.
         (0u64, gross_pot)
+    } else if config.is_fee_waived(total_winners) {
+        // Thin game → winners still split the pot, but with no fee taken.
+        (0u64, gross_pot)
     } else {
-        // Winners exist → normal fee logic applies.
-        let fee = gross_pot
-            .checked_mul(fee_bps)
-            .ok_or(IC42NErrorCode::MathOverflow)?
-            / FEE_BPS_DENOM;
+        // Winners exist → normal fee logic applies, clamped to the
+        // absolute cap; the excess stays in the net pot.
+        let fee = config.cap_fee(bps_of(gross_pot, live.current_fee_bps, config.fee_rounding_mode())?);
 
         let net = gross_pot
             .checked_sub(fee)
@@ -186,8 +250,18 @@ pub fn complete_resolve_game_handler(
     // If there are NO winners, then we carry over the pot and bets
     // If there are winners, carry-over is 0. (reset)
     // -----------------------------------------------------------------------
+    // The house seed only backs a payout; without winners it is recovered
+    // (`recover_house_seed`) instead of rolling to players.
+    let house_seed_out: u64 = if total_winners == 0 {
+        live.house_seed_lamports
+    } else {
+        0
+    };
     let carry_over_lamports_for_next: u64 = if total_winners == 0 {
-        expected_net // == gross_pot in this branch
+        // == gross_pot in this branch, minus the house seed
+        expected_net
+            .checked_sub(house_seed_out)
+            .ok_or(IC42NErrorCode::MathOverflow)?
     } else {
         0
     };
@@ -197,27 +271,17 @@ pub fn complete_resolve_game_handler(
         IC42NErrorCode::InvalidCarryOver
     );
 
-    let carry_over_bets_for_next: u32 = if total_winners == 0 {
+    // Per-number state stays on the LiveFeed for a carry (see step 7); a new
+    // chain clears it.
+    let carry_over_bets_for_next: u64 = if total_winners == 0 {
         live.total_bets
     } else {
         0
     };
 
-    let carry_over_bets_per_number: [u32; 10] = if total_winners == 0 {
-        live.bets_per_number
-    } else {
-        [0u32; 10]
-    };
-
-    let carry_over_lamports_per_number: [u64; 10] = if total_winners == 0 {
-        live.lamports_per_number
-    } else {
-        [0u64; 10]
-    };
-
 
     // -----------------------------------------------------------------------
-    // 4) Move protocol fee (ONLY if there are winners)
+    // 4) Protocol fee stays in the treasury until `collect_protocol_fee`
     // -----------------------------------------------------------------------
     let treasury_balance = **treasury.to_account_info().lamports.borrow();
 
@@ -225,37 +289,35 @@ pub fn complete_resolve_game_handler(
         .checked_sub(expected_fee)
         .ok_or(IC42NErrorCode::MathOverflow)?;
 
-    // After fee (if any), treasury must still hold at least the net pot.
+    // Treasury must cover both the owed fee (if any) and the net pot.
+    if balance_after_fee < expected_net {
+        msg!(
+            "Treasury short by {} lamports (see get_treasury_health)",
+            expected_net - balance_after_fee
+        );
+    }
     require!(
         balance_after_fee >= expected_net,
         IC42NErrorCode::InsufficientTreasuryBalance
     );
 
-    if expected_fee > 0 {
-        **treasury
-            .to_account_info()
-            .try_borrow_mut_lamports()? -= expected_fee;
-        **fee_vault
-            .to_account_info()
-            .try_borrow_mut_lamports()? += expected_fee;
-
-        treasury.total_fees_withdrawn = treasury
-            .total_fees_withdrawn
-            .checked_add(expected_fee)
-            .ok_or(IC42NErrorCode::MathOverflow)?;
-    }
-
     // -----------------------------------------------------------------------
     // 5) Populate the ResolvedGame snapshot PDA (final state)
     // -----------------------------------------------------------------------
 
 
     // These are genuinely "final result" fields – it's correct to set them here.
-    game.total_bets          = live.total_bets;
-    game.carry_over_bets     = carry_over_bets_for_next;
+    game.total_bets          = ResolvedGame::bet_count(live.total_bets)?;
+    game.carry_over_bets     = ResolvedGame::bet_count(carry_over_bets_for_next)?;
 
     game.protocol_fee_lamports = expected_fee;
     game.fee_bps                = live.current_fee_bps;
+    game.fee_collected          = 0;
+    game.fee_collectable_slot   = if expected_fee > 0 {
+        clock.slot.saturating_add(config.fee_delay_slots)
+    } else {
+        0
+    };
     game.net_prize_pool        = expected_net;
 
     // Inbound from previous epoch(s)
@@ -265,17 +327,44 @@ pub fn complete_resolve_game_handler(
 
     game.total_winners   = total_winners;
     game.claimed_winners = 0;
+    game.house_seed_lamports = house_seed_out;
+
+    // No winners → same carry semantics as `complete_rollover_game`
+    game.rollover_reason = if total_winners > 0 {
+        RolloverReason::None.as_u8()
+    } else if rolls_over {
+        RolloverReason::RolloverNumber.as_u8()
+    } else {
+        RolloverReason::NoWinners.as_u8()
+    };
 
-    let bitmap_bytes = ((total_winners as usize) + 7) / 8;
+    let bitmap_bytes = ResolvedGame::bitmap_len(total_winners);
     require!(
         bitmap_bytes <= ResolvedGame::MAX_BITMAP_LEN,
         IC42NErrorCode::TooManyWinners
     );
+    // Allocated fresh: the game was loaded before the realloc, with an empty
+    // bitmap, and is serialized back from this Vec. Its compute cost at large
+    // winner counts hasn't been benchmarked.
     game.claimed_bitmap  = vec![0u8; bitmap_bytes];
 
     game.merkle_root = merkle_root;
+    game.merkle_scheme = config.merkle_scheme;
+    game.claim_deadline_epoch = if total_winners > 0 {
+        config.claim_deadline_for(current_epoch)
+    } else {
+        0
+    };
+    game.unclaimed_swept = 0;
+    // Claims open once the dispute window passes (see `revert_resolution`)
+    game.dispute_until_slot = if total_winners > 0 {
+        clock.slot.saturating_add(config.dispute_window_slots)
+    } else {
+        0
+    };
     game.results_uri = results_uri;
     game.resolved_at = resolved_ts;
+    game.version     = RESOLVED_GAME_VERSION;
 
     // Update processing metadata / state machine fields
     game.status            = GameStatus::Resolved as u8;
@@ -291,17 +380,108 @@ pub fn complete_resolve_game_handler(
     // If the winning number is 0 or is the current secondary rollover number,
     // then we keep the same rollover number. Else, we use the winning number as the new rollover number.
     let next_secondary_rollover: u8 = get_next_rollover_number(game.winning_number,live.secondary_rollover_number);
+    let base_fee_bps = config.fee_settings_for(tier)?.base_fee_bps;
+    let cutoff_slots = config.bet_cutoff_for(tier)?;
+
+    if total_winners == 0 {
+        // The player pot (== carry_over_lamports_for_next) stays on the feed
+        live.take_house_seed();
+        live.carry_forward(next_epoch, cutoff_slots, base_fee_bps);
+    } else {
+        live.start_new_chain(
+            next_epoch,
+            cutoff_slots,
+            next_secondary_rollover,
+            base_fee_bps
+        );
+    }
+
+    emit!(GameResolved {
+        epoch: game.epoch,
+        tier: game.tier,
+        winning_number: game.winning_number,
+        total_winners: game.total_winners,
+        protocol_fee_lamports: game.protocol_fee_lamports,
+        net_prize_pool: game.net_prize_pool,
+        carry_out_lamports: game.carry_out_lamports,
+        merkle_root: game.merkle_root,
+        rollover_reason: game.rollover_reason,
+    });
+
+    Ok(())
+}
+
+/// Re-commits the winner set of a game reverted via `revert_resolution`.
+///
+/// The pot split is final (the fee is owed and the LiveFeed reset), so the
+/// fee / net pot must match the stored values and there must be winners on
+/// the (possibly corrected) `winning_number`. Claims are locked for a fresh
+/// dispute window, and the claim deadline and fee delay restart from now.
+fn recommit_reverted_game(
+    config: &Config,
+    game: &mut ResolvedGame,
+    protocol_fee_lamports: u64,
+    net_prize_pool: u64,
+    total_winners: u32,
+    merkle_root: [u8; 32],
+    results_uri: [u8; 128],
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(total_winners > 0, IC42NErrorCode::RevertedGameNeedsWinners);
+    require!(
+        total_winners <= game.total_bets,
+        IC42NErrorCode::WinnerCountExceedsBets
+    );
+    require!(
+        total_winners <= config.max_winners_for(game.tier)?,
+        IC42NErrorCode::TooManyWinners
+    );
+    require_eq!(
+        game.protocol_fee_lamports,
+        protocol_fee_lamports,
+        IC42NErrorCode::InvalidFee
+    );
+    require_eq!(
+        game.net_prize_pool,
+        net_prize_pool,
+        IC42NErrorCode::InvalidPotBreakdown
+    );
 
-    live.reset_for_new_epoch(
-        next_epoch,
-        config.bet_cutoff_slots,
-        carry_over_lamports_for_next,
-        carry_over_bets_for_next,
-        carry_over_lamports_per_number,
-        carry_over_bets_per_number,
-        next_secondary_rollover,
-        config.base_fee_bps
+    let bitmap_bytes = ResolvedGame::bitmap_len(total_winners);
+    require!(
+        bitmap_bytes <= ResolvedGame::MAX_BITMAP_LEN,
+        IC42NErrorCode::TooManyWinners
     );
+    game.claimed_bitmap  = vec![0u8; bitmap_bytes];
+
+    game.total_winners   = total_winners;
+    game.claimed_winners = 0;
+    game.merkle_root     = merkle_root;
+    game.results_uri     = results_uri;
+    game.dispute_until_slot = clock.slot.saturating_add(config.dispute_window_slots);
+    game.claim_deadline_epoch = config.claim_deadline_for(clock.epoch);
+    game.fee_collectable_slot = if game.protocol_fee_lamports > 0 {
+        clock.slot.saturating_add(config.fee_delay_slots)
+    } else {
+        0
+    };
+
+    game.status            = GameStatus::Resolved as u8;
+    game.last_updated_slot = clock.slot;
+    game.last_updated_ts   = clock.unix_timestamp;
+
+    emit!(GameResolved {
+        epoch: game.epoch,
+        tier: game.tier,
+        winning_number: game.winning_number,
+        total_winners: game.total_winners,
+        protocol_fee_lamports: game.protocol_fee_lamports,
+        net_prize_pool: game.net_prize_pool,
+        carry_out_lamports: game.carry_out_lamports,
+        merkle_root: game.merkle_root,
+        rollover_reason: game.rollover_reason,
+    });
 
     Ok(())
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use crate::errors::IC42NErrorCode;
 use crate::state::*;
 use crate::constants::*;
+use crate::utils::betting::{epoch_fully_settled, rng_slot_fresh};
+use crate::utils::resolve::{fallback_winning_number, is_drawn_number, verify_fallback_rng};
 
 // -----------------------------------------------------------------------------
 // InitResolvedGame
@@ -12,6 +15,9 @@ use crate::constants::*;
 //   - Ensures tier is active
 //   - Creates the ResolvedGame PDA and sets status = Processing
 //
+// The account is created with `BASE_SIZE` only (empty bitmap); it is grown
+// to fit the bitmap in `complete_resolve_game` once the winner count is known.
+//
 // Called once per (epoch, tier) after the epoch ends, typically by your
 // cron/worker when it detects a new epoch that needs resolution.
 // -----------------------------------------------------------------------------
@@ -35,13 +41,14 @@ pub struct InitResolvedGame<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + ResolvedGame::SIZE,
+        space = 8 + ResolvedGame::BASE_SIZE,
         seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
         bump
     )]
     pub resolved_game: Account<'info, ResolvedGame>,
 
-    #[account(mut, address = config.authority @ IC42NErrorCode::Unauthorized)]
+    /// Account allowed to resolve games: `config.authority` or `config.resolver`.
+    #[account(mut, constraint = config.can_resolve(&authority.key()) @ IC42NErrorCode::Unauthorized)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
@@ -71,6 +78,7 @@ pub fn init_resolved_game_handler(
 
     // Epoch must already be completed
     require!(live.epoch < current_epoch, IC42NErrorCode::EpochNotComplete);
+    require!(epoch_fully_settled(live.epoch), IC42NErrorCode::EpochNotComplete);
 
     // Tier consistency
     require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
@@ -79,12 +87,29 @@ pub fn init_resolved_game_handler(
     let tier_cfg = config.get_tier_settings(tier)?;
     require!(tier_cfg.is_active(), IC42NErrorCode::InactiveTier);
 
-    // The winning number must be valid
-    require!(winning_number <= 9, IC42NErrorCode::InvalidWinningNumber);
+    // A cancelled chain is settled by refunds, never resolved
+    require!(!live.is_chain_cancelled(), IC42NErrorCode::ChainCancelled);
+
+    // Normal resolution needs a drawn number; 0 only exists as the
+    // explicit rollover signal on `complete_rollover_game`
+    require!(is_drawn_number(winning_number), IC42NErrorCode::InvalidWinningNumber);
+
+    // RNG must come from the end of the epoch being resolved
+    require!(
+        rng_slot_fresh(epoch, rng_epoch_slot_used, config.rng_max_age_slots),
+        IC42NErrorCode::StaleRngSlot
+    );
 
     // There must be bets to init this game
     require!(live.total_bets > 0 && live.total_lamports > 0, IC42NErrorCode::NoBetsToResolve);
 
+    // Tiny pots accumulate via rollover, unless the chain can't roll over anymore
+    require!(
+        !config.is_pot_below_resolve_threshold(live.total_lamports)
+            || !config.is_rollover_allowed(live.epochs_carried_over),
+        IC42NErrorCode::PotBelowResolveThreshold
+    );
+
     // ─────────────────────────────────────────────────────────────
     // 2) Initialize ResolvedGame identity + state-machine fields
     // ─────────────────────────────────────────────────────────────
@@ -127,6 +152,63 @@ pub fn init_resolved_game_handler(
     game.first_epoch_in_chain = live.first_epoch_in_chain;
     game.rollover_reason = RolloverReason::None.as_u8();
     game.secondary_rollover_number = live.secondary_rollover_number;
-    game._reserved = [0u8; 12];
+    game.merkle_scheme = MerkleScheme::Indexed.as_u8();
+    game.claim_deadline_epoch = 0;
+    game.unclaimed_swept = 0;
+    game.dispute_until_slot = 0;
+    game.fee_collectable_slot = 0;
+    game.fee_collected = 0;
+    game.house_seed_lamports = 0;
+    game.results_uri_ext_len = 0;
+    game.frozen = 0;
     Ok(())
+}
+
+/// `InitResolvedGame` plus the SlotHashes sysvar the fallback number is
+/// drawn from.
+#[derive(Accounts)]
+pub struct ResolveWithFallbackRng<'info> {
+    pub init: InitResolvedGame<'info>,
+
+    /// CHECK: Address-checked SlotHashes sysvar; read by `verify_fallback_rng`.
+    #[account(address = slot_hashes::ID @ IC42NErrorCode::InvalidInput)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+/// `init_resolved_game` when the regular RNG source is unavailable: the
+/// winning number is not chosen by the caller but derived from the chain's
+/// `first_epoch_in_chain`, the tier and `rng_blockhash_used` via
+/// `fallback_winning_number`, so it can be reproduced from the stored game.
+///
+/// The RNG slot and hash must be the SlotHashes entry ending the epoch, so
+/// this has to run while that entry is still in the sysvar (~512 slots).
+pub fn resolve_with_fallback_rng_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ResolveWithFallbackRng<'info>>,
+    epoch: u64,
+    tier: u8,
+    rng_epoch_slot_used: u64,
+    rng_blockhash_used: [u8; 32],
+) -> Result<()> {
+    let last_epoch_slot = EpochSchedule::get()?.get_last_slot_in_epoch(epoch);
+    verify_fallback_rng(
+        &ctx.accounts.slot_hashes.try_borrow_data()?,
+        last_epoch_slot,
+        rng_epoch_slot_used,
+        &rng_blockhash_used,
+    )?;
+
+    let winning_number = fallback_winning_number(
+        ctx.accounts.init.live_feed.first_epoch_in_chain,
+        tier,
+        &rng_blockhash_used,
+    );
+
+    init_resolved_game_handler(
+        Context::new(ctx.program_id, &mut ctx.accounts.init, ctx.remaining_accounts, ctx.bumps.init),
+        epoch,
+        tier,
+        winning_number,
+        rng_epoch_slot_used,
+        rng_blockhash_used,
+    )
 }
\ No newline at end of file
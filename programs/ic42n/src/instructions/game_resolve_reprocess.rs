@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::errors::IC42NErrorCode;
 use crate::state::*;
+use crate::utils::betting::epoch_fully_settled;
 
 /// ---------------------------------------------------------------------------
 /// BeginResolveGame
@@ -27,7 +28,6 @@ pub struct ReprocessResolveGame<'info> {
         mut,
         seeds = [Config::SEED],
         bump = config.bump,
-        has_one = authority @ IC42NErrorCode::Unauthorized
     )]
     pub config: Account<'info, Config>,
 
@@ -47,8 +47,8 @@ pub struct ReprocessResolveGame<'info> {
     )]
     pub resolved_game: Account<'info, ResolvedGame>,
 
-    /// Authority allowed to kick off resolution for this program
-    #[account(mut, address = config.authority @ IC42NErrorCode::Unauthorized)]
+    /// Authority or resolver allowed to kick off resolution for this program.
+    #[account(mut, constraint = config.can_resolve(&authority.key()) @ IC42NErrorCode::Unauthorized)]
     pub authority: Signer<'info>,
 }
 
@@ -74,6 +74,7 @@ pub fn reprocessing_resolve_game_handler(
 
     // Epoch must already be completed
     require!(live.epoch < current_epoch, IC42NErrorCode::EpochNotComplete);
+    require!(epoch_fully_settled(live.epoch), IC42NErrorCode::EpochNotComplete);
 
     // Tier consistency
     require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use crate::constants::{FEE_BPS_DENOM, RESOLVED_GAME_VERSION};
+use crate::constants::RESOLVED_GAME_VERSION;
 use crate::errors::IC42NErrorCode;
-use crate::state::{Config, GameStatus, LiveFeed, ResolvedGame, RolloverReason};
+use crate::events::GameResolved;
+use crate::state::{Config, GameStatus, LiveFeed, MerkleScheme, ResolvedGame, RolloverReason};
 use crate::state::treasury::Treasury;
-use crate::utils::resolve::{get_next_rollover_number, next_fee_bps_on_rollover};
+use crate::utils::betting::{epoch_fully_settled, rng_slot_fresh};
+use crate::utils::resolve::{get_next_rollover_number, is_rollover_number, next_fee_bps_on_rollover};
 
 #[derive(Accounts)]
 #[instruction(epoch: u64, tier: u8)]
@@ -13,7 +15,6 @@ pub struct ResolvedGameRollover<'info> {
         mut,
         seeds = [Config::SEED],
         bump = config.bump,
-        has_one = authority @ IC42NErrorCode::Unauthorized
     )]
     pub config: Account<'info, Config>,
 
@@ -27,7 +28,7 @@ pub struct ResolvedGameRollover<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + ResolvedGame::SIZE,
+        space = 8 + ResolvedGame::BASE_SIZE,
         seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
         bump
     )]
@@ -41,7 +42,8 @@ pub struct ResolvedGameRollover<'info> {
     pub treasury: Account<'info, Treasury>,
 
 
-    #[account(mut, address = config.authority @ IC42NErrorCode::Unauthorized)]
+    /// Account allowed to resolve games: `config.authority` or `config.resolver`.
+    #[account(mut, constraint = config.can_resolve(&authority.key()) @ IC42NErrorCode::Unauthorized)]
     pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
@@ -53,6 +55,7 @@ pub fn complete_rollover_game_handler(
     epoch: u64,
     tier: u8,
     winning_number: u8,
+    is_rollover: bool,
     rng_epoch_slot_used: u64,
     rng_blockhash_used: [u8; 32],
 ) -> Result<()> {
@@ -66,22 +69,31 @@ pub fn complete_rollover_game_handler(
     // It can happen if
     // - The winning number is 0 or the secondary rollover number
     // - There are no winners.
+    // - The pot is below `config.min_resolve_lamports`
+    //
+    // `is_rollover` marks the draw itself as the rollover signal (0); any
+    // other draw must be a real number 1..=9.
     let w = winning_number as usize;
     require!(w < 10, IC42NErrorCode::InvalidWinningNumber);
+    require!(
+        is_rollover == (winning_number == 0),
+        IC42NErrorCode::InvalidWinningNumber
+    );
 
-    let is_rollover_number = winning_number == 0 || winning_number == live.secondary_rollover_number;
+    let is_rollover_number = is_rollover_number(winning_number, live.secondary_rollover_number);
     let has_winners = live.bets_per_number[w] > 0;
+    let pot_below_threshold = config.is_pot_below_resolve_threshold(live.total_lamports);
     require!(
-        is_rollover_number || !has_winners,
+        is_rollover_number || !has_winners || pot_below_threshold,
         IC42NErrorCode::CarryNotAllowed
     );
 
     let rollover_reason = if is_rollover_number {
         RolloverReason::RolloverNumber
-    } else {
-        // since require! ensures (is_rollover_number || !has_winners),
-        // and we're in the else branch => must be !has_winners
+    } else if !has_winners {
         RolloverReason::NoWinners
+    } else {
+        RolloverReason::PotBelowThreshold
     };
 
 
@@ -97,35 +109,43 @@ pub fn complete_rollover_game_handler(
     // Epoch/tier alignment
     require_eq!(live.epoch, epoch, IC42NErrorCode::EpochMismatch);
     require!(live.epoch < current_epoch, IC42NErrorCode::EpochNotComplete);
+    require!(epoch_fully_settled(live.epoch), IC42NErrorCode::EpochNotComplete);
     require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
+    require!(
+        rng_slot_fresh(epoch, rng_epoch_slot_used, config.rng_max_age_slots),
+        IC42NErrorCode::StaleRngSlot
+    );
 
     let tier_cfg = config.get_tier_settings(tier)?;
     require!(tier_cfg.is_active(), IC42NErrorCode::InactiveTier);
+    require!(!live.is_chain_cancelled(), IC42NErrorCode::ChainCancelled);
 
+    // Chain must not have hit the rollover cap; past it the game has to be
+    // resolved with winners instead.
+    require!(
+        config.is_rollover_allowed(live.epochs_carried_over),
+        IC42NErrorCode::MaxRolloversExceeded
+    );
+
+
+    // The pot about to be carried must match the per-number stakes
+    live.reconcile()?;
 
     // Gross pot is everything in live.total_lamports.
     // In rollover: no fee, full pot carries forward.
     let gross_pot = live.total_lamports;
-    let fee_bps   = live.current_fee_bps as u64;
-
-    let fee = gross_pot
-        .checked_mul(fee_bps)
-        .ok_or(IC42NErrorCode::MathOverflow)?
-        / FEE_BPS_DENOM;
-
-    let expected_net = gross_pot
-        .checked_sub(fee)
-        .ok_or(IC42NErrorCode::MathOverflow)?;
 
     // Treasury must be able to cover the full pot.
     let treasury_balance = **treasury.to_account_info().lamports.borrow();
     require!(
-        treasury_balance >= expected_net,
+        treasury_balance >= gross_pot,
         IC42NErrorCode::InsufficientTreasuryBalance
     );
 
-    // Full gross pot gets carried over
-    let carry_over_lamports_for_next = gross_pot;
+    // Full player pot gets carried over; the house seed is left for
+    // `recover_house_seed` instead of rolling to players
+    let house_seed = live.take_house_seed();
+    let carry_over_lamports_for_next = live.total_lamports;
     let carry_over_bets_for_next     = live.total_bets;
     let carry_over_bets_per_number   = live.bets_per_number;
     let carry_over_lamports_per_number = live.lamports_per_number;
@@ -145,13 +165,14 @@ pub fn complete_rollover_game_handler(
     game.last_updated_slot = clock.slot;
     game.last_updated_ts   = resolved_ts;
 
-    game.carry_over_bets       = carry_over_bets_for_next;
-    game.total_bets            = live.total_bets;
+    game.carry_over_bets       = ResolvedGame::bet_count(carry_over_bets_for_next)?;
+    game.total_bets            = ResolvedGame::bet_count(live.total_bets)?;
     game.carry_in_lamports  = live.carried_over_lamports;
     game.carry_out_lamports = carry_over_lamports_for_next;
-    game.protocol_fee_lamports = fee;
+    // Nothing is charged on a carried pot, so no fee is ever owed for it
+    game.protocol_fee_lamports = 0;
     game.fee_bps = live.current_fee_bps;
-    game.net_prize_pool        = expected_net;
+    game.net_prize_pool        = gross_pot;
     game.total_winners   = 0;
     game.claimed_winners = 0;
     game.resolved_at      = resolved_ts;
@@ -164,7 +185,14 @@ pub fn complete_rollover_game_handler(
     game.claimed_lamports = 0;
     game.rollover_reason = rollover_reason.as_u8();
     game.secondary_rollover_number = live.secondary_rollover_number;
-    game._reserved = [0u8; 12];
+    game.merkle_scheme = MerkleScheme::Indexed.as_u8();
+    game.claim_deadline_epoch = 0;
+    game.unclaimed_swept = 0;
+    game.dispute_until_slot = 0;
+    game.fee_collectable_slot = 0;
+    game.fee_collected = 0;
+    game.house_seed_lamports = house_seed;
+    game.results_uri_ext_len = 0;
 
 
     // If the winning number is 0 or is the current secondary rollover number,
@@ -172,29 +200,42 @@ pub fn complete_rollover_game_handler(
     let next_secondary_rollover: u8 = get_next_rollover_number(winning_number,live.secondary_rollover_number);
 
     // The fee only decreases on rollover-number carry
+    let tier_fees = config.fee_settings_for(tier)?;
     let next_fee_bps = if is_rollover_number {
         next_fee_bps_on_rollover(
             live.current_fee_bps,
-            config.rollover_fee_step_bps,
-            config.min_fee_bps,
+            tier_fees.rollover_fee_step_bps,
+            tier_fees.min_fee_bps,
         )
     } else {
         // no-winners carry: keep the current fee (but still enforce >= min)
-        live.current_fee_bps.max(config.min_fee_bps)
+        live.current_fee_bps.max(tier_fees.min_fee_bps)
     };
 
     // Reset LiveFeed for the next epoch using your existing helper.
     let next_epoch = live.epoch + 1;
     live.reset_for_new_epoch(
         next_epoch,
-        config.bet_cutoff_slots,
+        config.bet_cutoff_for(tier)?,
         carry_over_lamports_for_next,
         carry_over_bets_for_next,
         carry_over_lamports_per_number,
         carry_over_bets_per_number,
         next_secondary_rollover,
         next_fee_bps
-    );
+    )?;
+
+    emit!(GameResolved {
+        epoch: game.epoch,
+        tier: game.tier,
+        winning_number: game.winning_number,
+        total_winners: game.total_winners,
+        protocol_fee_lamports: game.protocol_fee_lamports,
+        net_prize_pool: game.net_prize_pool,
+        carry_out_lamports: game.carry_out_lamports,
+        merkle_root: game.merkle_root,
+        rollover_reason: game.rollover_reason,
+    });
 
     Ok(())
 }
\ No newline at end of file
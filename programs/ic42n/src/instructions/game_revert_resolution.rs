@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::utils::resolve::is_rollover_number;
+
+/// Reverts a winning resolution while its dispute window is still open, or
+/// any time while the game is frozen (see `freeze_game`) as long as nothing
+/// was claimed yet.
+///
+/// The game goes back to `Processing` with its Merkle root cleared, so no
+/// claim can land until `complete_resolve_game` re-commits a corrected
+/// winner set. `winning_number` replaces a bad number (pass the stored one
+/// to keep it); it must still pay out, since the pot can no longer roll over.
+/// The pot split stays as-is: the protocol fee remains owed (it cannot be
+/// collected while the game is unresolved) and the LiveFeed has already
+/// started the next chain.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct RevertResolution<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = resolved_game.bump,
+        constraint = resolved_game.epoch == epoch @ IC42NErrorCode::EpochMismatch,
+        constraint = resolved_game.tier == tier   @ IC42NErrorCode::TierMismatch,
+    )]
+    pub resolved_game: Account<'info, ResolvedGame>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn revert_resolution_handler(
+    ctx: Context<RevertResolution>,
+    _epoch: u64,
+    _tier: u8,
+    winning_number: u8,
+) -> Result<()> {
+    let game  = &mut ctx.accounts.resolved_game;
+    let clock = Clock::get()?;
+
+    require!(
+        game.status == GameStatus::Resolved as u8,
+        IC42NErrorCode::GameNotResolved
+    );
+    require!(
+        game.is_in_dispute_window(clock.slot) || game.is_frozen(),
+        IC42NErrorCode::DisputeWindowClosed
+    );
+    // Claims are locked during the window; a game frozen after it may have
+    // paid some already, and those can't be taken back
+    require!(game.claimed_winners == 0, IC42NErrorCode::AlreadyClaimed);
+
+    require!(winning_number <= 9, IC42NErrorCode::InvalidWinningNumber);
+    require!(
+        !is_rollover_number(winning_number, game.secondary_rollover_number),
+        IC42NErrorCode::ShouldHaveRolledOver
+    );
+
+    game.winning_number = winning_number;
+    game.merkle_root = [0u8; 32];
+    game.dispute_until_slot = 0;
+    game.attempt_count = game.attempt_count.saturating_add(1);
+
+    game.status            = GameStatus::Processing as u8;
+    game.last_updated_slot = clock.slot;
+    game.last_updated_ts   = clock.unix_timestamp;
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::treasury::Treasury;
+use crate::utils::transfers::require_treasury_can_pay;
+
+/// Moves expired, unclaimed winnings of a resolved game to the fee vault.
+///
+/// Only allowed once the game's `claim_deadline_epoch` has passed. After a
+/// sweep the game is marked so any further claim reverts with
+/// `ClaimWindowClosed`. Frozen games are held until unfrozen.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct SweepUnclaimed<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = resolved_game.bump,
+        constraint = resolved_game.epoch == epoch @ IC42NErrorCode::EpochMismatch,
+        constraint = resolved_game.tier == tier   @ IC42NErrorCode::TierMismatch,
+    )]
+    pub resolved_game: Account<'info, ResolvedGame>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        address = config.fee_vault @ IC42NErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn sweep_unclaimed_handler(
+    ctx: Context<SweepUnclaimed>,
+    _epoch: u64,
+    _tier: u8,
+) -> Result<()> {
+    let game      = &mut ctx.accounts.resolved_game;
+    let treasury  = &mut ctx.accounts.treasury;
+    let fee_vault = &mut ctx.accounts.fee_vault;
+
+    let current_epoch = Clock::get()?.epoch;
+
+    require!(
+        !ctx.accounts.config.are_fee_withdrawals_paused(),
+        IC42NErrorCode::WithdrawPaused
+    );
+
+    require!(
+        game.status == GameStatus::Resolved as u8,
+        IC42NErrorCode::GameNotResolved
+    );
+    require!(game.total_winners > 0, IC42NErrorCode::ClaimNotAllowed);
+    game.assert_not_frozen()?;
+
+    // Games without a deadline never expire
+    require!(game.claim_deadline_epoch != 0, IC42NErrorCode::ClaimWindowStillOpen);
+    require!(game.unclaimed_swept == 0, IC42NErrorCode::ClaimWindowClosed);
+    require!(
+        !game.is_claim_window_open(current_epoch),
+        IC42NErrorCode::ClaimWindowStillOpen
+    );
+
+    let unclaimed = game
+        .net_prize_pool
+        .checked_sub(game.claimed_lamports)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+
+    // Close the claim window before moving lamports
+    game.unclaimed_swept = 1;
+
+    if unclaimed > 0 {
+        let treasury_balance = **treasury.to_account_info().lamports.borrow();
+        require_treasury_can_pay(treasury_balance, unclaimed)?;
+
+        treasury.total_swept_lamports = treasury
+            .total_swept_lamports
+            .checked_add(unclaimed)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        treasury.release_obligation(unclaimed);
+
+        **treasury.to_account_info().try_borrow_mut_lamports()? -= unclaimed;
+        **fee_vault.to_account_info().try_borrow_mut_lamports()? += unclaimed;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::constants::{TIER1_MAX, TIER1_MIN, TIER2_MAX, TIER2_MIN, TIER3_MAX, TIER3_MIN};
+use crate::constants::{
+    DEFAULT_WELCOME_TICKETS, TIER1_MAX, TIER1_MIN, TIER2_MAX, TIER2_MIN, TIER3_MAX, TIER3_MIN,
+};
 use crate::state::*;
-use crate::state::tiers::{TierSettings};
+use crate::state::tiers::{TierFeeSettings, TierSettings};
 use crate::state::treasury::Treasury;
 
 #[derive(Accounts)]
@@ -74,7 +76,36 @@ pub fn initialize_handler(ctx: Context<Initialize>, fee_bps: u16, tier: u8) -> R
     cfg.bump = ctx.bumps.config;
     cfg.min_fee_bps = 200;
     cfg.rollover_fee_step_bps = 100;
-    cfg._reserved = [0; 16];
+    cfg.max_rollovers = 0;
+    cfg.max_predictions_per_epoch = 0;
+    cfg.emergency_refunds_open = 0;
+    cfg.merkle_scheme = MerkleScheme::Indexed.as_u8();
+    cfg.claim_window_epochs = 0;
+    cfg.allow_type_switch = 0;
+    cfg._reserved = [0; 8];
+    cfg.pending_fee_vault = Pubkey::default();
+    cfg.pending_fee_vault_epoch = 0;
+    cfg.pending_authority = Pubkey::default();
+    cfg.resolver = Pubkey::default();
+    cfg.min_resolve_lamports = 0;
+    cfg.rng_max_age_slots = 0;
+    cfg.dispute_window_slots = 0;
+    cfg.fee_delay_slots = 0;
+    cfg.tier_fees = [TierFeeSettings::default(); 5];
+    cfg.tier_max_winners = [0; 5];
+    cfg.strict_cutoff = 1;
+    cfg.tier_bet_cutoff_slots = [0; 5];
+    cfg.welcome_tickets = DEFAULT_WELCOME_TICKETS;
+    cfg.pause_claims = 0;
+    cfg.pause_fee_withdrawals = 0;
+    cfg.min_winners_for_fee = 0;
+    cfg.fee_rounding = FeeRounding::Floor.as_u8();
+    cfg.tier_max_total_exposure = [0; 5];
+    cfg.tier_changes_disabled = [0; 5];
+    cfg.max_active_tiers = 5;
+    cfg.tier_change_ticket_cost = [0; 5];
+    cfg.max_fee_lamports = 0;
+    cfg.verbose_logging = 0;
 
     cfg.tiers = [
         // Tier 1: 0.01 – 1 SOL
@@ -87,7 +118,9 @@ pub fn initialize_handler(ctx: Context<Initialize>, fee_bps: u16, tier: u8) -> R
             ticket_reward_bps: 1_000,   // 10% of losers
             ticket_reward_max: 100,     // cap 100 recipients
             tickets_per_recipient: 1,
-            _reserved: [0; 10],
+            max_lamports_per_number: 0,
+            leaderboard_enabled: 0,
+            paused: 0,
 
         },
         // Tier 2: 1 – 10 SOL
@@ -100,7 +133,9 @@ pub fn initialize_handler(ctx: Context<Initialize>, fee_bps: u16, tier: u8) -> R
             ticket_reward_bps: 1_000,   // 10% of losers
             ticket_reward_max: 100,     // cap 100 recipients
             tickets_per_recipient: 1,
-            _reserved: [0; 10],
+            max_lamports_per_number: 0,
+            leaderboard_enabled: 0,
+            paused: 0,
         },
         // Tier 3: 10 – 100 SOL
         TierSettings {
@@ -112,7 +147,9 @@ pub fn initialize_handler(ctx: Context<Initialize>, fee_bps: u16, tier: u8) -> R
             ticket_reward_bps: 1_000,   // 10% of losers
             ticket_reward_max: 100,     // cap 100 recipients
             tickets_per_recipient: 1,
-            _reserved: [0; 10],
+            max_lamports_per_number: 0,
+            leaderboard_enabled: 0,
+            paused: 0,
         },
         // Tier 4: placeholder / inactive tier
         TierSettings {
@@ -124,7 +161,9 @@ pub fn initialize_handler(ctx: Context<Initialize>, fee_bps: u16, tier: u8) -> R
             ticket_reward_bps: 0,
             ticket_reward_max: 0,
             tickets_per_recipient: 1,
-            _reserved: [0; 10],
+            max_lamports_per_number: 0,
+            leaderboard_enabled: 0,
+            paused: 0,
         },
         // Tier 5: placeholder / inactive tier
         TierSettings {
@@ -136,7 +175,9 @@ pub fn initialize_handler(ctx: Context<Initialize>, fee_bps: u16, tier: u8) -> R
             ticket_reward_bps: 0,
             ticket_reward_max: 0,
             tickets_per_recipient: 1,
-            _reserved: [0; 10],
+            max_lamports_per_number: 0,
+            leaderboard_enabled: 0,
+            paused: 0,
         },
     ];
 
@@ -163,8 +204,11 @@ pub fn initialize_handler(ctx: Context<Initialize>, fee_bps: u16, tier: u8) -> R
     treasury.total_in_lamports = 0;
     treasury.total_out_lamports = 0;
     treasury.total_fees_withdrawn = 0;
-    treasury.version = 1;
-    treasury._reserved = [0; 32];
+    treasury.total_refunded_lamports = 0;
+    treasury.total_swept_lamports = 0;
+    treasury.obligations_lamports = 0;
+    treasury.version = Treasury::OBLIGATIONS_VERSION;
+    treasury._reserved = [0; 8];
 
     Ok(())
 }
\ No newline at end of file
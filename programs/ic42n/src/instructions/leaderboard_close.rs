@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::leaderboard::Leaderboard;
+
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct CloseLeaderboard<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Leaderboard::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = leaderboard.bump,
+        close = authority
+    )]
+    pub leaderboard: Box<Account<'info, Leaderboard>>,
+
+    #[account(mut, address = config.authority @ IC42NErrorCode::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+/// Reclaims rent from a past epoch's leaderboard.
+pub fn close_leaderboard_handler(
+    _ctx: Context<CloseLeaderboard>,
+    epoch: u64,
+    _tier: u8,
+) -> Result<()> {
+    require!(epoch < Clock::get()?.epoch, IC42NErrorCode::EpochNotComplete);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::leaderboard::{Leaderboard, LeaderboardEntry};
+
+/// Creates the leaderboard for (epoch, tier). Called by the worker at the
+/// start of each epoch for tiers with `leaderboard_enabled`.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct InitLeaderboard<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Leaderboard::SIZE,
+        seeds = [Leaderboard::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump
+    )]
+    pub leaderboard: Box<Account<'info, Leaderboard>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_leaderboard_handler(
+    ctx: Context<InitLeaderboard>,
+    epoch: u64,
+    tier: u8,
+) -> Result<()> {
+    let tier_settings = ctx.accounts.config.get_tier_settings(tier)?;
+    require!(
+        tier_settings.is_leaderboard_enabled(),
+        IC42NErrorCode::LeaderboardDisabled
+    );
+
+    let board = &mut ctx.accounts.leaderboard;
+    board.epoch = epoch;
+    board.tier = tier;
+    board.bump = ctx.bumps.leaderboard;
+    board.len = 0;
+    board.entries = [LeaderboardEntry::default(); Leaderboard::MAX_ENTRIES];
+    board._reserved = [0u8; 16];
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,10 +1,19 @@
 // src/instructions/mod.rs
 pub mod initialize;
 pub mod config_update;
+pub mod config_propose_fee_vault;
+pub mod config_accept_fee_vault;
+pub mod config_upgrade;
+pub mod config_propose_authority;
+pub mod config_accept_authority;
 pub mod tier_init;
+pub mod tier_init_batch;
 pub mod tier_update_active;
 pub mod tier_reset;
 pub mod tier_close;
+pub mod tier_cancel;
+pub mod tier_migrate;
+pub mod tier_set_rollover;
 pub mod game_resolve_init;
 pub mod game_resolve_reprocess;
 pub mod game_resolve_complete;
@@ -12,18 +21,55 @@ pub mod game_resolve_rollover;
 pub mod ticket_award_manual;
 pub mod ticket_award_auto;
 pub mod profile_close;
+pub mod profile_migrate;
 pub mod game_close;
+pub mod game_close_batch;
+pub mod game_sweep_unclaimed;
+pub mod game_revert_resolution;
+pub mod game_collect_fee;
+pub mod game_assign_leaf_indices;
+pub mod game_recover_house_seed;
+pub mod game_freeze;
+pub mod tier_seed_treasury;
+pub mod treasury_sweep_dust;
+pub mod treasury_upgrade;
 pub mod prediction_place;
 pub mod prediction_increase;
 pub mod prediction_change_number;
+pub mod prediction_change_coverage;
+pub mod prediction_reroll;
 pub mod prediction_claim;
+pub mod prediction_claim_multiple;
+pub mod prediction_emergency_refund;
+pub mod prediction_close;
+pub mod prediction_migrate;
+pub mod leaderboard_init;
+pub mod leaderboard_close;
+pub mod view_fee_schedule;
+pub mod view_treasury_health;
+pub mod view_claim_leaf;
+pub mod view_game_claim_status;
+pub mod view_canonical_choice;
+pub mod view_prediction_eligibility;
+pub mod view_treasury_stats;
+pub mod view_chain_lineage;
+pub mod view_claim_status_batch;
 
 
 pub use initialize::*;
 pub use config_update::*;
+pub use config_propose_fee_vault::*;
+pub use config_accept_fee_vault::*;
+pub use config_upgrade::*;
+pub use config_propose_authority::*;
+pub use config_accept_authority::*;
 pub use tier_init::*;
+pub use tier_init_batch::*;
 pub use tier_update_active::*;
 pub use tier_close::*;
+pub use tier_cancel::*;
+pub use tier_migrate::*;
+pub use tier_set_rollover::*;
 pub use tier_reset::*;
 pub use game_resolve_init::*;
 pub use game_resolve_reprocess::*;
@@ -32,8 +78,36 @@ pub use game_resolve_rollover::*;
 pub use ticket_award_manual::*;
 pub use ticket_award_auto::*;
 pub use profile_close::*;
+pub use profile_migrate::*;
 pub use game_close::*;
+pub use game_close_batch::*;
+pub use game_sweep_unclaimed::*;
+pub use game_revert_resolution::*;
+pub use game_collect_fee::*;
+pub use game_assign_leaf_indices::*;
+pub use game_recover_house_seed::*;
+pub use game_freeze::*;
+pub use tier_seed_treasury::*;
+pub use treasury_sweep_dust::*;
+pub use treasury_upgrade::*;
 pub use prediction_place::*;
 pub use prediction_increase::*;
 pub use prediction_change_number::*;
+pub use prediction_change_coverage::*;
+pub use prediction_reroll::*;
 pub use prediction_claim::*;
+pub use prediction_claim_multiple::*;
+pub use prediction_emergency_refund::*;
+pub use prediction_close::*;
+pub use prediction_migrate::*;
+pub use leaderboard_init::*;
+pub use leaderboard_close::*;
+pub use view_fee_schedule::*;
+pub use view_treasury_health::*;
+pub use view_claim_leaf::*;
+pub use view_game_claim_status::*;
+pub use view_canonical_choice::*;
+pub use view_prediction_eligibility::*;
+pub use view_treasury_stats::*;
+pub use view_chain_lineage::*;
+pub use view_claim_status_batch::*;
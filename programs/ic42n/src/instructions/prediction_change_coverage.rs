@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::player_profile::PlayerProfile;
+use crate::state::treasury::Treasury;
+use crate::utils::betting::{is_betting_still_open, is_within_exposure_cap};
+use crate::utils::prediction::{
+    derive_prediction_selections,
+    retract_per_number_from_live,
+    apply_per_number_to_live,
+    apply_mask_diff_to_bets_per_number,
+    enforce_number_cap,
+};
+use crate::utils::transfers::{require_treasury_can_pay, transfer_lamports};
+
+/// Same as `ChangePredictionNumber`, but the new selection set may cover a
+/// different number of selections. The lamport difference is settled against
+/// the treasury in the same transaction.
+#[derive(Accounts)]
+#[instruction(tier: u8, new_prediction_type: u8, new_choice: u32)]
+pub struct ChangePredictionCoverage<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump = live_feed.bump,
+    )]
+    pub live_feed: Box<Account<'info, LiveFeed>>,
+
+    #[account(
+        mut,
+        seeds = [
+            Prediction::SEED_PREFIX,
+            player.key().as_ref(),
+            &live_feed.first_epoch_in_chain.to_le_bytes(),
+            &[tier],
+        ],
+        bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+    )]
+    pub prediction: Box<Account<'info, Prediction>>,
+
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
+        bump = profile.bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+    )]
+    pub profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+      seeds = [Config::SEED],
+      bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+        constraint = treasury.key() == live_feed.treasury @ IC42NErrorCode::TreasuryMismatch
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Changes the numbers of a prediction, allowing `selection_count` to change.
+///
+/// `lamports_per_number` is kept constant, so the prediction total becomes
+/// `lamports_per_number * new_count`:
+/// - more selections: the player pays the difference into the treasury
+/// - fewer selections: the treasury refunds the difference to the player
+pub fn change_prediction_coverage_handler(
+    ctx: Context<ChangePredictionCoverage>,
+    tier: u8,
+    new_prediction_type: u8,
+    new_choice: u32,
+) -> Result<()> {
+    let pred = &mut ctx.accounts.prediction;
+    let profile = &mut ctx.accounts.profile;
+    let live = &mut ctx.accounts.live_feed;
+    let config = &ctx.accounts.config;
+    let treasury = &mut ctx.accounts.treasury;
+    let player = &ctx.accounts.player;
+
+    let clock = Clock::get()?;
+    let current_epoch = clock.epoch;
+
+    pred.assert_invariant()?;
+
+    require!(config.pause_bet == 0, IC42NErrorCode::BettingPaused);
+
+    // ─────────────────────────────
+    // Epoch / chain / tier checks
+    // ─────────────────────────────
+    require!(current_epoch == live.epoch, IC42NErrorCode::EpochMismatch);
+    require!(pred.has_claimed == 0, IC42NErrorCode::AlreadyClaimed);
+
+    require_eq!(
+        pred.game_epoch,
+        live.first_epoch_in_chain,
+        IC42NErrorCode::EpochMismatch
+    );
+
+    require!(
+        pred.epoch >= live.first_epoch_in_chain && pred.epoch <= live.epoch,
+        IC42NErrorCode::EpochMismatch
+    );
+
+    require_eq!(pred.tier, tier, IC42NErrorCode::TierMismatch);
+    require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
+
+    let tier_settings = config.get_tier_settings(tier)?;
+    require!(tier_settings.is_active(), IC42NErrorCode::InactiveTier);
+    require!(!live.is_chain_cancelled(), IC42NErrorCode::ChainCancelled);
+    require!(!tier_settings.is_paused(), IC42NErrorCode::TierPaused);
+
+    // ─────────────────────────────
+    // Cutoff + tickets
+    // ─────────────────────────────
+    require!(
+        is_betting_still_open(
+            live.bet_cutoff_slots,
+            config.is_strict_cutoff(),
+            config.is_verbose_logging(),
+        ),
+        IC42NErrorCode::BettingClosed
+    );
+
+    require!(
+        profile.tickets_available > 0,
+        IC42NErrorCode::NoChangeTickets
+    );
+
+    // ─────────────────────────────
+    // Derive NEW selection set
+    // ─────────────────────────────
+    let blocked = live.secondary_rollover_number;
+    let (new_count, new_selections, new_mask) =
+        derive_prediction_selections(new_prediction_type, new_choice, blocked)?;
+
+    require!(pred.selections_mask != new_mask, IC42NErrorCode::NoOpChange);
+
+    // ─────────────────────────────
+    // Settlement amounts (per-number stays constant)
+    // ─────────────────────────────
+    let per_number = pred.lamports_per_number;
+    let old_total = pred.lamports;
+    let new_total = per_number
+        .checked_mul(new_count as u64)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+
+    // Only widening adds exposure; narrowing an over-cap position stays allowed
+    if new_total > old_total {
+        require!(
+            is_within_exposure_cap(new_total, config.max_total_exposure_for(tier)?),
+            IC42NErrorCode::ExposureCapExceeded
+        );
+    }
+
+    // ─────────────────────────────
+    // Move per-number stats from OLD set to NEW set
+    // ─────────────────────────────
+    retract_per_number_from_live(live, per_number, &pred.selections, pred.selection_count)?;
+    enforce_number_cap(
+        live,
+        per_number,
+        &new_selections,
+        new_count,
+        tier_settings.max_lamports_per_number,
+    )?;
+    apply_mask_diff_to_bets_per_number(live, pred.selections_mask, new_mask)?;
+    apply_per_number_to_live(live, per_number, &new_selections, new_count)?;
+
+    // ─────────────────────────────
+    // Settle the lamport delta
+    // ─────────────────────────────
+    if new_total > old_total {
+        let charge = new_total - old_total;
+
+        live.total_lamports = live
+            .total_lamports
+            .checked_add(charge)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+
+        treasury.total_in_lamports = treasury
+            .total_in_lamports
+            .checked_add(charge)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        treasury.add_obligation(charge)?;
+
+        transfer_lamports(
+            &player.to_account_info(),
+            &treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            charge,
+        )?;
+
+        profile.total_lamports_wagered = profile.total_lamports_wagered.saturating_add(charge);
+    } else if new_total < old_total {
+        let refund = old_total - new_total;
+
+        require!(live.total_lamports >= refund, IC42NErrorCode::InvalidLiveFeedState);
+        live.total_lamports = live
+            .total_lamports
+            .checked_sub(refund)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+
+        treasury.total_refunded_lamports = treasury
+            .total_refunded_lamports
+            .checked_add(refund)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+
+        let treasury_balance = **treasury.to_account_info().lamports.borrow();
+        require_treasury_can_pay(treasury_balance, refund)?;
+        treasury.release_obligation(refund);
+
+        **treasury.to_account_info().try_borrow_mut_lamports()? -= refund;
+        **player.to_account_info().try_borrow_mut_lamports()? += refund;
+
+        profile.total_lamports_wagered = profile.total_lamports_wagered.saturating_sub(refund);
+    }
+
+    // ─────────────────────────────
+    // Update Prediction fields
+    // ─────────────────────────────
+    pred.prediction_type = new_prediction_type;
+    pred.selection_count = new_count;
+    pred.selections = new_selections;
+    pred.selections_mask = new_mask;
+    pred.coverage_bps = Prediction::coverage_bps_for(new_count);
+    pred.lamports = new_total;
+
+    pred.changed_count = pred.changed_count.saturating_add(1);
+    pred.touch(clock.slot, clock.unix_timestamp);
+
+    // Consume ticket
+    profile.tickets_available = profile.tickets_available.saturating_sub(1);
+
+    pred.assert_invariant()?;
+
+    Ok(())
+}
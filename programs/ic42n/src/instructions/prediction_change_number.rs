@@ -8,6 +8,7 @@ use crate::utils::prediction::{
     derive_prediction_selections,
     retract_per_number_from_live,
     apply_per_number_to_live,
+    apply_mask_diff_to_bets_per_number,
 };
 
 #[derive(Accounts)]
@@ -23,6 +24,12 @@ pub struct ChangePredictionNumber<'info> {
     )]
     pub live_feed: Account<'info, LiveFeed>,
 
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
     #[account(
         mut,
         seeds = [
@@ -54,6 +61,7 @@ pub fn change_prediction_number_handler(
     let pred = &mut ctx.accounts.prediction;
     let profile = &mut ctx.accounts.profile;
     let live = &mut ctx.accounts.live_feed;
+    let config = &ctx.accounts.config;
 
     let clock = Clock::get()?;
     let current_epoch = clock.epoch;
@@ -82,16 +90,32 @@ pub fn change_prediction_number_handler(
     require_eq!(pred.tier, tier, IC42NErrorCode::TierMismatch);
     require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
 
+    // ─────────────────────────────
+    // Pause flags
+    // ─────────────────────────────
+    require!(config.pause_bet == 0, IC42NErrorCode::BettingPaused);
+    require!(!live.is_chain_cancelled(), IC42NErrorCode::ChainCancelled);
+    require!(
+        !config.get_tier_settings(tier)?.is_paused(),
+        IC42NErrorCode::TierPaused
+    );
+    config.assert_changes_allowed(tier)?;
+
     // ─────────────────────────────
     // Cutoff + tickets
     // ─────────────────────────────
     require!(
-        is_betting_still_open(live.bet_cutoff_slots),
+        is_betting_still_open(
+            live.bet_cutoff_slots,
+            config.is_strict_cutoff(),
+            config.is_verbose_logging(),
+        ),
         IC42NErrorCode::BettingClosed
     );
 
+    let ticket_cost = config.change_ticket_cost_for(tier)?;
     require!(
-        profile.tickets_available > 0,
+        profile.tickets_available >= ticket_cost,
         IC42NErrorCode::NoChangeTickets
     );
 
@@ -105,7 +129,13 @@ pub fn change_prediction_number_handler(
     // Avoid no-op change (same coverage set)
     require!(pred.selections_mask != new_mask, IC42NErrorCode::NoOpChange);
 
-    // Do NOT allow changing selection_count here (avoids refunds/extra payments)
+    require!(
+        config.is_type_change_allowed(pred.prediction_type, new_prediction_type),
+        IC42NErrorCode::TypeSwitchNotAllowed
+    );
+
+    // Do NOT allow changing selection_count here (avoids refunds/extra payments).
+    // Use `change_prediction_coverage` to change the count with settlement.
     require!(new_count == pred.selection_count,IC42NErrorCode::InvalidChoiceCount);
 
     // ─────────────────────────────
@@ -119,34 +149,9 @@ pub fn change_prediction_number_handler(
     )?;
 
     // ─────────────────────────────
-    // Update bets_per_number based on mask diff (your logic kept)
+    // Update bets_per_number based on mask diff
     // ─────────────────────────────
-    let old_mask = pred.selections_mask;
-    let removed = old_mask & !new_mask;
-    let added = new_mask & !old_mask;
-
-    for n in 1u8..=9u8 {
-        let bit = 1u16 << n;
-        let idx = n as usize;
-
-        require!(
-            idx < live.bets_per_number.len() && idx < live.lamports_per_number.len(),
-            IC42NErrorCode::InvalidBetNumber
-        );
-
-        if (removed & bit) != 0 {
-            require!(live.bets_per_number[idx] >= 1, IC42NErrorCode::InvalidLiveFeedState);
-            live.bets_per_number[idx] = live.bets_per_number[idx]
-                .checked_sub(1)
-                .ok_or(IC42NErrorCode::MathOverflow)?;
-        }
-
-        if (added & bit) != 0 {
-            live.bets_per_number[idx] = live.bets_per_number[idx]
-                .checked_add(1)
-                .ok_or(IC42NErrorCode::MathOverflow)?;
-        }
-    }
+    apply_mask_diff_to_bets_per_number(live, pred.selections_mask, new_mask)?;
 
     // ─────────────────────────────
     // Update Prediction fields
@@ -155,12 +160,13 @@ pub fn change_prediction_number_handler(
     pred.selection_count = new_count;
     pred.selections = new_selections;
     pred.selections_mask = new_mask;
+    pred.coverage_bps = Prediction::coverage_bps_for(new_count);
 
     pred.changed_count = pred.changed_count.saturating_add(1);
-    pred.last_updated_at_ts = clock.unix_timestamp;
+    pred.touch(clock.slot, clock.unix_timestamp);
 
-    // Consume ticket
-    profile.tickets_available = profile.tickets_available.saturating_sub(1);
+    // Consume tickets
+    profile.consume_tickets(ticket_cost)?;
 
     // ─────────────────────────────
     // Apply NEW per-number lamports to live feed
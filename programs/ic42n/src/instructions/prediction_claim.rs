@@ -1,20 +1,32 @@
 use anchor_lang::prelude::*;
-use sha2::{Digest, Sha256};
 
 use crate::errors::IC42NErrorCode;
-use crate::state::{GameStatus, Prediction};
+use crate::state::{Config, GameStatus, MerkleScheme, Prediction};
+use crate::state::player_profile::PlayerProfile;
 use crate::state::resolved_game::ResolvedGame;
 use crate::state::treasury::Treasury;
-use crate::utils::bitmap::{is_claimed, set_claimed};
-use crate::utils::merkle::verify_merkle_proof;
+use crate::utils::claim::check_winning_prediction;
+use crate::utils::merkle::{
+    expected_proof_len, verify_merkle_proof, verify_merkle_proof_sorted, verify_merkle_proof_v2,
+};
+use crate::utils::transfers::require_treasury_can_pay;
 
 /// Allows a winner to claim their payout for a resolved (epoch, tier) game.
 ///
 /// Claims are validated using a Merkle proof against the committed
-/// `merkle_root`, and double-claims are prevented using a bitmap.
+/// `merkle_root`, and double-claims are prevented using a bitmap. Games
+/// resolved at `LEAF_INDEX_VERSION` or later also require `index` to be the
+/// prediction's assigned `leaf_index` (see `assign_leaf_indices`).
 #[derive(Accounts)]
 #[instruction(epoch: u64, tier: u8)]
 pub struct ClaimPrediction<'info> {
+    /// Global config (claim pause flag).
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
     /// Resolved game account containing Merkle root and claim tracking.
     #[account(
         mut,
@@ -40,6 +52,16 @@ pub struct ClaimPrediction<'info> {
     )]
     pub prediction: Account<'info, Prediction>,
 
+    /// Claimer's profile, if it still exists; its open-prediction counter is
+    /// settled here. Predictions placed before the counter can outlive it.
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, claimer.key().as_ref()],
+        bump = profile.bump,
+        constraint = profile.player == claimer.key() @ IC42NErrorCode::Unauthorized,
+    )]
+    pub profile: Option<Box<Account<'info, PlayerProfile>>>,
+
     /// Treasury holding lamports for all payouts.
     #[account(
         mut,
@@ -64,22 +86,14 @@ pub fn claim_prediction_handler(
     amount: u64,
     proof: Vec<[u8; 32]>,
 ) -> Result<()> {
-    require!(proof.len() <= 40, IC42NErrorCode::ProofTooLong);
-
     let game = &mut ctx.accounts.game;
     let pred = &mut ctx.accounts.prediction;
     let treasury = &mut ctx.accounts.treasury;
     let claimer = &ctx.accounts.claimer;
 
-    // Make sure values are correct.
-    pred.assert_invariant()?;
+    require!(!ctx.accounts.config.are_claims_paused(), IC42NErrorCode::ClaimsPaused);
+    game.assert_not_frozen()?;
 
-    // Claim must not have been processed already
-    require!(
-        !is_claimed(&game.claimed_bitmap, index),
-        IC42NErrorCode::AlreadyClaimed
-    );
-    require!(pred.has_claimed == 0, IC42NErrorCode::AlreadyClaimed);
     require!(
         game.claimed_winners < game.total_winners,
         IC42NErrorCode::TooManyClaims
@@ -92,67 +106,44 @@ pub fn claim_prediction_handler(
     );
     require_eq!(game.epoch, epoch, IC42NErrorCode::EpochMismatch);
     require_eq!(game.tier, tier, IC42NErrorCode::TierMismatch);
-
-    require!(amount > 0, IC42NErrorCode::InvalidClaimAmount);
     require!(game.total_winners > 0, IC42NErrorCode::ClaimNotAllowed);
 
-    // Index bounds and bitmap integrity
-    require!(index < game.total_winners, IC42NErrorCode::InvalidClaimIndex);
-
-    let byte_index = (index / 8) as usize;
+    let clock = Clock::get()?;
     require!(
-        byte_index < game.claimed_bitmap.len(),
-        IC42NErrorCode::BitmapOutOfBounds
+        game.is_claim_window_open(clock.epoch),
+        IC42NErrorCode::ClaimWindowClosed
     );
-
-    let expected_len = ((game.total_winners as usize) + 7) / 8;
     require!(
-        game.claimed_bitmap.len() == expected_len,
-        IC42NErrorCode::InvalidBitmapLen
+        !game.is_in_dispute_window(clock.slot),
+        IC42NErrorCode::GameInDisputeWindow
     );
 
-    // --- OPTIONAL but recommended: sanity-check the prediction selection data ---
-    // Ensures the account isn't corrupted (and helps prevent weird proof binding issues).
-    let k = pred.selection_count as usize;
-    require!(k >= 1 && k <= 8, IC42NErrorCode::InvalidBetNumber);
-
-    let mut recomputed: u16 = 0;
-    for i in 0..k {
-        let n = pred.selections[i];
-        require!(n >= 1 && n <= 9, IC42NErrorCode::InvalidBetNumber);
-        recomputed |= 1u16 << n;
-    }
-    require!(recomputed == pred.selections_mask, IC42NErrorCode::InvalidBetNumber);
-
-    // Rebuild Merkle leaf
-    //
-    // IMPORTANT CHOICE:
-    // - Minimal leaf (matches your old claim): epoch/tier/index/wallet/amount
-    // - Stronger leaf (recommended): also commit to selections_mask (and optionally lamports)
-    //
-    // If you change the leaf format, your resolver (Merkle builder) must match this exactly.
-    let mut hasher = Sha256::new();
-    hasher.update(b"IC42N_V2");
-    hasher.update(&epoch.to_le_bytes());
-    hasher.update(&[tier]);
-    hasher.update(&index.to_le_bytes());
-    hasher.update(claimer.key().as_ref());
-    hasher.update(&amount.to_le_bytes());
-
-    // Bind proof to the exact coverage set the user had for this chain
-    hasher.update(&pred.selections_mask.to_le_bytes());
-
-    let leaf_hash: [u8; 32] = hasher.finalize().into();
+    let leaf_hash = check_winning_prediction(game, pred, &claimer.key(), index, amount)?;
 
     // Verify Merkle proof
     require!(
         game.merkle_root != [0u8; 32],
         IC42NErrorCode::EmptyMerkleRoot
     );
-    require!(
-        verify_merkle_proof(&leaf_hash, &proof, &game.merkle_root, index),
-        IC42NErrorCode::InvalidProof
-    );
+    let proof_ok = if !game.uses_domain_separated_merkle() {
+        // Games resolved before IC42N_V3 keep their original node hashing
+        // (always index-ordered) and are verified exactly as before.
+        verify_merkle_proof_v2(&leaf_hash, &proof, &game.merkle_root, index)
+    } else {
+        let depth = expected_proof_len(game.total_winners) as usize;
+        if game.merkle_scheme == MerkleScheme::Sorted as u8 {
+            // Leaves of an unbalanced sorted tree may sit one level higher
+            require!(
+                proof.len() <= depth && proof.len() + 1 >= depth,
+                IC42NErrorCode::InvalidProofLength
+            );
+            verify_merkle_proof_sorted(&leaf_hash, &proof, &game.merkle_root)
+        } else {
+            require_eq!(proof.len(), depth, IC42NErrorCode::InvalidProofLength);
+            verify_merkle_proof(&leaf_hash, &proof, &game.merkle_root, index)
+        }
+    };
+    require!(proof_ok, IC42NErrorCode::InvalidProof);
 
     // Ensure a sufficient prize pool and treasury balance
     let remaining = game
@@ -162,26 +153,25 @@ pub fn claim_prediction_handler(
     require!(amount <= remaining, IC42NErrorCode::InsufficientPrizePool);
 
     let treasury_balance = **treasury.to_account_info().lamports.borrow();
-    require!(treasury_balance >= amount, IC42NErrorCode::InsufficientTreasuryBalance);
+    require_treasury_can_pay(treasury_balance, amount)?;
 
-    // Transfer lamports
-    **treasury.to_account_info().try_borrow_mut_lamports()? -= amount;
-    **claimer.to_account_info().try_borrow_mut_lamports()? += amount;
+    // Record the claim before any lamports move (checks-effects-interactions)
+    game.record_claim(index, amount)?;
 
-    // Record claim
-    set_claimed(&mut game.claimed_bitmap, index);
+    pred.has_claimed = 1;
+    pred.claimed_at_ts = clock.unix_timestamp;
+    if let Some(profile) = ctx.accounts.profile.as_mut() {
+        profile.settle_prediction();
+    }
 
-    game.claimed_lamports = game
-        .claimed_lamports
-        .checked_add(amount)
-        .ok_or(IC42NErrorCode::MathOverflow)?;
-    game.claimed_winners = game
-        .claimed_winners
-        .checked_add(1)
-        .ok_or(IC42NErrorCode::MathOverflow)?;
+    // Post-mutation: nothing above may push the totals past the game
+    pred.assert_invariant()?;
+    game.assert_claim_accounting()?;
 
-    pred.has_claimed = 1;
-    pred.claimed_at_ts = Clock::get()?.unix_timestamp;
+    // Transfer lamports
+    treasury.release_obligation(amount);
+    **treasury.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **claimer.to_account_info().try_borrow_mut_lamports()? += amount;
 
     Ok(())
 }
\ No newline at end of file
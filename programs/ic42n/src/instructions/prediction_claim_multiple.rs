@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::{Config, GameStatus, MerkleScheme, Prediction};
+use crate::state::player_profile::PlayerProfile;
+use crate::state::resolved_game::ResolvedGame;
+use crate::state::treasury::Treasury;
+use crate::utils::bitmap::{is_claimed, set_claimed};
+use crate::utils::claim::check_winning_prediction;
+use crate::utils::merkle::{expected_proof_len, verify_merkle_multiproof};
+use crate::utils::transfers::require_treasury_can_pay;
+
+/// Max winners paid in a single `claim_multiple_winners` call.
+pub const MAX_MULTI_CLAIM_LEAVES: usize = 16;
+
+/// One winner leaf in a multiproof claim. Its wallet, prediction and profile
+/// are the matching triple in `remaining_accounts`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MultiClaimLeaf {
+    pub index: u32,
+    pub amount: u64,
+}
+
+/// Operator-sponsored bulk distribution for a resolved (epoch, tier) game.
+///
+/// All leaves are verified against `merkle_root` with one flag-based
+/// multiproof. Only games committed with the sorted-pair scheme qualify,
+/// since the multiproof does not track leaf positions.
+///
+/// `remaining_accounts[3 * i..3 * i + 3]` must be the wallet bound to
+/// `leaves[i]`, that wallet's Prediction for the game's chain and its
+/// PlayerProfile (or this program's id if the profile is gone, as for an
+/// absent optional account), all but the program id writable. Each leaf runs
+/// the same prediction checks as `claim_prediction` (see
+/// `check_winning_prediction`) and is settled the same way.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct ClaimMultipleWinners<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = game.bump,
+        constraint = game.resolved_at != 0 @ IC42NErrorCode::GameNotResolved
+    )]
+    pub game: Account<'info, ResolvedGame>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn claim_multiple_winners_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimMultipleWinners<'info>>,
+    epoch: u64,
+    tier: u8,
+    leaves: Vec<MultiClaimLeaf>,
+    proof_flags: Vec<bool>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let treasury = &mut ctx.accounts.treasury;
+    let accounts = ctx.remaining_accounts;
+
+    require!(!ctx.accounts.config.are_claims_paused(), IC42NErrorCode::ClaimsPaused);
+    game.assert_not_frozen()?;
+    require!(!leaves.is_empty(), IC42NErrorCode::InvalidInput);
+    require!(leaves.len() <= MAX_MULTI_CLAIM_LEAVES, IC42NErrorCode::TooManyClaims);
+    require_eq!(accounts.len(), 3 * leaves.len(), IC42NErrorCode::InvalidInput);
+
+    // Game and claim parameters must match
+    require!(
+        game.status == GameStatus::Resolved as u8,
+        IC42NErrorCode::GameNotResolved
+    );
+    require_eq!(game.epoch, epoch, IC42NErrorCode::EpochMismatch);
+    require_eq!(game.tier, tier, IC42NErrorCode::TierMismatch);
+    require!(game.total_winners > 0, IC42NErrorCode::ClaimNotAllowed);
+    let clock = Clock::get()?;
+    require!(
+        game.is_claim_window_open(clock.epoch),
+        IC42NErrorCode::ClaimWindowClosed
+    );
+    require!(
+        !game.is_in_dispute_window(clock.slot),
+        IC42NErrorCode::GameInDisputeWindow
+    );
+    require!(
+        game.merkle_scheme == MerkleScheme::Sorted as u8 && game.uses_domain_separated_merkle(),
+        IC42NErrorCode::InvalidMerkleScheme
+    );
+    require!(
+        game.merkle_root != [0u8; 32],
+        IC42NErrorCode::EmptyMerkleRoot
+    );
+
+    // Every leaf climbs at most `depth` levels, so a longer multiproof can't
+    // belong to this tree
+    let depth = expected_proof_len(game.total_winners) as usize;
+    require!(
+        proof_flags.len() <= leaves.len() * depth,
+        IC42NErrorCode::InvalidProofLength
+    );
+
+    // ─────────────────────────────
+    // Per-leaf checks + leaf hashes
+    // ─────────────────────────────
+    let mut leaf_hashes: Vec<[u8; 32]> = Vec::with_capacity(leaves.len());
+    let mut predictions: Vec<Account<'info, Prediction>> = Vec::with_capacity(leaves.len());
+    let mut profiles: Vec<Option<Account<'info, PlayerProfile>>> = Vec::with_capacity(leaves.len());
+    let mut total_amount: u64 = 0;
+
+    for (leaf, triple) in leaves.iter().zip(accounts.chunks_exact(3)) {
+        let (wallet, pred_info, profile_info) = (&triple[0], &triple[1], &triple[2]);
+        require!(
+            wallet.is_writable && pred_info.is_writable,
+            IC42NErrorCode::InvalidInput
+        );
+
+        let pred: Account<'info, Prediction> = Account::try_from(pred_info)?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                Prediction::SEED_PREFIX,
+                wallet.key.as_ref(),
+                game.first_epoch_in_chain.to_le_bytes().as_ref(),
+                &[tier],
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(pred_info.key(), expected, IC42NErrorCode::InvalidInput);
+        require_keys_eq!(pred.player, wallet.key(), IC42NErrorCode::Unauthorized);
+        require!(pred.tier == tier, IC42NErrorCode::TierMismatch);
+        // One leaf per prediction within the batch
+        require!(
+            !predictions.iter().any(|p| p.key() == pred_info.key()),
+            IC42NErrorCode::InvalidInput
+        );
+
+        let profile = if profile_info.key == ctx.program_id {
+            None
+        } else {
+            require!(profile_info.is_writable, IC42NErrorCode::InvalidInput);
+            let profile: Account<'info, PlayerProfile> = Account::try_from(profile_info)?;
+            let (expected, _) = Pubkey::find_program_address(
+                &[PlayerProfile::SEED_PREFIX, wallet.key.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(profile_info.key(), expected, IC42NErrorCode::InvalidInput);
+            require_keys_eq!(profile.player, wallet.key(), IC42NErrorCode::Unauthorized);
+            Some(profile)
+        };
+
+        leaf_hashes.push(check_winning_prediction(
+            game,
+            &pred,
+            wallet.key,
+            leaf.index,
+            leaf.amount,
+        )?);
+
+        total_amount = total_amount
+            .checked_add(leaf.amount)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        predictions.push(pred);
+        profiles.push(profile);
+    }
+
+    require!(
+        verify_merkle_multiproof(&leaf_hashes, &proof_flags, &proof, &game.merkle_root),
+        IC42NErrorCode::InvalidProof
+    );
+
+    // ─────────────────────────────
+    // Prize pool + treasury coverage for the whole batch
+    // ─────────────────────────────
+    let remaining = game
+        .net_prize_pool
+        .checked_sub(game.claimed_lamports)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+    require!(total_amount <= remaining, IC42NErrorCode::InsufficientPrizePool);
+
+    let claimed_after = game
+        .claimed_winners
+        .checked_add(leaves.len() as u32)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+    require!(claimed_after <= game.total_winners, IC42NErrorCode::TooManyClaims);
+
+    let treasury_balance = **treasury.to_account_info().lamports.borrow();
+    require_treasury_can_pay(treasury_balance, total_amount)?;
+
+    // ─────────────────────────────
+    // Record claims, then pay each wallet
+    // ─────────────────────────────
+    for leaf in leaves.iter() {
+        // Indices were checked against the stored bitmap above; a set bit
+        // here can only come from an earlier leaf in this batch
+        require!(
+            !is_claimed(&game.claimed_bitmap, leaf.index),
+            IC42NErrorCode::InvalidIndex
+        );
+        set_claimed(&mut game.claimed_bitmap, leaf.index);
+    }
+
+    game.claimed_lamports = game
+        .claimed_lamports
+        .checked_add(total_amount)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+    game.claimed_winners = claimed_after;
+    game.assert_claim_accounting()?;
+
+    for (pred, profile) in predictions.iter_mut().zip(profiles.iter_mut()) {
+        pred.has_claimed = 1;
+        pred.claimed_at_ts = clock.unix_timestamp;
+        pred.exit(ctx.program_id)?;
+        if let Some(profile) = profile {
+            profile.settle_prediction();
+            profile.exit(ctx.program_id)?;
+        }
+    }
+
+    treasury.release_obligation(total_amount);
+    for (leaf, triple) in leaves.iter().zip(accounts.chunks_exact(3)) {
+        **treasury.to_account_info().try_borrow_mut_lamports()? -= leaf.amount;
+        **triple[0].try_borrow_mut_lamports()? += leaf.amount;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::Prediction;
+use crate::state::player_profile::PlayerProfile;
+use crate::state::resolved_game::ResolvedGame;
+
+/// Closes a settled Prediction and returns its rent to the player.
+///
+/// The game passed in must be the resolved game that ended the prediction's
+/// chain (`first_epoch_in_chain == prediction.game_epoch`), so a prediction
+/// that is still riding a rollover can't be closed.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct ClosePrediction<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = game.bump,
+        constraint = game.epoch == epoch @ IC42NErrorCode::EpochMismatch,
+        constraint = game.tier == tier @ IC42NErrorCode::TierMismatch,
+    )]
+    pub game: Account<'info, ResolvedGame>,
+
+    #[account(
+        mut,
+        seeds = [
+            Prediction::SEED_PREFIX,
+            player.key().as_ref(),
+            game.first_epoch_in_chain.to_le_bytes().as_ref(),
+            &[tier]
+        ],
+        bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+        constraint = prediction.tier == tier @ IC42NErrorCode::TierMismatch,
+        constraint = prediction.game_epoch == game.first_epoch_in_chain @ IC42NErrorCode::EpochMismatch,
+        close = player
+    )]
+    pub prediction: Account<'info, Prediction>,
+
+    /// Player's profile, if it still exists (see `ClaimPrediction::profile`).
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
+        bump = profile.bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+    )]
+    pub profile: Option<Box<Account<'info, PlayerProfile>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn close_prediction_handler(ctx: Context<ClosePrediction>, _epoch: u64, _tier: u8) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let pred = &ctx.accounts.prediction;
+
+    let current_epoch = Clock::get()?.epoch;
+
+    require!(
+        game.allows_prediction_close(
+            pred.has_claimed != 0,
+            pred.mask_has(game.winning_number),
+            current_epoch,
+        ),
+        IC42NErrorCode::PredictionStillActive
+    );
+
+    // Claims and refunds already settled the counter
+    if pred.has_claimed == 0 {
+        if let Some(profile) = ctx.accounts.profile.as_mut() {
+            profile.settle_prediction();
+        }
+    }
+
+    Ok(())
+}
\ No newline at end of file
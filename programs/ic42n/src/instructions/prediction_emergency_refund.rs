@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::player_profile::PlayerProfile;
+use crate::state::treasury::Treasury;
+use crate::utils::prediction::retract_prediction_from_live;
+use crate::utils::transfers::require_treasury_can_pay;
+
+/// Emergency exit for players while the protocol is fully paused.
+///
+/// Only available when `pause_bet`, `pause_withdraw` and
+/// `emergency_refunds_open` are all set. Refunds the full `prediction.lamports`
+/// of a prediction in the tier's live chain; once a chain resolved its stakes
+/// belong to the prize pool and winners claim as usual.
+///
+/// Predictions in a tier chain cancelled by `deactivate_and_refund_tier` can
+/// use this refund without the protocol-wide pause.
+#[derive(Accounts)]
+#[instruction(tier: u8, game_epoch: u64)]
+pub struct EmergencyRefundPrediction<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump = live_feed.bump,
+    )]
+    pub live_feed: Box<Account<'info, LiveFeed>>,
+
+    #[account(
+        mut,
+        seeds = [
+            Prediction::SEED_PREFIX,
+            player.key().as_ref(),
+            &game_epoch.to_le_bytes(),
+            &[tier],
+        ],
+        bump = prediction.bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+    )]
+    pub prediction: Box<Account<'info, Prediction>>,
+
+    /// Player's profile, if it still exists (see `ClaimPrediction::profile`).
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
+        bump = profile.bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+    )]
+    pub profile: Option<Box<Account<'info, PlayerProfile>>>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+        constraint = treasury.key() == live_feed.treasury @ IC42NErrorCode::TreasuryMismatch
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+}
+
+pub fn emergency_refund_prediction_handler(
+    ctx: Context<EmergencyRefundPrediction>,
+    tier: u8,
+    game_epoch: u64,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let live = &mut ctx.accounts.live_feed;
+    let pred = &mut ctx.accounts.prediction;
+    let treasury = &mut ctx.accounts.treasury;
+    let player = &ctx.accounts.player;
+
+    require!(
+        config.are_emergency_refunds_open() || live.is_refundable_chain(pred.game_epoch),
+        IC42NErrorCode::EmergencyRefundsClosed
+    );
+
+    require_eq!(pred.tier, tier, IC42NErrorCode::TierMismatch);
+    require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
+    require_eq!(pred.game_epoch, game_epoch, IC42NErrorCode::EpochMismatch);
+
+    pred.assert_refundable(live.first_epoch_in_chain)?;
+    let amount = pred.lamports;
+
+    // ─────────────────────────────
+    // Keep the live pot closed: the stake leaves it
+    // ─────────────────────────────
+    retract_prediction_from_live(live, pred)?;
+
+    // ─────────────────────────────
+    // Record refund before moving lamports
+    // ─────────────────────────────
+    let clock = Clock::get()?;
+
+    pred.refunded = 1;
+    pred.has_claimed = 1;
+    if let Some(profile) = ctx.accounts.profile.as_mut() {
+        profile.settle_prediction();
+    }
+    pred.claimed_at_ts = clock.unix_timestamp;
+    pred.touch(clock.slot, clock.unix_timestamp);
+
+    treasury.total_refunded_lamports = treasury
+        .total_refunded_lamports
+        .checked_add(amount)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+
+    // ─────────────────────────────
+    // Transfer lamports treasury → player
+    // ─────────────────────────────
+    let treasury_balance = **treasury.to_account_info().lamports.borrow();
+    require_treasury_can_pay(treasury_balance, amount)?;
+    treasury.release_obligation(amount);
+
+    **treasury.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **player.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    Ok(())
+}
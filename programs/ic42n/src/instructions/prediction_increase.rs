@@ -2,10 +2,11 @@ use anchor_lang::prelude::*;
 
 use crate::errors::IC42NErrorCode;
 use crate::state::*;
+use crate::state::leaderboard::Leaderboard;
 use crate::state::player_profile::PlayerProfile;
 use crate::state::treasury::Treasury;
-use crate::utils::prediction::apply_per_number_to_live;
-use crate::utils::betting::{is_amount_in_tier, is_betting_still_open};
+use crate::utils::prediction::{apply_per_number_to_live, choice_matches_selections, enforce_number_cap};
+use crate::utils::betting::{increased_stake_in_tier, is_betting_still_open, is_within_exposure_cap};
 use crate::utils::transfers::transfer_lamports;
 
 #[derive(Accounts)]
@@ -21,12 +22,15 @@ pub struct IncreasePrediction<'info> {
     )]
     pub live_feed: Account<'info, LiveFeed>,
 
+    /// Seeded by its own `game_epoch` (not the live chain's) so a prediction
+    /// from an already resolved chain reaches the handler and fails with
+    /// `PredictionGameResolved` instead of a seeds mismatch.
     #[account(
         mut,
         seeds = [
             Prediction::SEED_PREFIX,
             player.key().as_ref(),
-            &live_feed.first_epoch_in_chain.to_le_bytes(),
+            &prediction.game_epoch.to_le_bytes(),
             &[tier],
         ],
         bump,
@@ -56,9 +60,22 @@ pub struct IncreasePrediction<'info> {
     )]
     pub treasury: Account<'info, Treasury>,
 
+    /// Leaderboard for the current (epoch, tier); only updated when passed.
+    #[account(
+        mut,
+        seeds = [Leaderboard::SEED_PREFIX, live_feed.epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = leaderboard.bump,
+    )]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// Adds `additional_lamports` to every selected number of an existing prediction.
+///
+/// The selections always come from the stored prediction. `choice` is only a
+/// confirmation: it must re-encode the stored selections for the stored
+/// `prediction_type`, otherwise the call fails with `ChoiceMismatch`.
 pub fn increase_prediction_handler(
     ctx: Context<IncreasePrediction>,
     tier: u8,
@@ -76,7 +93,6 @@ pub fn increase_prediction_handler(
     require!(config.pause_bet == 0, IC42NErrorCode::BettingPaused);
     require!(pred.has_claimed == 0, IC42NErrorCode::AlreadyClaimed);
     require!(additional_lamports > 0, IC42NErrorCode::InvalidBetAmount);
-    require!(choice > 0, IC42NErrorCode::InvalidBetNumber);
 
     let clock = Clock::get()?;
     let current_epoch = clock.epoch;
@@ -86,11 +102,7 @@ pub fn increase_prediction_handler(
     // ─────────────────────────────
     require!(current_epoch == live.epoch, IC42NErrorCode::EpochMismatch);
 
-    require_eq!(
-        pred.game_epoch,
-        live.first_epoch_in_chain,
-        IC42NErrorCode::EpochMismatch
-    );
+    pred.assert_in_live_chain(live.first_epoch_in_chain)?;
 
     require!(
         pred.epoch >= live.first_epoch_in_chain && pred.epoch <= live.epoch,
@@ -104,7 +116,11 @@ pub fn increase_prediction_handler(
     // Cutoff & limits
     // ─────────────────────────────
     require!(
-        is_betting_still_open(live.bet_cutoff_slots),
+        is_betting_still_open(
+            live.bet_cutoff_slots,
+            config.is_strict_cutoff(),
+            config.is_verbose_logging(),
+        ),
         IC42NErrorCode::BettingClosed
     );
 
@@ -112,6 +128,8 @@ pub fn increase_prediction_handler(
 
     let tier_settings = config.get_tier_settings(tier)?;
     require!(tier_settings.is_active(), IC42NErrorCode::InactiveTier);
+    require!(!live.is_chain_cancelled(), IC42NErrorCode::ChainCancelled);
+    require!(!tier_settings.is_paused(), IC42NErrorCode::TierPaused);
 
     // ─────────────────────────────
     // Selection invariants
@@ -136,19 +154,28 @@ pub fn increase_prediction_handler(
     }
     require!(recomputed == pred.selections_mask, IC42NErrorCode::InvalidBetNumber);
 
+    // Caller's choice must describe the same selections it is increasing
+    require!(
+        choice_matches_selections(pred.prediction_type, choice, pred.selections_mask),
+        IC42NErrorCode::ChoiceMismatch
+    );
+
     // ─────────────────────────────
     // Compute new per-number + totals
     // ─────────────────────────────
-    let new_per_number = pred
-        .lamports_per_number
-        .checked_add(additional_lamports)
-        .ok_or(IC42NErrorCode::MathOverflow)?;
+    // Tier range applies to PER NUMBER, against the tier's current bounds
+    // (the existing position itself is grandfathered)
+    let new_per_number =
+        increased_stake_in_tier(pred.lamports_per_number, additional_lamports, &tier_settings)?;
 
-    // tier range applies to PER NUMBER
-    require!(
-        is_amount_in_tier(new_per_number, &tier_settings),
-        IC42NErrorCode::BetOutOfTierRange
-    );
+    // Each selected number grows by `additional_lamports`
+    enforce_number_cap(
+        live,
+        additional_lamports,
+        &pred.selections,
+        pred.selection_count,
+        tier_settings.max_lamports_per_number,
+    )?;
 
     let additional_total = additional_lamports
         .checked_mul(k)
@@ -158,6 +185,10 @@ pub fn increase_prediction_handler(
         .lamports
         .checked_add(additional_total)
         .ok_or(IC42NErrorCode::MathOverflow)?;
+    require!(
+        is_within_exposure_cap(new_total, config.max_total_exposure_for(tier)?),
+        IC42NErrorCode::ExposureCapExceeded
+    );
 
     // ─────────────────────────────
     // Update Prediction
@@ -166,7 +197,7 @@ pub fn increase_prediction_handler(
     pred.lamports = new_total;
 
     pred.changed_count = pred.changed_count.saturating_add(1);
-    pred.last_updated_at_ts = clock.unix_timestamp;
+    pred.touch(clock.slot, clock.unix_timestamp);
     
     
     // ─────────────────────────────
@@ -190,12 +221,14 @@ pub fn increase_prediction_handler(
         .total_lamports
         .checked_add(additional_total)
         .ok_or(IC42NErrorCode::MathOverflow)?;
+    live.record_lifetime_wager(additional_total, false);
 
     // Treasury increases by additional_total
     treasury.total_in_lamports = treasury
         .total_in_lamports
         .checked_add(additional_total)
         .ok_or(IC42NErrorCode::MathOverflow)?;
+    treasury.add_obligation(additional_total)?;
 
     // ─────────────────────────────
     // Transfer extra lamports player → treasury (TOTAL delta)
@@ -211,6 +244,17 @@ pub fn increase_prediction_handler(
         pred.lamports == pred.expected_total_lamports(),
         IC42NErrorCode::InvalidBetAmount
     );
+
+    // ─────────────────────────────
+    // Leaderboard (opt-in per tier)
+    // ─────────────────────────────
+    if let Some(board) = ctx.accounts.leaderboard.as_mut() {
+        require!(
+            tier_settings.is_leaderboard_enabled(),
+            IC42NErrorCode::LeaderboardDisabled
+        );
+        board.record(player.key(), pred.lamports, pred.placed_slot);
+    }
     
     Ok(())
 }
\ No newline at end of file
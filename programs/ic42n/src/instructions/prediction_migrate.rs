@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::errors::IC42NErrorCode;
+use crate::state::Prediction;
+use crate::state::player_profile::PlayerProfile;
+use crate::utils::transfers::grow_account;
+
+/// Grows a version 2 Prediction (`LEGACY_SIZE`) to the current layout so it
+/// can be loaded again.
+///
+/// The version 3 tail is zeroed and `version` stays 2, so the missing
+/// nonce, slot and pot snapshot read as unknown. Permissionless: the layout
+/// change can't alter the prediction, and winners must be migrated before
+/// `assign_leaf_indices` can load them, so the payer (e.g. the resolver)
+/// covers the extra rent. Predictions already at `SIZE` are left untouched.
+///
+/// Version 2 predictions predate `PlayerProfile.unclaimed_predictions`, so an
+/// unsettled one is counted on the player's profile here, if passed (it must
+/// be on the current profile layout).
+#[derive(Accounts)]
+#[instruction(game_epoch: u64, tier: u8)]
+pub struct MigratePrediction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only used to derive the prediction PDA.
+    pub player: UncheckedAccount<'info>,
+
+    /// CHECK: Legacy layout can't be deserialized as `Prediction` until grown;
+    /// the PDA seeds (which bind the player), owner and discriminator are
+    /// verified instead.
+    #[account(
+        mut,
+        seeds = [
+            Prediction::SEED_PREFIX,
+            player.key().as_ref(),
+            game_epoch.to_le_bytes().as_ref(),
+            &[tier]
+        ],
+        bump,
+        owner = crate::ID @ IC42NErrorCode::Unauthorized,
+    )]
+    pub prediction: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
+        bump = profile.bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+    )]
+    pub profile: Option<Box<Account<'info, PlayerProfile>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_prediction_handler(
+    ctx: Context<MigratePrediction>,
+    _game_epoch: u64,
+    _tier: u8,
+) -> Result<()> {
+    let prediction = ctx.accounts.prediction.to_account_info();
+    let old_len = prediction.data_len();
+
+    {
+        let data = prediction.try_borrow_data()?;
+        require!(data.len() >= 8 + Prediction::LEGACY_SIZE, IC42NErrorCode::InvalidInput);
+        require!(data[..8] == *Prediction::DISCRIMINATOR, IC42NErrorCode::InvalidInput);
+    }
+
+    if old_len == 8 + Prediction::SIZE {
+        return Ok(());
+    }
+    require_eq!(old_len, 8 + Prediction::LEGACY_SIZE, IC42NErrorCode::InvalidInput);
+
+    grow_account(
+        &prediction,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + Prediction::SIZE,
+    )?;
+
+    let mut data = prediction.try_borrow_mut_data()?;
+    data[old_len..].fill(0);
+
+    let pred = Prediction::try_deserialize(&mut &data[..])?;
+    if pred.has_claimed == 0 {
+        if let Some(profile) = ctx.accounts.profile.as_mut() {
+            profile.unclaimed_predictions = profile.unclaimed_predictions.saturating_add(1);
+        }
+    }
+
+    Ok(())
+}
\ No newline at end of file
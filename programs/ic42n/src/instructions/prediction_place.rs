@@ -3,14 +3,23 @@ use anchor_lang::prelude::*;
 use crate::constants::RECENT_BETS_CAP;
 use crate::errors::IC42NErrorCode;
 use crate::state::*;
+use crate::state::leaderboard::Leaderboard;
 use crate::state::player_profile::PlayerProfile;
 use crate::state::treasury::Treasury;
-use crate::utils::betting::{is_amount_in_tier, is_betting_still_open};
-use crate::utils::prediction::derive_prediction_selections;
+use crate::utils::betting::{is_amount_in_tier, is_betting_still_open, is_within_exposure_cap};
+use crate::utils::prediction::{derive_prediction_selections, enforce_number_cap};
 use crate::utils::transfers::transfer_lamports;
+use crate::utils::xp::{level_for_xp, xp_for_wager};
 
 #[derive(Accounts)]
-#[instruction(tier: u8, prediction_type: u8, choice: u32, lamports: u64)]
+#[instruction(
+    tier: u8,
+    prediction_type: u8,
+    choice: u32,
+    lamports: u64,
+    referrer: Option<Pubkey>,
+    recent_bets_cap: Option<u16>
+)]
 pub struct PlacePrediction<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
@@ -39,7 +48,9 @@ pub struct PlacePrediction<'info> {
     #[account(
         init_if_needed,
         payer = player,
-        space = 8 + PlayerProfile::SIZE,
+        space = 8 + PlayerProfile::size_for_cap(
+            recent_bets_cap.unwrap_or(RECENT_BETS_CAP as u16)
+        ),
         seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
         bump,
         constraint = profile.player == Pubkey::default()
@@ -47,6 +58,22 @@ pub struct PlacePrediction<'info> {
     )]
     pub profile: Box<Account<'info, PlayerProfile>>,
 
+    /// Referrer's profile. Required only when `referrer` is passed for a new profile.
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, referrer_profile.player.as_ref()],
+        bump = referrer_profile.bump,
+    )]
+    pub referrer_profile: Option<Box<Account<'info, PlayerProfile>>>,
+
+    /// Leaderboard for the current (epoch, tier); only updated when passed.
+    #[account(
+        mut,
+        seeds = [Leaderboard::SEED_PREFIX, live_feed.epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = leaderboard.bump,
+    )]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+
     #[account(
         mut,
         seeds = [Treasury::SEED],
@@ -70,6 +97,8 @@ pub fn place_prediction_handler(
     prediction_type: u8,
     choice: u32,
     lamports: u64, // per-number lamports
+    referrer: Option<Pubkey>, // only honored when the profile is created
+    recent_bets_cap: Option<u16>, // ditto; None = RECENT_BETS_CAP
 ) -> Result<()> {
     let pred = &mut ctx.accounts.prediction;
     let live = &mut ctx.accounts.live_feed;
@@ -90,7 +119,11 @@ pub fn place_prediction_handler(
     require!(live.tier == tier, IC42NErrorCode::TierMismatch);
 
     require!(
-        is_betting_still_open(live.bet_cutoff_slots),
+        is_betting_still_open(
+            live.bet_cutoff_slots,
+            config.is_strict_cutoff(),
+            config.is_verbose_logging(),
+        ),
         IC42NErrorCode::BettingClosed
     );
 
@@ -110,15 +143,30 @@ pub fn place_prediction_handler(
     // ─────────────────────────────
     let tier_settings = config.get_tier_settings(tier)?;
     require!(tier_settings.is_active(), IC42NErrorCode::InactiveTier);
+    require!(!live.is_chain_cancelled(), IC42NErrorCode::ChainCancelled);
+    require!(!tier_settings.is_paused(), IC42NErrorCode::TierPaused);
     require!(
         is_amount_in_tier(lamports, &tier_settings),
         IC42NErrorCode::BetOutOfTierRange
     );
 
+    // No single number may be pushed past the tier's concentration cap
+    enforce_number_cap(
+        live,
+        lamports,
+        &selections,
+        selection_count,
+        tier_settings.max_lamports_per_number,
+    )?;
+
     // total exposure = per-number * selection_count
     let total_lamports = lamports
         .checked_mul(k)
         .ok_or(IC42NErrorCode::MathOverflow)?;
+    require!(
+        is_within_exposure_cap(total_lamports, config.max_total_exposure_for(tier)?),
+        IC42NErrorCode::ExposureCapExceeded
+    );
 
     // ─────────────────────────────
     // Initialize / hydrate PlayerProfile
@@ -134,13 +182,39 @@ pub fn place_prediction_handler(
         profile.last_played_timestamp = 0;
         profile.xp_points = 0;
 
-        profile.recent_bets = [Pubkey::default(); RECENT_BETS_CAP];
-        profile.recent_bets_len = 0;
-        profile.recent_bets_head = 0;
-        profile.tickets_available = 1;
-        profile._reserved = [0u8; 16];
+        profile.init_recent_bets(recent_bets_cap.unwrap_or(RECENT_BETS_CAP as u16))?;
+        profile.tickets_available = config.welcome_tickets_granted();
+        profile.epoch_predictions = 0;
+        profile.tickets_awarded_total = 0;
+        profile.level = 0;
+        profile.unclaimed_predictions = 0;
+        profile.referrer = Pubkey::default();
+        profile.referred_count = 0;
+
+        // Referral is attributed once, on the referee's first bet
+        if let Some(referrer) = referrer {
+            let referrer_profile = ctx
+                .accounts
+                .referrer_profile
+                .as_mut()
+                .ok_or(IC42NErrorCode::InvalidReferrer)?;
+
+            PlayerProfile::check_referral(&player.key(), &referrer, &referrer_profile.player)?;
+
+            profile.referrer = referrer;
+            referrer_profile.referred_count = referrer_profile.referred_count.saturating_add(1);
+        }
     }
 
+    // ─────────────────────────────
+    // Enforce per-player epoch prediction cap
+    // ─────────────────────────────
+    let placed_this_epoch = profile.predictions_in_epoch(clock.epoch);
+    require!(
+        config.is_epoch_prediction_allowed(placed_this_epoch),
+        IC42NErrorCode::EpochBetLimitReached
+    );
+
     // ─────────────────────────────
     // Enforce one prediction per game chain
     // ─────────────────────────────
@@ -171,7 +245,7 @@ pub fn place_prediction_handler(
 
     pred.placed_slot = clock.slot;
     pred.placed_at_ts = clock.unix_timestamp;
-    pred.last_updated_at_ts = clock.unix_timestamp;
+    pred.touch(clock.slot, clock.unix_timestamp);
 
     pred.has_claimed = 0;
     pred.claimed_at_ts = 0;
@@ -179,7 +253,11 @@ pub fn place_prediction_handler(
     pred.bump = ctx.bumps.prediction;
     pred.version = Prediction::VERSION;
 
-    pred._reserved = [0u8; 8];
+    pred.refunded = 0;
+    pred.coverage_bps = Prediction::coverage_bps_for(selection_count);
+    pred.leaf_index = 0;
+    pred.leaf_index_assigned = 0;
+    pred.entry_nonce = live.next_entry_nonce()?;
 
     // Extend the profile deletion lock: always push it forward, never shorten it
     let new_until = clock.epoch.saturating_add(2);
@@ -188,38 +266,13 @@ pub fn place_prediction_handler(
     // Store in profile recent bets ring buffer
     let pred_pk = pred.key();
     profile.push_recent_bet(pred_pk);
+    profile.unclaimed_predictions = profile.unclaimed_predictions.saturating_add(1);
 
     // ─────────────────────────────
     // Update live feed stats
     // ─────────────────────────────
-    live.total_bets = live
-        .total_bets
-        .checked_add(1)
-        .ok_or(IC42NErrorCode::MathOverflow)?;
-
-    // TOTAL exposure
-    live.total_lamports = live
-        .total_lamports
-        .checked_add(total_lamports)
-        .ok_or(IC42NErrorCode::MathOverflow)?;
-
-    // Per-number stats: each selected number gets full per-number lamports (no split)
-    for i in 0..(selection_count as usize) {
-        let n = selections[i] as usize;
-
-        require!(
-            n < live.bets_per_number.len() && n < live.lamports_per_number.len(),
-            IC42NErrorCode::InvalidBetNumber
-        );
-
-        live.bets_per_number[n] = live.bets_per_number[n]
-            .checked_add(1)
-            .ok_or(IC42NErrorCode::MathOverflow)?;
-
-        live.lamports_per_number[n] = live.lamports_per_number[n]
-            .checked_add(lamports)
-            .ok_or(IC42NErrorCode::MathOverflow)?;
-    }
+    pred.placed_pot_snapshot =
+        live.record_placement(total_lamports, lamports, &selections, selection_count)?;
 
     // ─────────────────────────────
     // Update treasury stats (TOTAL)
@@ -228,6 +281,7 @@ pub fn place_prediction_handler(
         .total_in_lamports
         .checked_add(total_lamports)
         .ok_or(IC42NErrorCode::MathOverflow)?;
+    treasury.add_obligation(total_lamports)?;
 
     // ─────────────────────────────
     // Transfer lamports player → treasury (TOTAL)
@@ -247,11 +301,24 @@ pub fn place_prediction_handler(
         .total_lamports_wagered
         .saturating_add(total_lamports);
 
+    profile.epoch_predictions = placed_this_epoch.saturating_add(1);
     profile.last_played_epoch = clock.epoch;
     profile.last_played_tier = tier;
     profile.last_played_timestamp = clock.unix_timestamp;
 
-    profile.xp_points = profile.xp_points.saturating_add(1);
+    profile.xp_points = profile.xp_points.saturating_add(xp_for_wager(total_lamports));
+    profile.level = level_for_xp(profile.xp_points);
+
+    // ─────────────────────────────
+    // Leaderboard (opt-in per tier)
+    // ─────────────────────────────
+    if let Some(board) = ctx.accounts.leaderboard.as_mut() {
+        require!(
+            tier_settings.is_leaderboard_enabled(),
+            IC42NErrorCode::LeaderboardDisabled
+        );
+        board.record(player.key(), pred.lamports, pred.placed_slot);
+    }
 
     if profile.first_played_epoch == 0 {
         profile.first_played_epoch = live.first_epoch_in_chain;
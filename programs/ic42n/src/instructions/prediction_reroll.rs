@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::player_profile::PlayerProfile;
+use crate::utils::betting::is_betting_still_open;
+use crate::utils::resolve::slot_hash_at_or_before;
+use crate::utils::prediction::{
+    apply_mask_diff_to_bets_per_number,
+    apply_per_number_to_live,
+    enforce_number_cap,
+    reroll_prediction_type,
+    reroll_seed,
+    reroll_selections,
+    retract_per_number_from_live,
+};
+
+/// Spends the tier's change ticket cost (`change_ticket_cost_for`) to swap
+/// a prediction's numbers for a fresh, deterministic pick of the same
+/// `selection_count`.
+///
+/// The new set is drawn from 1..=9 minus the feed's blocked number using
+/// `reroll_seed` (newest SlotHashes entry, prediction key, `changed_count`).
+/// It may equal the old set; the entry's `placed_slot` is refreshed either way.
+/// The prediction becomes a digit type (single / two / multi number).
+#[derive(Accounts)]
+#[instruction(tier: u8)]
+pub struct RerollPrediction<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump = live_feed.bump,
+    )]
+    pub live_feed: Account<'info, LiveFeed>,
+
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [
+            Prediction::SEED_PREFIX,
+            player.key().as_ref(),
+            &live_feed.first_epoch_in_chain.to_le_bytes(),
+            &[tier],
+        ],
+        bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+    )]
+    pub prediction: Box<Account<'info, Prediction>>,
+
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
+        bump = profile.bump,
+        has_one = player @ IC42NErrorCode::Unauthorized,
+    )]
+    pub profile: Box<Account<'info, PlayerProfile>>,
+
+    /// CHECK: Address-checked SlotHashes sysvar; only its newest entry is read.
+    #[account(address = slot_hashes::ID @ IC42NErrorCode::InvalidInput)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+pub fn reroll_prediction_handler(ctx: Context<RerollPrediction>, tier: u8) -> Result<()> {
+    let pred_key = ctx.accounts.prediction.key();
+    let pred = &mut ctx.accounts.prediction;
+    let profile = &mut ctx.accounts.profile;
+    let live = &mut ctx.accounts.live_feed;
+    let config = &ctx.accounts.config;
+
+    let clock = Clock::get()?;
+
+    pred.assert_invariant()?;
+
+    // ─────────────────────────────
+    // Epoch / chain / tier checks
+    // ─────────────────────────────
+    require!(clock.epoch == live.epoch, IC42NErrorCode::EpochMismatch);
+    require!(pred.has_claimed == 0, IC42NErrorCode::AlreadyClaimed);
+    require_eq!(pred.game_epoch, live.first_epoch_in_chain, IC42NErrorCode::EpochMismatch);
+    require!(
+        pred.epoch >= live.first_epoch_in_chain && pred.epoch <= live.epoch,
+        IC42NErrorCode::EpochMismatch
+    );
+    require_eq!(pred.tier, tier, IC42NErrorCode::TierMismatch);
+    require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
+
+    // ─────────────────────────────
+    // Pause flags + cutoff
+    // ─────────────────────────────
+    let tier_settings = config.get_tier_settings(tier)?;
+    require!(config.pause_bet == 0, IC42NErrorCode::BettingPaused);
+    require!(!live.is_chain_cancelled(), IC42NErrorCode::ChainCancelled);
+    require!(!tier_settings.is_paused(), IC42NErrorCode::TierPaused);
+    config.assert_changes_allowed(tier)?;
+    require!(
+        is_betting_still_open(
+            live.bet_cutoff_slots,
+            config.is_strict_cutoff(),
+            config.is_verbose_logging(),
+        ),
+        IC42NErrorCode::BettingClosed
+    );
+
+    // ─────────────────────────────
+    // Draw the new selection set
+    // ─────────────────────────────
+    let new_type = reroll_prediction_type(pred.selection_count);
+    require!(
+        config.is_type_change_allowed(pred.prediction_type, new_type),
+        IC42NErrorCode::TypeSwitchNotAllowed
+    );
+
+    let (_, slot_hash) =
+        slot_hash_at_or_before(&ctx.accounts.slot_hashes.try_borrow_data()?, u64::MAX)?;
+    let seed = reroll_seed(&slot_hash, &pred_key, pred.changed_count);
+    let (new_selections, new_mask) =
+        reroll_selections(&seed, pred.selection_count, live.secondary_rollover_number)?;
+
+    // ─────────────────────────────
+    // Move the stake to the new numbers
+    // ─────────────────────────────
+    retract_per_number_from_live(
+        live,
+        pred.lamports_per_number,
+        &pred.selections,
+        pred.selection_count,
+    )?;
+    enforce_number_cap(
+        live,
+        pred.lamports_per_number,
+        &new_selections,
+        pred.selection_count,
+        tier_settings.max_lamports_per_number,
+    )?;
+    apply_mask_diff_to_bets_per_number(live, pred.selections_mask, new_mask)?;
+    apply_per_number_to_live(
+        live,
+        pred.lamports_per_number,
+        &new_selections,
+        pred.selection_count,
+    )?;
+
+    // ─────────────────────────────
+    // Update Prediction + spend the tickets
+    // ─────────────────────────────
+    pred.prediction_type = new_type;
+    pred.selections = new_selections;
+    pred.selections_mask = new_mask;
+
+    pred.changed_count = pred.changed_count.saturating_add(1);
+    pred.placed_slot = clock.slot;
+    pred.touch(clock.slot, clock.unix_timestamp);
+
+    // Same per-tier price as a number change
+    profile.consume_tickets(config.change_ticket_cost_for(tier)?)?;
+
+    Ok(())
+}
\ No newline at end of file
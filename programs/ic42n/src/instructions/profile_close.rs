@@ -12,9 +12,14 @@ pub struct ClosePlayerProfile<'info> {
         seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
         bump = profile.bump,
         constraint = profile.player == player.key() @ IC42NErrorCode::Unauthorized,
-        close = player
     )]
     pub profile: Box<Account<'info, PlayerProfile>>,
+
+    /// Receives the reclaimed rent instead of `player` when passed
+    /// (e.g. a smart wallet's vault). Only the player can choose it.
+    #[account(mut)]
+    pub rent_recipient: Option<SystemAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -26,5 +31,32 @@ pub fn close_player_profile_handler(ctx: Context<ClosePlayerProfile>) -> Result<
         clock.epoch >= profile.locked_until_epoch,
         IC42NErrorCode::ProfileLockedActiveGame
     );
-    Ok(())
+    require!(
+        profile.unclaimed_predictions == 0,
+        IC42NErrorCode::HasUnclaimedPredictions
+    );
+
+    let recipient = rent_destination(
+        ctx.accounts.player.to_account_info(),
+        ctx.accounts.rent_recipient.as_ref().map(|r| r.to_account_info()),
+    );
+    ctx.accounts.profile.close(recipient)
+}
+
+/// The account the closed profile's rent goes to: the optional
+/// `rent_recipient`, else the player.
+fn rent_destination<T>(player: T, rent_recipient: Option<T>) -> T {
+    rent_recipient.unwrap_or(player)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rent_goes_to_player_unless_redirected() {
+        let (player, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(rent_destination(player, None), player);
+        assert_eq!(rent_destination(player, Some(recipient)), recipient);
+    }
 }
\ No newline at end of file
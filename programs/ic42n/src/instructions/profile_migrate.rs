@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::errors::IC42NErrorCode;
+use crate::state::player_profile::PlayerProfile;
+use crate::utils::transfers::grow_account;
+
+/// Upgrades a fixed-ring PlayerProfile (`LEGACY_SIZE` or `FIXED_RING_SIZE`)
+/// to the current layout with a `RECENT_BETS_CAP`-entry ring.
+///
+/// The ring gets its Vec length prefix, `recent_bets_cap` is set, and any
+/// missing referral fields are zero-initialized (no referrer, 0 referred).
+/// Profiles already on the current layout are left untouched.
+#[derive(Accounts)]
+pub struct MigratePlayerProfile<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// CHECK: Legacy layout can't be deserialized as `PlayerProfile` until grown;
+    /// the PDA seeds, owner and discriminator are verified instead.
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, player.key().as_ref()],
+        bump,
+        owner = crate::ID @ IC42NErrorCode::Unauthorized,
+    )]
+    pub profile: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_player_profile_handler(ctx: Context<MigratePlayerProfile>) -> Result<()> {
+    let profile = ctx.accounts.profile.to_account_info();
+    let old_len = profile.data_len();
+
+    {
+        let data = profile.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + PlayerProfile::LEGACY_SIZE,
+            IC42NErrorCode::InvalidInput
+        );
+        require!(
+            data[..8] == *PlayerProfile::DISCRIMINATOR,
+            IC42NErrorCode::InvalidInput
+        );
+    }
+
+    // Layouts are told apart by length (see `fixed_ring_sizes_never_match_a_vec_layout`)
+    if old_len != 8 + PlayerProfile::LEGACY_SIZE && old_len != 8 + PlayerProfile::FIXED_RING_SIZE {
+        return Ok(());
+    }
+
+    grow_account(
+        &profile,
+        &ctx.accounts.player.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + PlayerProfile::SIZE,
+    )?;
+
+    let mut data = profile.try_borrow_mut_data()?;
+    PlayerProfile::upgrade_fixed_ring(&mut data, old_len);
+
+    Ok(())
+}
\ No newline at end of file
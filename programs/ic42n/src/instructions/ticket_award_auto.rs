@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::player_profile::PlayerProfile;
 use crate::errors::IC42NErrorCode;
+use crate::events::{TicketAwardSource, TicketsAwarded};
 use crate::state::{Config};
 use crate::utils::ticket::{ award_tickets_to_profile};
 
@@ -47,7 +48,14 @@ pub fn award_ticket_auto_handler(ctx: Context<AutoAwardTicket>, tier: u8) -> Res
         return Ok(());
     }
 
-    award_tickets_to_profile(profile, tier_settings.tickets_per_recipient as u32);
+    let credited = award_tickets_to_profile(profile, tickets);
+
+    emit!(TicketsAwarded {
+        player: profile.player,
+        tier,
+        count: credited,
+        source: TicketAwardSource::Auto.as_u8(),
+    });
 
     Ok(())
 }
\ No newline at end of file
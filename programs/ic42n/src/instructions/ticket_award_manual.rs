@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::constants::MAX_TICKETS_PER_GRANT;
 use crate::state::player_profile::PlayerProfile;
 use crate::errors::IC42NErrorCode;
+use crate::events::{TicketAwardSource, TicketsAwarded};
 use crate::state::{Config};
 use crate::utils::ticket::{ award_tickets_to_profile};
 
@@ -29,6 +30,13 @@ pub fn award_ticket_manual_handler(ctx: Context<ManualAwardTicket>, tickets: u32
 
     let profile = &mut ctx.accounts.profile;
     
-    award_tickets_to_profile(profile, tickets);
+    let credited = award_tickets_to_profile(profile, tickets);
+
+    emit!(TicketsAwarded {
+        player: profile.player,
+        tier: 0,
+        count: credited,
+        source: TicketAwardSource::Manual.as_u8(),
+    });
     Ok(())
 }
\ No newline at end of file
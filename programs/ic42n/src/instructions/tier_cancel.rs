@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+
+/// Retires a tier that still holds a live pot.
+///
+/// The feed's current chain is marked cancelled, so it can no longer take
+/// bets or be resolved and each of its predictions can be refunded in full
+/// through `emergency_refund_prediction`. The tier is then deactivated. Once
+/// refunds have emptied the feed it can be closed (or reset to a new chain).
+///
+/// Rejected while the chain's game is mid-resolution (`Processing` or
+/// `Failed`), since completing it would pay winners on top of the refunds.
+#[derive(Accounts)]
+#[instruction(tier: u8)]
+pub struct DeactivateAndRefundTier<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump = live_feed.bump,
+    )]
+    pub live_feed: Account<'info, LiveFeed>,
+
+    /// CHECK: The ResolvedGame PDA of `pending_resolution_epoch`; usually not
+    /// created yet, so it is only deserialized when the program owns it.
+    #[account(
+        seeds = [
+            ResolvedGame::SEED_PREFIX,
+            live_feed.pending_resolution_epoch.to_le_bytes().as_ref(),
+            &[tier]
+        ],
+        bump,
+    )]
+    pub pending_game: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn deactivate_and_refund_tier_handler(
+    ctx: Context<DeactivateAndRefundTier>,
+    tier: u8,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let live = &mut ctx.accounts.live_feed;
+
+    require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
+
+    let pending = ctx.accounts.pending_game.to_account_info();
+    if pending.owner == ctx.program_id && !pending.data_is_empty() {
+        let game = ResolvedGame::try_deserialize(&mut &pending.try_borrow_data()?[..])?;
+        require!(
+            game.status == GameStatus::Resolved as u8,
+            IC42NErrorCode::GameAlreadyResolvingOrResolved
+        );
+    }
+
+    live.chain_cancelled = 1;
+    config.set_tier_active(tier, 0)
+}
\ No newline at end of file
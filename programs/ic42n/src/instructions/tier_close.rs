@@ -43,6 +43,13 @@ pub fn close_tier_live_feed_handler(
         IC42NErrorCode::LiveFeedNotEmpty
     );
 
+    // A house seed only leaves through a no-winner resolution
+    require_eq!(
+        live.house_seed_lamports,
+        0,
+        IC42NErrorCode::LiveFeedNotEmpty
+    );
+
     // Deactivate tier
     config.set_tier_active(tier, 0)?;
 
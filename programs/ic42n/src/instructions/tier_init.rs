@@ -59,11 +59,11 @@ pub fn init_tier_live_feed_handler(
     // ─────────────────────────────────────────────
     live.init_new(
         current_epoch,
-        config.bet_cutoff_slots,
+        config.bet_cutoff_for(tier)?,
         tier,
         ctx.accounts.treasury.key(),
         ctx.bumps.live_feed,
-        config.base_fee_bps,
+        config.fee_settings_for(tier)?.base_fee_bps,
     );
     
     Ok(())
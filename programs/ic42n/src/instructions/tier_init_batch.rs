@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::treasury::Treasury;
+use crate::utils::transfers::create_pda_account;
+
+/// Launch-day variant of `init_tier_live_feed`: activates several tiers and
+/// creates their LiveFeed PDAs in one transaction.
+///
+/// `remaining_accounts[i]` must be the (writable, uninitialized) LiveFeed PDA
+/// for `tiers[i]`.
+#[derive(Accounts)]
+pub struct InitTierLiveFeedsBatch<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Pass treasury for the live feeds.
+    #[account(
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_tier_live_feeds_batch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitTierLiveFeedsBatch<'info>>,
+    tiers: Vec<u8>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let feeds = ctx.remaining_accounts;
+
+    require!(!tiers.is_empty(), IC42NErrorCode::InvalidInput);
+    require!(tiers.len() <= config.tiers.len(), IC42NErrorCode::InvalidInput);
+    require_eq!(feeds.len(), tiers.len(), IC42NErrorCode::InvalidInput);
+
+    for (i, tier) in tiers.iter().enumerate() {
+        require!(!tiers[..i].contains(tier), IC42NErrorCode::InvalidInput);
+    }
+
+    let current_epoch = Clock::get()?.epoch;
+    let treasury_key = ctx.accounts.treasury.key();
+    let authority = ctx.accounts.authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    for (&tier, feed) in tiers.iter().zip(feeds.iter()) {
+        // ─────────────────────────────────────────────
+        // 1) PDA must match the tier and be unused
+        // ─────────────────────────────────────────────
+        let (expected, bump) =
+            Pubkey::find_program_address(&[LiveFeed::SEED_PREFIX, &[tier]], &crate::ID);
+        require_keys_eq!(feed.key(), expected, IC42NErrorCode::InvalidLiveFeedState);
+        require!(feed.is_writable, IC42NErrorCode::InvalidInput);
+        require!(
+            feed.data_is_empty() && feed.owner == &system_program::ID,
+            IC42NErrorCode::InvalidLiveFeedState
+        );
+
+        // ─────────────────────────────────────────────
+        // 2) Ensure this tier is configured, then activate
+        // ─────────────────────────────────────────────
+        config.set_tier_active(tier, 1)?;
+
+        // ─────────────────────────────────────────────
+        // 3) Create + initialize the LiveFeed
+        // ─────────────────────────────────────────────
+        create_pda_account(
+            feed,
+            &authority,
+            &system_program,
+            8 + LiveFeed::SIZE,
+            &[LiveFeed::SEED_PREFIX, &[tier], &[bump]],
+        )?;
+
+        let mut data = feed.try_borrow_mut_data()?;
+        data[..8].copy_from_slice(LiveFeed::DISCRIMINATOR);
+        let mut live = LiveFeed::try_deserialize(&mut &data[..])?;
+
+        live.init_new(
+            current_epoch,
+            config.bet_cutoff_for(tier)?,
+            tier,
+            treasury_key,
+            bump,
+            config.fee_settings_for(tier)?.base_fee_bps,
+        );
+        live.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::utils::transfers::grow_account;
+
+/// Upgrades a `LEGACY_SIZE` LiveFeed (u32 bet counters) to the current
+/// layout with u64 `total_bets`, `carried_over_bets` and `bets_per_number`.
+///
+/// Authority only; the authority pays for the extra rent. Feeds already at
+/// `SIZE` are left untouched.
+#[derive(Accounts)]
+#[instruction(tier: u8)]
+pub struct MigrateLiveFeed<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The legacy layout can't be deserialized as `LiveFeed` until
+    /// rewritten; the PDA seeds, owner and discriminator are verified instead.
+    #[account(
+        mut,
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump,
+        owner = crate::ID @ IC42NErrorCode::Unauthorized,
+    )]
+    pub live_feed: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_live_feed_handler(ctx: Context<MigrateLiveFeed>, _tier: u8) -> Result<()> {
+    let feed = ctx.accounts.live_feed.to_account_info();
+    let old_len = feed.data_len();
+
+    {
+        let data = feed.try_borrow_data()?;
+        require!(data.len() >= 8 + LiveFeed::LEGACY_SIZE, IC42NErrorCode::InvalidInput);
+        require!(data[..8] == *LiveFeed::DISCRIMINATOR, IC42NErrorCode::InvalidInput);
+    }
+
+    if old_len == 8 + LiveFeed::SIZE {
+        return Ok(());
+    }
+    require_eq!(old_len, 8 + LiveFeed::LEGACY_SIZE, IC42NErrorCode::InvalidInput);
+
+    grow_account(
+        &feed,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + LiveFeed::SIZE,
+    )?;
+
+    let mut data = feed.try_borrow_mut_data()?;
+    LiveFeed::upgrade_legacy_counters(&mut data[8..])
+}
\ No newline at end of file
@@ -27,7 +27,8 @@ pub struct ResetLiveFeed<'info> {
 pub fn reset_live_feed_handler(
     ctx: Context<ResetLiveFeed>,
     tier: u8,
-    rollover: u8
+    rollover: u8,
+    preserve_chain: bool,
 ) -> Result<()> {
     let live = &mut ctx.accounts.live_feed;
     let config = &ctx.accounts.config;
@@ -54,26 +55,47 @@ pub fn reset_live_feed_handler(
     );
 
 
+    // Never skip past an epoch whose bets still need a ResolvedGame
+    require!(
+        !live.blocks_reset(current_epoch),
+        IC42NErrorCode::UnresolvedEpochBlocksReset
+    );
+
     // 3) Do not allow wiping a pot or bets by mistake.
     // This should always be true for the "no activity" path.
+    // A house seed alone doesn't count as activity and stays on the feed.
     require!(
-        live.total_lamports == 0
+        live.total_lamports == live.house_seed_lamports
             && live.carried_over_lamports == 0
             && live.total_bets == 0
             && live.carried_over_bets == 0,
         IC42NErrorCode::LiveFeedNotEmpty
     );
-    
-    live.reset_for_new_epoch(
-        current_epoch,
-        config.bet_cutoff_slots,
-        0, 
-        0,
-        [0u64; 10],
-        [0u32; 10],
-        rollover,
-        config.base_fee_bps
-    );
-    
+
+    let base_fee_bps = config.fee_settings_for(tier)?.base_fee_bps;
+
+    // 4) Re-sync only the epoch (keeping chain lineage), or start a new chain
+    if preserve_chain {
+        live.resync_epoch(
+            current_epoch,
+            config.bet_cutoff_for(tier)?,
+            rollover,
+            base_fee_bps
+        );
+    } else {
+        let house_seed = live.take_house_seed();
+        live.reset_for_new_epoch(
+            current_epoch,
+            config.bet_cutoff_for(tier)?,
+            0,
+            0,
+            [0u64; 10],
+            [0u64; 10],
+            rollover,
+            base_fee_bps
+        )?;
+        live.add_house_seed(house_seed)?;
+    }
+
     Ok(())
 }
\ No newline at end of file
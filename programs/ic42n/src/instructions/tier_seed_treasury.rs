@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::treasury::Treasury;
+use crate::utils::transfers::transfer_lamports;
+
+/// Authority deposits house liquidity into the treasury for a tier's pot.
+///
+/// The seed counts toward the feed's `total_lamports` (and so the payout),
+/// but not toward `total_bets` or any number's stake. If the game resolves
+/// without winners it is pulled out of the carry and can be returned to the
+/// fee vault with `recover_house_seed`.
+#[derive(Accounts)]
+#[instruction(tier: u8)]
+pub struct SeedTreasury<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump = live_feed.bump,
+    )]
+    pub live_feed: Account<'info, LiveFeed>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn seed_treasury_handler(ctx: Context<SeedTreasury>, tier: u8, lamports: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let live = &mut ctx.accounts.live_feed;
+
+    require!(lamports > 0, IC42NErrorCode::InvalidInput);
+    require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
+    require_keys_eq!(live.treasury, ctx.accounts.treasury.key(), IC42NErrorCode::InvalidInput);
+
+    let tier_cfg = config.get_tier_settings(tier)?;
+    require!(tier_cfg.is_active(), IC42NErrorCode::InactiveTier);
+
+    // Only seed the live epoch, never one waiting on resolution
+    require!(!live.is_behind(Clock::get()?.epoch), IC42NErrorCode::EpochMismatch);
+
+    transfer_lamports(
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        lamports,
+    )?;
+    ctx.accounts.treasury.add_obligation(lamports)?;
+
+    live.add_house_seed(lamports)
+}
\ No newline at end of file
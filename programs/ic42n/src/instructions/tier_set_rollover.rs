@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+
+/// Corrects a mis-set `secondary_rollover_number` without touching the rest
+/// of the feed. Only allowed while the feed holds no bets and no carry.
+#[derive(Accounts)]
+#[instruction(tier: u8)]
+pub struct SetSecondaryRollover<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump = live_feed.bump,
+    )]
+    pub live_feed: Account<'info, LiveFeed>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_secondary_rollover_handler(
+    ctx: Context<SetSecondaryRollover>,
+    tier: u8,
+    rollover: u8,
+) -> Result<()> {
+    let live = &mut ctx.accounts.live_feed;
+
+    require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
+
+    live.set_secondary_rollover(rollover)
+}
\ No newline at end of file
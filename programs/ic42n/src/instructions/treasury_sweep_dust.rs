@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::treasury::Treasury;
+use crate::utils::transfers::{require_treasury_can_pay, treasury_surplus};
+
+/// Moves treasury dust (lamports nobody is owed) to the fee vault.
+///
+/// The surplus is the balance left after `Treasury::obligations_lamports`
+/// and the rent-exempt reserve, so a treasury must be upgraded with
+/// `upgrade_treasury` first. Authority-only and blocked while
+/// `pause_fee_withdrawals` is set.
+#[derive(Accounts)]
+pub struct SweepTreasuryDust<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        address = config.fee_vault @ IC42NErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn sweep_treasury_dust_handler(ctx: Context<SweepTreasuryDust>) -> Result<()> {
+    require!(
+        !ctx.accounts.config.are_fee_withdrawals_paused(),
+        IC42NErrorCode::WithdrawPaused
+    );
+
+    let treasury = &mut ctx.accounts.treasury;
+    require!(treasury.tracks_obligations(), IC42NErrorCode::TreasuryNotUpgraded);
+
+    let total_obligations = treasury.obligations_lamports;
+    let treasury_balance = treasury.to_account_info().lamports();
+
+    let surplus = treasury_surplus(treasury_balance, total_obligations);
+    require!(surplus > 0, IC42NErrorCode::NoTreasurySurplus);
+    require_treasury_can_pay(treasury_balance, surplus)?;
+
+    msg!(
+        "Sweeping {} lamports of dust (balance={} obligations={})",
+        surplus,
+        treasury_balance,
+        total_obligations
+    );
+
+    treasury.total_fees_withdrawn = treasury
+        .total_fees_withdrawn
+        .checked_add(surplus)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+
+    **treasury.to_account_info().try_borrow_mut_lamports()? -= surplus;
+    **ctx.accounts.fee_vault.to_account_info().try_borrow_mut_lamports()? += surplus;
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::treasury::Treasury;
+
+/// Starts the running `obligations_lamports` total on a treasury created
+/// before `Treasury::OBLIGATIONS_VERSION` (see
+/// `Treasury::start_tracking_obligations`). Authority only; a treasury that
+/// already tracks its obligations is left untouched.
+#[derive(Accounts)]
+pub struct UpgradeTreasury<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = authority @ IC42NErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn upgrade_treasury_handler(ctx: Context<UpgradeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    if treasury.tracks_obligations() {
+        return Ok(());
+    }
+
+    let balance = treasury.to_account_info().lamports();
+    treasury.start_tracking_obligations(balance);
+
+    msg!("Treasury obligations start at {}", treasury.obligations_lamports);
+    Ok(())
+}
\ No newline at end of file
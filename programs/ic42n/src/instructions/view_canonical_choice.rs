@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::utils::prediction::canonicalize_choice;
+
+/// Canonical selection set for a digit-encoded choice.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CanonicalChoice {
+    /// Ascending, zero-padded past `selection_count`.
+    pub selections: [u8; 8],
+    pub selection_count: u8,
+    pub selections_mask: u16,
+}
+
+/// Read-only: simulate and read the return data.
+///
+/// No accounts; the result depends only on the arguments.
+#[derive(Accounts)]
+pub struct ComputeCanonicalChoice {}
+
+/// Canonicalizes `choice` exactly as `place_prediction` does, so clients can
+/// build `selections_mask` without reimplementing the digit rules.
+pub fn compute_canonical_choice_handler(
+    _ctx: Context<ComputeCanonicalChoice>,
+    choice: u32,
+    blocked: u8,
+) -> Result<CanonicalChoice> {
+    let (selections, selection_count, selections_mask) = canonicalize_choice(choice, blocked)?;
+    Ok(CanonicalChoice {
+        selections,
+        selection_count,
+        selections_mask,
+    })
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+
+/// Most games one `chain_lineage` call returns; 29 entries (35 bytes each
+/// plus the 4-byte length) fit in Solana's 1024-byte return data.
+pub const MAX_LINEAGE_ENTRIES: usize = 29;
+
+/// One game of a chain, as rendered in its jackpot history.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ChainLineageEntry {
+    pub epoch: u64,
+    pub status: u8,
+
+    /// `RolloverReason`; `None` for the game that paid out.
+    pub rollover_reason: u8,
+    pub winning_number: u8,
+
+    pub carry_in_lamports: u64,
+    pub carry_out_lamports: u64,
+    pub net_prize_pool: u64,
+}
+
+impl From<&ResolvedGame> for ChainLineageEntry {
+    fn from(game: &ResolvedGame) -> Self {
+        Self {
+            epoch: game.epoch,
+            status: game.status,
+            rollover_reason: game.rollover_reason,
+            winning_number: game.winning_number,
+            carry_in_lamports: game.carry_in_lamports,
+            carry_out_lamports: game.carry_out_lamports,
+            net_prize_pool: game.net_prize_pool,
+        }
+    }
+}
+
+/// Orders the games of the chain starting at `first_epoch` on `tier` by
+/// epoch. Every game must belong to that chain, and no epoch may repeat.
+pub fn build_chain_lineage(
+    first_epoch: u64,
+    tier: u8,
+    games: &[ResolvedGame],
+) -> Result<Vec<ChainLineageEntry>> {
+    require!(games.len() <= MAX_LINEAGE_ENTRIES, IC42NErrorCode::InvalidInput);
+
+    let mut entries = Vec::with_capacity(games.len());
+    for game in games.iter() {
+        require_eq!(game.tier, tier, IC42NErrorCode::TierMismatch);
+        require_eq!(game.first_epoch_in_chain, first_epoch, IC42NErrorCode::EpochMismatch);
+        entries.push(ChainLineageEntry::from(game));
+    }
+
+    entries.sort_by_key(|e| e.epoch);
+    require!(
+        entries.windows(2).all(|w| w[0].epoch < w[1].epoch),
+        IC42NErrorCode::InvalidInput
+    );
+
+    Ok(entries)
+}
+
+/// Read-only: simulate and read the return data.
+///
+/// `remaining_accounts` are the chain's ResolvedGame PDAs, in any order.
+#[derive(Accounts)]
+pub struct GetChainLineage {}
+
+pub fn chain_lineage_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetChainLineage>,
+    first_epoch: u64,
+    tier: u8,
+) -> Result<Vec<ChainLineageEntry>> {
+    let mut games = Vec::with_capacity(ctx.remaining_accounts.len());
+
+    for info in ctx.remaining_accounts.iter() {
+        // Checks owner + discriminator
+        let game: Account<ResolvedGame> = Account::try_from(info)?;
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[ResolvedGame::SEED_PREFIX, game.epoch.to_le_bytes().as_ref(), &[game.tier]],
+            ctx.program_id,
+        );
+        require_keys_eq!(info.key(), expected, IC42NErrorCode::InvalidInput);
+
+        games.push(game.into_inner());
+    }
+
+    build_chain_lineage(first_epoch, tier, &games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain_game(epoch: u64, reason: RolloverReason, carry_in: u64, carry_out: u64) -> ResolvedGame {
+        let mut game = ResolvedGame::deserialize(&mut &[0u8; ResolvedGame::BASE_SIZE][..]).unwrap();
+        game.epoch = epoch;
+        game.tier = 2;
+        game.first_epoch_in_chain = 700;
+        game.status = GameStatus::Resolved as u8;
+        game.rollover_reason = reason.as_u8();
+        game.carry_in_lamports = carry_in;
+        game.carry_out_lamports = carry_out;
+        game
+    }
+
+    #[test]
+    fn lineage_orders_a_mixed_chain() {
+        // Passed out of order: no winners, then the blocked number, then a payout
+        let mut paid = chain_game(702, RolloverReason::None, 5_000, 0);
+        paid.winning_number = 3;
+        paid.net_prize_pool = 7_600;
+        let games = [
+            paid,
+            chain_game(700, RolloverReason::NoWinners, 0, 2_000),
+            chain_game(701, RolloverReason::RolloverNumber, 2_000, 5_000),
+        ];
+
+        let lineage = build_chain_lineage(700, 2, &games).unwrap();
+        assert_eq!(lineage.iter().map(|e| e.epoch).collect::<Vec<_>>(), vec![700, 701, 702]);
+        assert_eq!(
+            lineage.iter().map(|e| e.rollover_reason).collect::<Vec<_>>(),
+            vec![
+                RolloverReason::NoWinners.as_u8(),
+                RolloverReason::RolloverNumber.as_u8(),
+                RolloverReason::None.as_u8(),
+            ]
+        );
+
+        // Each game carries in what the previous one carried out
+        assert!(lineage.windows(2).all(|w| w[0].carry_out_lamports == w[1].carry_in_lamports));
+        assert_eq!(lineage[2].net_prize_pool, 7_600);
+    }
+
+    #[test]
+    fn lineage_rejects_foreign_or_repeated_games() {
+        let game = chain_game(700, RolloverReason::NoWinners, 0, 2_000);
+
+        let mut other_chain = chain_game(705, RolloverReason::None, 0, 0);
+        other_chain.first_epoch_in_chain = 705;
+        assert!(build_chain_lineage(700, 2, &[game.clone(), other_chain]).is_err());
+
+        assert!(build_chain_lineage(700, 3, std::slice::from_ref(&game)).is_err());
+        assert!(build_chain_lineage(700, 2, &[game.clone(), game]).is_err());
+    }
+}
\ No newline at end of file
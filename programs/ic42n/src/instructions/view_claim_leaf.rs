@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::utils::merkle::hash_claim_leaf;
+
+/// Read-only: simulate and read the return data.
+///
+/// No accounts; the leaf depends only on the arguments.
+#[derive(Accounts)]
+pub struct ComputeClaimLeaf {}
+
+/// Canonical claim leaf exactly as `claim_prediction` rebuilds it for current
+/// (`IC42N_V3`) games, so off-chain Merkle builders can check their leaf
+/// encoding against the program.
+pub fn compute_claim_leaf_handler(
+    _ctx: Context<ComputeClaimLeaf>,
+    epoch: u64,
+    tier: u8,
+    index: u32,
+    claimer: Pubkey,
+    amount: u64,
+    selections_mask: u16,
+) -> Result<[u8; 32]> {
+    Ok(hash_claim_leaf(epoch, tier, index, &claimer, amount, selections_mask))
+}
\ No newline at end of file
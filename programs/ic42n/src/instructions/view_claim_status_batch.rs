@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::resolved_game::ResolvedGame;
+use crate::utils::bitmap::is_claimed;
+
+/// Most indices one `claim_status_batch` call answers; one bit each in the
+/// returned `u64`.
+pub const MAX_CLAIM_STATUS_INDICES: usize = 64;
+
+/// Bit `i` of the result is set if `indices[i]` is claimed in `bitmap`.
+///
+/// Every index must be below `total_winners`, so an out-of-range index is an
+/// error rather than a bit that looks claimed.
+pub fn claim_status_mask(bitmap: &Vec<u8>, total_winners: u32, indices: &[u32]) -> Result<u64> {
+    require!(indices.len() <= MAX_CLAIM_STATUS_INDICES, IC42NErrorCode::InvalidInput);
+
+    let mut mask = 0u64;
+    for (i, &index) in indices.iter().enumerate() {
+        require!(index < total_winners, IC42NErrorCode::InvalidClaimIndex);
+        if is_claimed(bitmap, index) {
+            mask |= 1u64 << i;
+        }
+    }
+    Ok(mask)
+}
+
+/// Read-only: simulate and read the return data.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct GetClaimStatusBatch<'info> {
+    #[account(
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, ResolvedGame>,
+}
+
+/// Claimed/unclaimed state of several winner indices of one game, read from
+/// its `claimed_bitmap` in a single call (see `claim_status_mask`).
+pub fn claim_status_batch_handler(
+    ctx: Context<GetClaimStatusBatch>,
+    _epoch: u64,
+    _tier: u8,
+    indices: Vec<u32>,
+) -> Result<u64> {
+    let game = &ctx.accounts.game;
+    claim_status_mask(&game.claimed_bitmap, game.total_winners, &indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bitmap::set_claimed;
+
+    #[test]
+    fn batch_reports_claimed_indices_in_request_order() {
+        let mut bitmap = vec![0u8; 2];
+        set_claimed(&mut bitmap, 1);
+        set_claimed(&mut bitmap, 9);
+
+        let mask = claim_status_mask(&bitmap, 12, &[0, 1, 9, 11, 1]).unwrap();
+        assert_eq!(mask, 0b10110);
+
+        assert_eq!(claim_status_mask(&bitmap, 12, &[]).unwrap(), 0);
+        assert_eq!(
+            claim_status_mask(&bitmap, 12, &[3, 12]).unwrap_err(),
+            error!(IC42NErrorCode::InvalidClaimIndex)
+        );
+        assert_eq!(
+            claim_status_mask(&bitmap, 12, &[0; MAX_CLAIM_STATUS_INDICES + 1]).unwrap_err(),
+            error!(IC42NErrorCode::InvalidInput)
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::utils::resolve::fee_bps_after_rollovers;
+
+/// Max entries returned by `get_fee_schedule`.
+pub const MAX_FEE_SCHEDULE_LEN: usize = 32;
+
+/// Fee preview for a tier's current chain.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FeeSchedule {
+    pub tier: u8,
+    pub epochs_carried_over: u8,
+
+    /// Max rollovers per chain (0 = unlimited).
+    pub max_rollovers: u8,
+
+    /// Tier's effective fees (see `Config::fee_settings_for`).
+    pub min_fee_bps: u16,
+    pub rollover_fee_step_bps: u16,
+
+    /// `schedule[0]` is the current fee; `schedule[i]` is the fee after `i`
+    /// more rollover-number carries. Ends at `min_fee_bps` or the rollover cap.
+    pub schedule: Vec<u16>,
+}
+
+/// Read-only: simulate and read the return data.
+#[derive(Accounts)]
+#[instruction(tier: u8)]
+pub struct GetFeeSchedule<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump = live_feed.bump,
+    )]
+    pub live_feed: Account<'info, LiveFeed>,
+}
+
+pub fn get_fee_schedule_handler(ctx: Context<GetFeeSchedule>, tier: u8) -> Result<FeeSchedule> {
+    let config = &ctx.accounts.config;
+    let live = &ctx.accounts.live_feed;
+
+    require_eq!(live.tier, tier, IC42NErrorCode::TierMismatch);
+
+    let fees = config.fee_settings_for(tier)?;
+
+    // Rollovers this chain may still take (the current fee is always listed)
+    let remaining = if config.max_rollovers == 0 {
+        MAX_FEE_SCHEDULE_LEN - 1
+    } else {
+        config.max_rollovers.saturating_sub(live.epochs_carried_over) as usize
+    }
+    .min(MAX_FEE_SCHEDULE_LEN - 1);
+
+    let mut schedule = Vec::with_capacity(remaining + 1);
+    for n in 0..=remaining {
+        let fee = fee_bps_after_rollovers(
+            live.current_fee_bps,
+            fees.rollover_fee_step_bps,
+            fees.min_fee_bps,
+            n as u8,
+        );
+        schedule.push(fee);
+        if fee <= fees.min_fee_bps {
+            break;
+        }
+    }
+
+    Ok(FeeSchedule {
+        tier,
+        epochs_carried_over: live.epochs_carried_over,
+        max_rollovers: config.max_rollovers,
+        min_fee_bps: fees.min_fee_bps,
+        rollover_fee_step_bps: fees.rollover_fee_step_bps,
+        schedule,
+    })
+}
\ No newline at end of file
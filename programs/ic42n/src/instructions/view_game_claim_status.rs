@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::resolved_game::ResolvedGame;
+
+/// Claim progress of a resolved game.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GameClaimStatus {
+    pub total_winners: u32,
+    pub claimed_winners: u32,
+    pub net_prize_pool: u64,
+    pub claimed_lamports: u64,
+
+    /// `net_prize_pool - claimed_lamports`, or 0 once swept.
+    pub unclaimed_lamports: u64,
+}
+
+/// Read-only: simulate and read the return data.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct GetGameClaimStatus<'info> {
+    #[account(
+        seeds = [ResolvedGame::SEED_PREFIX, epoch.to_le_bytes().as_ref(), &[tier]],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, ResolvedGame>,
+}
+
+pub fn game_claim_status_handler(
+    ctx: Context<GetGameClaimStatus>,
+    _epoch: u64,
+    _tier: u8,
+) -> Result<GameClaimStatus> {
+    let game = &ctx.accounts.game;
+
+    let unclaimed_lamports = if game.unclaimed_swept == 0 {
+        game.net_prize_pool.saturating_sub(game.claimed_lamports)
+    } else {
+        0
+    };
+
+    Ok(GameClaimStatus {
+        total_winners: game.total_winners,
+        claimed_winners: game.claimed_winners,
+        net_prize_pool: game.net_prize_pool,
+        claimed_lamports: game.claimed_lamports,
+        unclaimed_lamports,
+    })
+}
\ No newline at end of file
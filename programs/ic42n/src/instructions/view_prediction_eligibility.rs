@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::prediction::eligible_selections_mask;
+
+/// Which of a prediction's numbers can still win the tier's next draw.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PredictionEligibility {
+    /// `live_feed.secondary_rollover_number` the result was computed against.
+    pub blocked_number: u8,
+
+    /// Eligible selections, ascending, zero-padded past `eligible_count`.
+    pub eligible: [u8; 8],
+    pub eligible_mask: u16,
+
+    /// Effective coverage: 0 if the prediction covers the blocked number.
+    pub eligible_count: u8,
+    pub selection_count: u8,
+}
+
+/// Read-only: simulate and read the return data.
+#[derive(Accounts)]
+#[instruction(epoch: u64, tier: u8)]
+pub struct GetPredictionEligibility<'info> {
+    /// CHECK: Only used as a PDA seed for `prediction`.
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [LiveFeed::SEED_PREFIX, &[tier]],
+        bump = live_feed.bump,
+    )]
+    pub live_feed: Account<'info, LiveFeed>,
+
+    #[account(
+        seeds = [
+            Prediction::SEED_PREFIX,
+            player.key().as_ref(),
+            epoch.to_le_bytes().as_ref(),
+            &[tier],
+        ],
+        bump = prediction.bump,
+    )]
+    pub prediction: Account<'info, Prediction>,
+}
+
+/// `epoch` is the prediction's `game_epoch` (the first epoch of its chain).
+pub fn prediction_eligibility_handler(
+    ctx: Context<GetPredictionEligibility>,
+    _epoch: u64,
+    _tier: u8,
+) -> Result<PredictionEligibility> {
+    let pred = &ctx.accounts.prediction;
+    let blocked = ctx.accounts.live_feed.secondary_rollover_number;
+
+    let eligible_mask = eligible_selections_mask(pred.selections_mask, blocked);
+
+    let mut eligible = [0u8; 8];
+    let mut eligible_count = 0u8;
+    for n in (1u8..=9).filter(|&n| eligible_mask & (1u16 << n) != 0) {
+        eligible[eligible_count as usize] = n;
+        eligible_count += 1;
+    }
+
+    Ok(PredictionEligibility {
+        blocked_number: blocked,
+        eligible,
+        eligible_mask,
+        eligible_count,
+        selection_count: pred.selection_count,
+    })
+}
\ No newline at end of file
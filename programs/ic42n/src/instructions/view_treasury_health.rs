@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::*;
+use crate::state::treasury::Treasury;
+
+/// Treasury collateralization snapshot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TreasuryHealth {
+    /// Treasury lamports (incl. its rent-exempt reserve).
+    pub balance: u64,
+
+    /// Sum of `total_lamports` across the LiveFeeds passed in.
+    pub total_obligations: u64,
+
+    /// `total_obligations - balance`, or 0 when fully covered.
+    pub shortfall: u64,
+}
+
+/// Read-only: simulate and read the return data.
+///
+/// `remaining_accounts` are the LiveFeeds to account for (one per tier);
+/// pass every active tier to check the whole treasury before resolving.
+#[derive(Accounts)]
+pub struct GetTreasuryHealth<'info> {
+    #[account(
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+pub fn get_treasury_health_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetTreasuryHealth<'info>>,
+) -> Result<TreasuryHealth> {
+    let balance = ctx.accounts.treasury.to_account_info().lamports();
+
+    let mut seen_tiers: Vec<u8> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut total_obligations: u64 = 0;
+
+    for info in ctx.remaining_accounts.iter() {
+        // Checks owner + discriminator
+        let live: Account<LiveFeed> = Account::try_from(info)?;
+
+        let (expected, _) =
+            Pubkey::find_program_address(&[LiveFeed::SEED_PREFIX, &[live.tier]], ctx.program_id);
+        require_keys_eq!(info.key(), expected, IC42NErrorCode::InvalidInput);
+
+        // Counting a feed twice would overstate obligations
+        require!(!seen_tiers.contains(&live.tier), IC42NErrorCode::InvalidInput);
+        seen_tiers.push(live.tier);
+
+        total_obligations = total_obligations
+            .checked_add(live.total_lamports)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+    }
+
+    Ok(TreasuryHealth {
+        balance,
+        total_obligations,
+        shortfall: total_obligations.saturating_sub(balance),
+    })
+}
\ No newline at end of file
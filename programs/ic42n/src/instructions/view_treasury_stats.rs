@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use crate::state::treasury::Treasury;
+use crate::utils::transfers::{min_treasury_balance, treasury_surplus};
+
+/// Lifetime counters and current balances of the global treasury.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TreasuryStats {
+    pub total_in_lamports: u64,
+    pub total_out_lamports: u64,
+    pub total_fees_withdrawn: u64,
+    pub total_refunded_lamports: u64,
+    pub total_swept_lamports: u64,
+
+    /// Treasury lamports (incl. its rent-exempt reserve).
+    pub balance: u64,
+    pub rent_reserve: u64,
+
+    /// Lamports owed to players and the house (see `Treasury::owed_lamports`).
+    pub total_obligations: u64,
+
+    /// `balance - rent_reserve - total_obligations`, floored at 0.
+    pub available_balance: u64,
+}
+
+impl TreasuryStats {
+    pub fn new(treasury: &Treasury, balance: u64) -> Self {
+        let total_obligations = treasury.owed_lamports(balance);
+        Self {
+            total_in_lamports: treasury.total_in_lamports,
+            total_out_lamports: treasury.total_out_lamports,
+            total_fees_withdrawn: treasury.total_fees_withdrawn,
+            total_refunded_lamports: treasury.total_refunded_lamports,
+            total_swept_lamports: treasury.total_swept_lamports,
+            balance,
+            rent_reserve: min_treasury_balance(),
+            total_obligations,
+            available_balance: treasury_surplus(balance, total_obligations),
+        }
+    }
+}
+
+/// Read-only: simulate and read the return data.
+#[derive(Accounts)]
+pub struct GetTreasuryStats<'info> {
+    #[account(
+        seeds = [Treasury::SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+pub fn treasury_stats_handler(ctx: Context<GetTreasuryStats>) -> Result<TreasuryStats> {
+    let treasury = &ctx.accounts.treasury;
+    let balance = treasury.to_account_info().lamports();
+
+    Ok(TreasuryStats::new(treasury, balance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn treasury() -> Treasury {
+        Treasury {
+            authority: Pubkey::default(),
+            tier: 0,
+            bump: 0,
+            total_in_lamports: 9_000_000,
+            total_out_lamports: 4_000_000,
+            total_fees_withdrawn: 250_000,
+            version: Treasury::OBLIGATIONS_VERSION,
+            total_refunded_lamports: 100_000,
+            total_swept_lamports: 50_000,
+            obligations_lamports: 2_400_000,
+            _reserved: [0u8; 8],
+        }
+    }
+
+    #[test]
+    fn available_balance_is_balance_less_reserve_and_obligations() {
+        let reserve = min_treasury_balance();
+        let balance = reserve + 3_000_000;
+
+        let stats = TreasuryStats::new(&treasury(), balance);
+        assert_eq!(stats.rent_reserve, reserve);
+        assert_eq!(stats.total_obligations, 2_400_000);
+        assert_eq!(stats.available_balance, balance - reserve - 2_400_000);
+        assert_eq!(stats.total_in_lamports, 9_000_000);
+        assert_eq!(stats.total_fees_withdrawn, 250_000);
+
+        // Under-collateralized: nothing is available
+        let mut owing = treasury();
+        owing.obligations_lamports = 3_000_001;
+        assert_eq!(TreasuryStats::new(&owing, balance).available_balance, 0);
+
+        // Not upgraded yet: the whole balance is presumed owed
+        let mut legacy = treasury();
+        legacy.version = 1;
+        let stats = TreasuryStats::new(&legacy, balance);
+        assert_eq!(stats.total_obligations, 3_000_000);
+        assert_eq!(stats.available_balance, 0);
+    }
+}
\ No newline at end of file
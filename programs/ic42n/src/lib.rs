@@ -24,6 +24,7 @@ pub mod instructions;
 pub mod utils;
 pub mod errors;
 pub mod constants;
+pub mod events;
 
 use instructions::*;
 
@@ -51,11 +52,48 @@ pub mod ic42n {
         init_tier_live_feed_handler(ctx, tier)
     }
 
+    pub fn init_tier_live_feeds_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitTierLiveFeedsBatch<'info>>,
+        tiers: Vec<u8>,
+    ) -> Result<()> {
+        init_tier_live_feeds_batch_handler(ctx, tiers)
+    }
+
     // -------------------------------------------------------------------------
     // reset_live_feed
     // -------------------------------------------------------------------------
-    pub fn reset_live_feed(ctx: Context<ResetLiveFeed>, tier: u8, rollover: u8) -> Result<()> {
-        reset_live_feed_handler(ctx, tier, rollover)
+    pub fn reset_live_feed(
+        ctx: Context<ResetLiveFeed>,
+        tier: u8,
+        rollover: u8,
+        preserve_chain: bool,
+    ) -> Result<()> {
+        reset_live_feed_handler(ctx, tier, rollover, preserve_chain)
+    }
+
+    // -------------------------------------------------------------------------
+    // set_secondary_rollover
+    // -------------------------------------------------------------------------
+    pub fn set_secondary_rollover(
+        ctx: Context<SetSecondaryRollover>,
+        tier: u8,
+        rollover: u8,
+    ) -> Result<()> {
+        set_secondary_rollover_handler(ctx, tier, rollover)
+    }
+
+    // -------------------------------------------------------------------------
+    // seed_treasury
+    // -------------------------------------------------------------------------
+    pub fn seed_treasury(ctx: Context<SeedTreasury>, tier: u8, lamports: u64) -> Result<()> {
+        seed_treasury_handler(ctx, tier, lamports)
+    }
+
+    // -------------------------------------------------------------------------
+    // sweep_treasury_dust
+    // -------------------------------------------------------------------------
+    pub fn sweep_treasury_dust(ctx: Context<SweepTreasuryDust>) -> Result<()> {
+        sweep_treasury_dust_handler(ctx)
     }
 
     // -------------------------------------------------------------------------
@@ -65,6 +103,20 @@ pub mod ic42n {
         close_tier_live_feed_handler(ctx, tier)
     }
 
+    // -------------------------------------------------------------------------
+    // deactivate_and_refund_tier
+    // -------------------------------------------------------------------------
+    pub fn deactivate_and_refund_tier(ctx: Context<DeactivateAndRefundTier>, tier: u8) -> Result<()> {
+        deactivate_and_refund_tier_handler(ctx, tier)
+    }
+
+    // -------------------------------------------------------------------------
+    // migrate_live_feed
+    // -------------------------------------------------------------------------
+    pub fn migrate_live_feed(ctx: Context<MigrateLiveFeed>, tier: u8) -> Result<()> {
+        migrate_live_feed_handler(ctx, tier)
+    }
+
     // -------------------------------------------------------------------------
     // update_config
     // -------------------------------------------------------------------------
@@ -79,6 +131,26 @@ pub mod ic42n {
         new_rollover_fee_step_bps: Option<u16>,
         new_cutoff_slots: Option<u64>,
         new_roll_over_number: Option<u8>,
+        new_max_rollovers: Option<u8>,
+        new_max_predictions_per_epoch: Option<u16>,
+        emergency_refunds_open: Option<u8>,
+        new_merkle_scheme: Option<u8>,
+        new_claim_window_epochs: Option<u16>,
+        new_resolver: Option<Pubkey>,
+        new_min_resolve_lamports: Option<u64>,
+        new_rng_max_age_slots: Option<u64>,
+        allow_type_switch: Option<u8>,
+        new_dispute_window_slots: Option<u64>,
+        new_fee_delay_slots: Option<u64>,
+        strict_cutoff: Option<u8>,
+        new_welcome_tickets: Option<u8>,
+        pause_claims: Option<u8>,
+        pause_fee_withdrawals: Option<u8>,
+        new_min_winners_for_fee: Option<u32>,
+        new_fee_rounding: Option<u8>,
+        new_max_active_tiers: Option<u8>,
+        new_max_fee_lamports: Option<u64>,
+        verbose_logging: Option<u8>,
         tier_updates: Vec<TierUpdateArgs>,
     ) -> Result<()> {
         update_config_handler(
@@ -92,10 +164,66 @@ pub mod ic42n {
             new_rollover_fee_step_bps,
             new_cutoff_slots,
             new_roll_over_number,
+            new_max_rollovers,
+            new_max_predictions_per_epoch,
+            emergency_refunds_open,
+            new_merkle_scheme,
+            new_claim_window_epochs,
+            new_resolver,
+            new_min_resolve_lamports,
+            new_rng_max_age_slots,
+            allow_type_switch,
+            new_dispute_window_slots,
+            new_fee_delay_slots,
+            strict_cutoff,
+            new_welcome_tickets,
+            pause_claims,
+            pause_fee_withdrawals,
+            new_min_winners_for_fee,
+            new_fee_rounding,
+            new_max_active_tiers,
+            new_max_fee_lamports,
+            verbose_logging,
             tier_updates,
         )
     }
 
+    // -------------------------------------------------------------------------
+    // authority handoff
+    // -------------------------------------------------------------------------
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        propose_authority_handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        accept_authority_handler(ctx)
+    }
+
+    // -------------------------------------------------------------------------
+    // fee vault handoff
+    // -------------------------------------------------------------------------
+    pub fn propose_fee_vault(ctx: Context<ProposeFeeVault>, new_fee_vault: Pubkey) -> Result<()> {
+        propose_fee_vault_handler(ctx, new_fee_vault)
+    }
+
+    pub fn accept_fee_vault(ctx: Context<AcceptFeeVault>) -> Result<()> {
+        accept_fee_vault_handler(ctx)
+    }
+
+    // -------------------------------------------------------------------------
+    // upgrade_config
+    // -------------------------------------------------------------------------
+    pub fn upgrade_config(ctx: Context<UpgradeConfig>) -> Result<()> {
+        upgrade_config_handler(ctx)
+    }
+
+    // -------------------------------------------------------------------------
+    // upgrade_treasury
+    // -------------------------------------------------------------------------
+    pub fn upgrade_treasury(ctx: Context<UpgradeTreasury>) -> Result<()> {
+        upgrade_treasury_handler(ctx)
+    }
+
     // -------------------------------------------------------------------------
     // emergency_pause_all
     // -------------------------------------------------------------------------
@@ -111,10 +239,124 @@ pub mod ic42n {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             vec![],
         )
     }
 
+    // -------------------------------------------------------------------------
+    // get_fee_schedule (view)
+    // -------------------------------------------------------------------------
+    pub fn get_fee_schedule(ctx: Context<GetFeeSchedule>, tier: u8) -> Result<FeeSchedule> {
+        get_fee_schedule_handler(ctx, tier)
+    }
+
+    // -------------------------------------------------------------------------
+    // get_treasury_health (view)
+    // -------------------------------------------------------------------------
+    pub fn get_treasury_health<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetTreasuryHealth<'info>>,
+    ) -> Result<TreasuryHealth> {
+        get_treasury_health_handler(ctx)
+    }
+
+    // -------------------------------------------------------------------------
+    // treasury_stats (view)
+    // -------------------------------------------------------------------------
+    pub fn treasury_stats(ctx: Context<GetTreasuryStats>) -> Result<TreasuryStats> {
+        treasury_stats_handler(ctx)
+    }
+
+    // -------------------------------------------------------------------------
+    // compute_claim_leaf (view)
+    // -------------------------------------------------------------------------
+    pub fn compute_claim_leaf(
+        ctx: Context<ComputeClaimLeaf>,
+        epoch: u64,
+        tier: u8,
+        index: u32,
+        claimer: Pubkey,
+        amount: u64,
+        selections_mask: u16,
+    ) -> Result<[u8; 32]> {
+        compute_claim_leaf_handler(ctx, epoch, tier, index, claimer, amount, selections_mask)
+    }
+
+    // -------------------------------------------------------------------------
+    // game_claim_status (view)
+    // -------------------------------------------------------------------------
+    pub fn game_claim_status(
+        ctx: Context<GetGameClaimStatus>,
+        epoch: u64,
+        tier: u8,
+    ) -> Result<GameClaimStatus> {
+        game_claim_status_handler(ctx, epoch, tier)
+    }
+
+    // -------------------------------------------------------------------------
+    // compute_canonical_choice (view)
+    // -------------------------------------------------------------------------
+    pub fn compute_canonical_choice(
+        ctx: Context<ComputeCanonicalChoice>,
+        choice: u32,
+        blocked: u8,
+    ) -> Result<CanonicalChoice> {
+        compute_canonical_choice_handler(ctx, choice, blocked)
+    }
+
+    // -------------------------------------------------------------------------
+    // prediction_eligibility (view)
+    // -------------------------------------------------------------------------
+    pub fn prediction_eligibility(
+        ctx: Context<GetPredictionEligibility>,
+        epoch: u64,
+        tier: u8,
+    ) -> Result<PredictionEligibility> {
+        prediction_eligibility_handler(ctx, epoch, tier)
+    }
+
+    // -------------------------------------------------------------------------
+    // chain_lineage (view)
+    // -------------------------------------------------------------------------
+    pub fn chain_lineage<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetChainLineage>,
+        first_epoch: u64,
+        tier: u8,
+    ) -> Result<Vec<ChainLineageEntry>> {
+        chain_lineage_handler(ctx, first_epoch, tier)
+    }
+
+    // -------------------------------------------------------------------------
+    // claim_status_batch (view)
+    // -------------------------------------------------------------------------
+    pub fn claim_status_batch(
+        ctx: Context<GetClaimStatusBatch>,
+        epoch: u64,
+        tier: u8,
+        indices: Vec<u32>,
+    ) -> Result<u64> {
+        claim_status_batch_handler(ctx, epoch, tier, indices)
+    }
+
     // -------------------------------------------------------------------------
     // update_tier_active
     // -------------------------------------------------------------------------
@@ -132,8 +374,18 @@ pub mod ic42n {
         prediction_type: u8,
         choice: u32,
         lamports: u64,
+        referrer: Option<Pubkey>,
+        recent_bets_cap: Option<u16>,
     ) -> Result<()> {
-        place_prediction_handler(ctx, tier, prediction_type, choice, lamports)
+        place_prediction_handler(
+            ctx,
+            tier,
+            prediction_type,
+            choice,
+            lamports,
+            referrer,
+            recent_bets_cap,
+        )
     }
 
     pub fn change_prediction_number(
@@ -145,6 +397,23 @@ pub mod ic42n {
         change_prediction_number_handler(ctx, tier, new_prediction_type, new_choice)
     }
 
+    // Like change_prediction_number, but selection_count may change; the
+    // lamport delta is charged to / refunded from the treasury.
+    pub fn change_prediction_coverage(
+        ctx: Context<ChangePredictionCoverage>,
+        tier: u8,
+        new_prediction_type: u8,
+        new_choice: u32,
+    ) -> Result<()> {
+        change_prediction_coverage_handler(ctx, tier, new_prediction_type, new_choice)
+    }
+
+    // Spends a ticket to redraw the same number of selections at random
+    // (deterministic; see `reroll_seed`).
+    pub fn reroll_prediction(ctx: Context<RerollPrediction>, tier: u8) -> Result<()> {
+        reroll_prediction_handler(ctx, tier)
+    }
+
     pub fn increase_prediction(
         ctx: Context<IncreasePrediction>,
         tier: u8,
@@ -166,6 +435,40 @@ pub mod ic42n {
         claim_prediction_handler(ctx, epoch, tier, index, amount, proof)
     }
 
+    // Bulk payout of several winners verified with one Merkle multiproof
+    pub fn claim_multiple_winners<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimMultipleWinners<'info>>,
+        epoch: u64,
+        tier: u8,
+        leaves: Vec<MultiClaimLeaf>,
+        proof_flags: Vec<bool>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        claim_multiple_winners_handler(ctx, epoch, tier, leaves, proof_flags, proof)
+    }
+
+    // Emergency exit: full refund while the protocol is fully paused
+    pub fn emergency_refund_prediction(
+        ctx: Context<EmergencyRefundPrediction>,
+        tier: u8,
+        game_epoch: u64,
+    ) -> Result<()> {
+        emergency_refund_prediction_handler(ctx, tier, game_epoch)
+    }
+
+    pub fn close_prediction(ctx: Context<ClosePrediction>, epoch: u64, tier: u8) -> Result<()> {
+        close_prediction_handler(ctx, epoch, tier)
+    }
+
+    // Grows a version 2 prediction to the current layout (anyone may pay)
+    pub fn migrate_prediction(
+        ctx: Context<MigratePrediction>,
+        game_epoch: u64,
+        tier: u8,
+    ) -> Result<()> {
+        migrate_prediction_handler(ctx, game_epoch, tier)
+    }
+
     // =====================================================================
     // GAME RESOLUTION / ROLLOVER / CLOSE
     // =====================================================================
@@ -181,6 +484,16 @@ pub mod ic42n {
         init_resolved_game_handler(ctx, epoch, tier, winning_number, rng_epoch_slot_used, rng_blockhash_used)
     }
 
+    pub fn resolve_with_fallback_rng<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveWithFallbackRng<'info>>,
+        epoch: u64,
+        tier: u8,
+        rng_epoch_slot_used: u64,
+        rng_blockhash_used: [u8; 32],
+    ) -> Result<()> {
+        resolve_with_fallback_rng_handler(ctx, epoch, tier, rng_epoch_slot_used, rng_blockhash_used)
+    }
+
     pub fn begin_resolve_game(ctx: Context<ReprocessResolveGame>, epoch: u64, tier: u8) -> Result<()> {
         reprocessing_resolve_game_handler(ctx, epoch, tier)
     }
@@ -194,6 +507,7 @@ pub mod ic42n {
         total_winners: u32,
         merkle_root: [u8; 32],
         results_uri: [u8; 128],
+        results_uri_ext: Vec<u8>,
     ) -> Result<()> {
         complete_resolve_game_handler(
             ctx,
@@ -204,6 +518,7 @@ pub mod ic42n {
             total_winners,
             merkle_root,
             results_uri,
+            results_uri_ext,
         )
     }
 
@@ -212,16 +527,81 @@ pub mod ic42n {
         epoch: u64,
         tier: u8,
         winning_number: u8,
+        is_rollover: bool,
         rng_epoch_slot_used: u64,
         rng_blockhash_used: [u8; 32],
     ) -> Result<()> {
-        complete_rollover_game_handler(ctx, epoch, tier, winning_number, rng_epoch_slot_used, rng_blockhash_used)
+        complete_rollover_game_handler(
+            ctx,
+            epoch,
+            tier,
+            winning_number,
+            is_rollover,
+            rng_epoch_slot_used,
+            rng_blockhash_used,
+        )
     }
 
     pub fn close_resolved_game(ctx: Context<CloseGame>, epoch: u64, tier: u8) -> Result<()> {
         close_resolved_game_handler(ctx, epoch, tier)
     }
 
+    pub fn close_resolved_games_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseGamesBatch<'info>>,
+        epoch: u64,
+    ) -> Result<()> {
+        close_resolved_games_batch_handler(ctx, epoch)
+    }
+
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>, epoch: u64, tier: u8) -> Result<()> {
+        sweep_unclaimed_handler(ctx, epoch, tier)
+    }
+
+    pub fn revert_resolution(
+        ctx: Context<RevertResolution>,
+        epoch: u64,
+        tier: u8,
+        winning_number: u8,
+    ) -> Result<()> {
+        revert_resolution_handler(ctx, epoch, tier, winning_number)
+    }
+
+    pub fn collect_protocol_fee(ctx: Context<CollectProtocolFee>, epoch: u64, tier: u8) -> Result<()> {
+        collect_protocol_fee_handler(ctx, epoch, tier)
+    }
+
+    pub fn assign_leaf_indices<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssignLeafIndices<'info>>,
+        epoch: u64,
+        tier: u8,
+        indices: Vec<u32>,
+    ) -> Result<()> {
+        assign_leaf_indices_handler(ctx, epoch, tier, indices)
+    }
+
+    pub fn recover_house_seed(ctx: Context<RecoverHouseSeed>, epoch: u64, tier: u8) -> Result<()> {
+        recover_house_seed_handler(ctx, epoch, tier)
+    }
+
+    pub fn freeze_game(ctx: Context<FreezeGame>, epoch: u64, tier: u8) -> Result<()> {
+        freeze_game_handler(ctx, epoch, tier)
+    }
+
+    pub fn unfreeze_game(ctx: Context<FreezeGame>, epoch: u64, tier: u8) -> Result<()> {
+        unfreeze_game_handler(ctx, epoch, tier)
+    }
+
+    // -------------------------------------------------------------------------
+    // leaderboards
+    // -------------------------------------------------------------------------
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>, epoch: u64, tier: u8) -> Result<()> {
+        init_leaderboard_handler(ctx, epoch, tier)
+    }
+
+    pub fn close_leaderboard(ctx: Context<CloseLeaderboard>, epoch: u64, tier: u8) -> Result<()> {
+        close_leaderboard_handler(ctx, epoch, tier)
+    }
+
     // -------------------------------------------------------------------------
     // award tickets
     // -------------------------------------------------------------------------
@@ -239,4 +619,11 @@ pub mod ic42n {
     pub fn close_profile(ctx: Context<ClosePlayerProfile>) -> Result<()> {
         close_player_profile_handler(ctx)
     }
+
+    // -------------------------------------------------------------------------
+    // migrate_player_profile
+    // -------------------------------------------------------------------------
+    pub fn migrate_player_profile(ctx: Context<MigratePlayerProfile>) -> Result<()> {
+        migrate_player_profile_handler(ctx)
+    }
 }
\ No newline at end of file
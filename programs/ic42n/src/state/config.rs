@@ -1,7 +1,21 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{DEFAULT_WELCOME_TICKETS, FEE_VAULT_ACCEPT_DELAY_EPOCHS, MAX_TICKETS_PER_PLAYER};
 use crate::errors::IC42NErrorCode;
-use crate::state::tiers::TierSettings;
+use crate::state::resolved_game::ResolvedGame;
+use crate::state::tiers::{TierFeeSettings, TierSettings};
+
+/// How fee amounts are rounded to whole lamports (see `utils::math::bps_of`).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeeRounding {
+    Floor  = 0,
+    HalfUp = 1, // Half a lamport or more rounds up
+    Ceil   = 2,
+}
+impl FeeRounding {
+    pub fn as_u8(self) -> u8 { self as u8 }
+}
 
 /// Global configuration PDA.
 ///
@@ -12,7 +26,8 @@ pub struct Config {
     /// 1 = betting paused, 0 = enabled.
     pub pause_bet: u8,
 
-    /// 1 = withdrawals/claims paused, 0 = enabled.
+    /// Legacy alias: 1 = both `pause_claims` and `pause_fee_withdrawals` are
+    /// set (kept in sync by `apply_withdraw_pauses`), 0 = otherwise.
     pub pause_withdraw: u8,
 
     /// Program admin authority.
@@ -48,15 +63,127 @@ pub struct Config {
     /// Fee step applied to rollover scenarios (basis points).
     pub rollover_fee_step_bps: u16,
 
+    /// Maximum consecutive rollovers allowed per chain (0 = unlimited).
+    pub max_rollovers: u8,
+
+    /// Maximum predictions a single player may place per epoch (0 = unlimited).
+    pub max_predictions_per_epoch: u16,
+
+    /// 1 = emergency refunds enabled (only honored while fully paused), 0 = disabled.
+    pub emergency_refunds_open: u8,
+
+    /// Merkle scheme used by newly resolved games (see `MerkleScheme`).
+    pub merkle_scheme: u8,
+
+    /// Epochs after resolution during which winners may claim (0 = no expiry).
+    pub claim_window_epochs: u16,
+
+    /// 1 = `change_prediction_number` may switch `prediction_type`,
+    /// 0 = the type must stay the same.
+    pub allow_type_switch: u8,
+
     /// Reserved space for future upgrades.
-    pub _reserved: [u8; 16],
+    pub _reserved: [u8; 8],
+
+    // ─────────────────────────────
+    // Appended after `_reserved` (see `SIZE_V1`)
+    // ─────────────────────────────
+
+    /// Fee vault proposed via `propose_fee_vault` (default = none pending).
+    pub pending_fee_vault: Pubkey,
+
+    /// Epoch in which `pending_fee_vault` was proposed.
+    pub pending_fee_vault_epoch: u64,
+
+    /// Authority proposed via `propose_authority` (default = none pending).
+    pub pending_authority: Pubkey,
+
+    /// Lower-privilege key allowed to run resolution (default = authority only).
+    pub resolver: Pubkey,
+
+    /// Pots below this many lamports must roll over instead of resolving (0 = disabled).
+    pub min_resolve_lamports: u64,
+
+    /// Max slots between the RNG slot and the end of the resolved epoch
+    /// (0 = any slot inside the epoch).
+    pub rng_max_age_slots: u64,
+
+    /// Slots after a winning resolve during which claims are locked and the
+    /// authority may `revert_resolution` (0 = claims open immediately).
+    pub dispute_window_slots: u64,
+
+    /// Slots after resolve before the owed protocol fee can be collected.
+    pub fee_delay_slots: u64,
+
+    /// Per-tier fee schedules, indexed like `tiers` (see `fee_settings_for`).
+    pub tier_fees: [TierFeeSettings; 5],
+
+    /// Per-tier winner cap, indexed like `tiers` (0 = `MAX_WINNERS_PER_GAME`).
+    /// Lives here for the same reason as `tier_fees`.
+    pub tier_max_winners: [u32; 5],
+
+    /// 1 = a clock/schedule epoch mismatch closes betting instead of allowing it.
+    /// Set to 1 by `initialize` and `upgrade_config`.
+    pub strict_cutoff: u8,
+
+    /// Per-tier betting cutoff in slots, indexed like `tiers`
+    /// (0 = global `bet_cutoff_slots`; see `bet_cutoff_for`).
+    pub tier_bet_cutoff_slots: [u64; 5],
+
+    /// Change tickets granted on a player's first bet (0 = none; see
+    /// `welcome_tickets_granted`).
+    pub welcome_tickets: u8,
+
+    /// 1 = player claims paused, 0 = enabled.
+    pub pause_claims: u8,
+
+    /// 1 = fee vault sweeps (fees, unclaimed winnings, house seeds, dust)
+    /// paused, 0 = enabled.
+    pub pause_fee_withdrawals: u8,
+
+    /// Games with fewer winners than this pay out the full pot with no
+    /// protocol fee (0 = always charge; see `is_fee_waived`).
+    pub min_winners_for_fee: u32,
+
+    /// `FeeRounding` for protocol fees (0 = floor, which configs grown by
+    /// `upgrade_config` also get).
+    pub fee_rounding: u8,
+
+    /// Per-tier cap on a single prediction's total stake
+    /// (`lamports_per_number * selection_count`), indexed like `tiers`
+    /// (0 = no cap; see `max_total_exposure_for`).
+    pub tier_max_total_exposure: [u64; 5],
+
+    /// Per-tier switch for `change_prediction_number`, indexed like `tiers`.
+    /// Stored inverted so grown configs keep allowing changes:
+    /// 0 = changes allowed, 1 = disabled (see `assert_changes_allowed`).
+    pub tier_changes_disabled: [u8; 5],
+
+    /// Max tiers that may be active at once (0 = no cap; see
+    /// `assert_can_activate`). Only new activations are checked.
+    pub max_active_tiers: u8,
+
+    /// Tickets one `change_prediction_number` costs per tier, indexed like
+    /// `tiers` (0 = 1 ticket; see `change_ticket_cost_for`).
+    pub tier_change_ticket_cost: [u8; 5],
+
+    /// Absolute cap on one game's protocol fee in lamports; anything above
+    /// stays in the prize pool (0 = no cap; see `cap_fee`).
+    pub max_fee_lamports: u64,
+
+    /// 1 = log non-essential traces (e.g. every betting cutoff check),
+    /// 0 = quiet, for mainnet.
+    pub verbose_logging: u8,
 }
 
 impl Config {
     pub const SEED: &'static [u8] = b"config";
 
-    /// Serialized size excluding the 8-byte Anchor discriminator.
-    pub const SIZE: usize =
+    /// Size of the original layout (everything up to and including `_reserved`),
+    /// excluding the 8-byte Anchor discriminator.
+    ///
+    /// Configs created at this size are grown with `upgrade_config`.
+    pub const SIZE_V1: usize =
         1 +  // pause_bet
             1 +  // pause_withdraw
             32 + // authority
@@ -70,7 +197,48 @@ impl Config {
             1 +  // bump
             2 +  // min_fee_bps
             2 +  // rollover_fee_step_bps
-            16;  // reserved
+            1 +  // max_rollovers
+            2 +  // max_predictions_per_epoch
+            1 +  // emergency_refunds_open
+            1 +  // merkle_scheme
+            2 +  // claim_window_epochs
+            1 +  // allow_type_switch
+            8;   // reserved
+
+    /// Size after `upgrade_config`: `SIZE_V1` plus every field appended after
+    /// `_reserved`, excluding the 8-byte Anchor discriminator.
+    ///
+    /// New Config fields go at the end of this tail (never into the V1
+    /// layout), and this constant grows with them; `upgrade_config` zero-fills
+    /// whatever part of the tail an account is missing.
+    pub const SIZE_V2: usize =
+        Self::SIZE_V1 +
+            32 + // pending_fee_vault
+            8 +  // pending_fee_vault_epoch
+            32 + // pending_authority
+            32 + // resolver
+            8 +  // min_resolve_lamports
+            8 +  // rng_max_age_slots
+            8 +  // dispute_window_slots
+            8 +  // fee_delay_slots
+            (TierFeeSettings::SIZE * 5) + // tier_fees
+            (4 * 5) + // tier_max_winners
+            1 +  // strict_cutoff
+            (8 * 5) + // tier_bet_cutoff_slots
+            1 +  // welcome_tickets
+            1 +  // pause_claims
+            1 +  // pause_fee_withdrawals
+            4 +  // min_winners_for_fee
+            1 +  // fee_rounding
+            (8 * 5) + // tier_max_total_exposure
+            5 +  // tier_changes_disabled
+            1 +  // max_active_tiers
+            5 +  // tier_change_ticket_cost
+            8 +  // max_fee_lamports
+            1;   // verbose_logging
+
+    /// Serialized size excluding the 8-byte Anchor discriminator.
+    pub const SIZE: usize = Self::SIZE_V2;
 
     /// Returns tier settings by tier id (1..=5).
     pub fn get_tier_settings(&self, tier_id: u8) -> Result<TierSettings> {
@@ -81,8 +249,104 @@ impl Config {
             .ok_or_else(|| error!(IC42NErrorCode::UnknownTier))
     }
 
+    /// Index of `tier_id` in `tiers`, which also indexes every per-tier
+    /// array on Config.
+    fn tier_index(&self, tier_id: u8) -> Result<usize> {
+        self.tiers
+            .iter()
+            .position(|t| t.tier_id == tier_id)
+            .ok_or_else(|| error!(IC42NErrorCode::UnknownTier))
+    }
+
+    /// Fee schedule for a tier: its own `tier_fees` entry when enabled,
+    /// otherwise the global base / min / step fees.
+    pub fn fee_settings_for(&self, tier_id: u8) -> Result<TierFeeSettings> {
+        let idx = self.tier_index(tier_id)?;
+
+        let fees = self.tier_fees[idx];
+        if fees.is_enabled() {
+            return Ok(fees);
+        }
+
+        Ok(TierFeeSettings {
+            enabled: 0,
+            base_fee_bps: self.base_fee_bps,
+            min_fee_bps: self.min_fee_bps,
+            rollover_fee_step_bps: self.rollover_fee_step_bps,
+        })
+    }
+
+    /// Max winners a game of this tier may resolve with.
+    pub fn max_winners_for(&self, tier_id: u8) -> Result<u32> {
+        let idx = self.tier_index(tier_id)?;
+
+        Ok(match self.tier_max_winners[idx] {
+            0 => ResolvedGame::MAX_WINNERS_PER_GAME as u32,
+            cap => cap,
+        })
+    }
+
+    /// Cap on one prediction's total stake in this tier (0 = no cap).
+    pub fn max_total_exposure_for(&self, tier_id: u8) -> Result<u64> {
+        let idx = self.tier_index(tier_id)?;
+
+        Ok(self.tier_max_total_exposure[idx])
+    }
+
+    /// Fails with `ChangesDisabledForTier` if this tier forbids
+    /// `change_prediction_number`.
+    pub fn assert_changes_allowed(&self, tier_id: u8) -> Result<()> {
+        let idx = self.tier_index(tier_id)?;
+
+        require!(self.tier_changes_disabled[idx] == 0, IC42NErrorCode::ChangesDisabledForTier);
+        Ok(())
+    }
+
+    /// Tickets a number change costs in this tier (at least 1).
+    pub fn change_ticket_cost_for(&self, tier_id: u8) -> Result<u32> {
+        let idx = self.tier_index(tier_id)?;
+
+        Ok(match self.tier_change_ticket_cost[idx] {
+            0 => 1,
+            cost => cost as u32,
+        })
+    }
+
+    /// Betting cutoff for LiveFeeds of this tier.
+    pub fn bet_cutoff_for(&self, tier_id: u8) -> Result<u64> {
+        let idx = self.tier_index(tier_id)?;
+
+        Ok(match self.tier_bet_cutoff_slots[idx] {
+            0 => self.bet_cutoff_slots,
+            cutoff => cutoff,
+        })
+    }
+
+    pub fn active_tier_count(&self) -> usize {
+        self.tiers.iter().filter(|t| t.is_active()).count()
+    }
+
+    /// Fails with `MaxActiveTiersExceeded` if activating `tier_id` would
+    /// exceed `max_active_tiers`. Tiers that are already active always pass.
+    pub fn assert_can_activate(&self, tier_id: u8) -> Result<()> {
+        let settings = self.get_tier_settings(tier_id)?;
+        if settings.is_active() || self.max_active_tiers == 0 {
+            return Ok(());
+        }
+
+        require!(
+            self.active_tier_count() < self.max_active_tiers as usize,
+            IC42NErrorCode::MaxActiveTiersExceeded
+        );
+        Ok(())
+    }
+
     /// Sets the `active` flag for a tier.
     pub fn set_tier_active(&mut self, tier_id: u8, active: u8) -> Result<()> {
+        if active == 1 {
+            self.assert_can_activate(tier_id)?;
+        }
+
         let settings = self
             .tiers
             .iter_mut()
@@ -90,25 +354,173 @@ impl Config {
             .ok_or_else(|| error!(IC42NErrorCode::UnknownTier))?;
 
         if active == 1 {
-            require!(
-                settings.max_bet_lamports > 0 && settings.curve_factor > 0.0,
-                IC42NErrorCode::InactiveTier
-            );
+            settings.check_activatable()?;
         }
 
         settings.active = active;
         Ok(())
     }
 
+    /// Tickets a newly created profile starts with, capped at
+    /// `MAX_TICKETS_PER_PLAYER`.
+    pub fn welcome_tickets_granted(&self) -> u32 {
+        (self.welcome_tickets as u32).min(MAX_TICKETS_PER_PLAYER)
+    }
+
+    /// Writes the fields appended after `SIZE_V1` whose zero value isn't the
+    /// intended default. Run by `upgrade_config` on a freshly grown V1 Config.
+    pub fn set_upgrade_defaults(&mut self) {
+        self.welcome_tickets = DEFAULT_WELCOME_TICKETS;
+        self.strict_cutoff = 1;
+    }
+
+    pub fn is_verbose_logging(&self) -> bool {
+        self.verbose_logging != 0
+    }
+
+    pub fn is_strict_cutoff(&self) -> bool {
+        self.strict_cutoff != 0
+    }
+
     pub fn is_betting_paused(&self) -> bool {
         self.pause_bet != 0
     }
+
+    pub fn are_claims_paused(&self) -> bool {
+        self.pause_claims != 0
+    }
+
+    pub fn are_fee_withdrawals_paused(&self) -> bool {
+        self.pause_fee_withdrawals != 0
+    }
+
+    /// True if a game with `total_winners` winners is resolved without a
+    /// protocol fee. Games without winners roll over instead and never get here.
+    pub fn is_fee_waived(&self, total_winners: u32) -> bool {
+        total_winners > 0 && total_winners < self.min_winners_for_fee
+    }
+
+    /// `fee` clamped to `max_fee_lamports` (unchanged when the cap is 0).
+    pub fn cap_fee(&self, fee: u64) -> u64 {
+        match self.max_fee_lamports {
+            0 => fee,
+            cap => fee.min(cap),
+        }
+    }
+
+    /// Rounding mode for protocol fees; unknown values fall back to floor.
+    pub fn fee_rounding_mode(&self) -> FeeRounding {
+        match self.fee_rounding {
+            1 => FeeRounding::HalfUp,
+            2 => FeeRounding::Ceil,
+            _ => FeeRounding::Floor,
+        }
+    }
+
+    /// Applies withdrawal pause updates. `pause_withdraw` sets both flags
+    /// and is applied first, so a specific flag passed alongside it wins.
+    /// `pause_withdraw` is then re-derived as "both paused".
+    pub fn apply_withdraw_pauses(
+        &mut self,
+        pause_withdraw: Option<u8>,
+        pause_claims: Option<u8>,
+        pause_fee_withdrawals: Option<u8>,
+    ) {
+        let flag = |v: u8| if v == 1 { 1 } else { 0 };
+
+        if let Some(pause) = pause_withdraw {
+            self.pause_claims = flag(pause);
+            self.pause_fee_withdrawals = flag(pause);
+        }
+        if let Some(pause) = pause_claims {
+            self.pause_claims = flag(pause);
+        }
+        if let Some(pause) = pause_fee_withdrawals {
+            self.pause_fee_withdrawals = flag(pause);
+        }
+
+        self.pause_withdraw = self.pause_claims & self.pause_fee_withdrawals;
+    }
+
+    /// Emergency refunds require a full pause (bets + withdrawals) and the
+    /// explicit `emergency_refunds_open` switch.
+    pub fn are_emergency_refunds_open(&self) -> bool {
+        self.pause_bet != 0 && self.pause_withdraw != 0 && self.emergency_refunds_open != 0
+    }
+
+    /// Returns true if a chain that has already carried over
+    /// `epochs_carried_over` times may roll over once more.
+    pub fn is_rollover_allowed(&self, epochs_carried_over: u8) -> bool {
+        self.max_rollovers == 0 || epochs_carried_over < self.max_rollovers
+    }
+
+    /// Returns true if a player who already placed `placed_this_epoch`
+    /// predictions this epoch may place another one.
+    pub fn is_epoch_prediction_allowed(&self, placed_this_epoch: u16) -> bool {
+        self.max_predictions_per_epoch == 0 || placed_this_epoch < self.max_predictions_per_epoch
+    }
+
+    /// Last epoch in which a game resolved during `resolved_epoch` can be
+    /// claimed. Returns 0 (no deadline) when the claim window is disabled.
+    pub fn claim_deadline_for(&self, resolved_epoch: u64) -> u64 {
+        if self.claim_window_epochs == 0 {
+            0
+        } else {
+            resolved_epoch.saturating_add(self.claim_window_epochs as u64)
+        }
+    }
+
+    /// Returns true if a pot of `total_lamports` is too small to resolve and
+    /// should accumulate via rollover instead.
+    pub fn is_pot_below_resolve_threshold(&self, total_lamports: u64) -> bool {
+        self.min_resolve_lamports > 0 && total_lamports < self.min_resolve_lamports
+    }
+
+    /// Returns true if `signer` may run the resolution instructions.
+    pub fn can_resolve(&self, signer: &Pubkey) -> bool {
+        *signer == self.authority
+            || (self.resolver != Pubkey::default() && *signer == self.resolver)
+    }
+
+    /// Returns true if a prediction of type `current` may be changed to `new`.
+    pub fn is_type_change_allowed(&self, current: u8, new: u8) -> bool {
+        self.allow_type_switch != 0 || current == new
+    }
+
+    pub fn has_pending_authority(&self) -> bool {
+        self.pending_authority != Pubkey::default()
+    }
+
+    pub fn has_pending_fee_vault(&self) -> bool {
+        self.pending_fee_vault != Pubkey::default()
+    }
+
+    /// Returns true if `signer` may promote the pending fee vault at `current_epoch`.
+    ///
+    /// The pending vault itself can always accept. The authority can only
+    /// accept on its own once `FEE_VAULT_ACCEPT_DELAY_EPOCHS` have passed
+    /// since the proposal.
+    pub fn can_accept_fee_vault(&self, signer: &Pubkey, current_epoch: u64) -> bool {
+        if !self.has_pending_fee_vault() {
+            return false;
+        }
+        if *signer == self.pending_fee_vault {
+            return true;
+        }
+        *signer == self.authority
+            && current_epoch
+                >= self
+                    .pending_fee_vault_epoch
+                    .saturating_add(FEE_VAULT_ACCEPT_DELAY_EPOCHS)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::solana_program::sysvar::epoch_schedule::EpochSchedule;
     use borsh::BorshSerialize;
+    use crate::utils::betting::is_betting_open_at;
 
     fn default_tier(tier_id: u8) -> TierSettings {
         TierSettings {
@@ -120,12 +532,13 @@ mod tests {
             ticket_reward_bps: 0,
             ticket_reward_max: 0,
             tickets_per_recipient: 1,
-            _reserved: [0; 10],
+            max_lamports_per_number: 0,
+            leaderboard_enabled: 0,
+            paused: 0,
         }
     }
 
-    #[test]
-    fn config_size_matches_serialization() {
+    fn default_config() -> Config {
         let tiers = [
             default_tier(1),
             default_tier(2),
@@ -134,7 +547,7 @@ mod tests {
             default_tier(5),
         ];
 
-        let cfg = Config {
+        Config {
             pause_bet: 0,
             pause_withdraw: 0,
             authority: Pubkey::default(),
@@ -148,10 +561,419 @@ mod tests {
             bump: 0,
             min_fee_bps: 300,
             rollover_fee_step_bps: 100,
-            _reserved: [0; 16],
-        };
+            max_rollovers: 0,
+            max_predictions_per_epoch: 0,
+            emergency_refunds_open: 0,
+            merkle_scheme: 0,
+            claim_window_epochs: 0,
+            allow_type_switch: 0,
+            _reserved: [0; 8],
+            pending_fee_vault: Pubkey::default(),
+            pending_fee_vault_epoch: 0,
+            pending_authority: Pubkey::default(),
+            resolver: Pubkey::default(),
+            min_resolve_lamports: 0,
+            rng_max_age_slots: 0,
+            dispute_window_slots: 0,
+            fee_delay_slots: 0,
+            tier_fees: [TierFeeSettings::default(); 5],
+            tier_max_winners: [0; 5],
+            strict_cutoff: 0,
+            tier_bet_cutoff_slots: [0; 5],
+            welcome_tickets: DEFAULT_WELCOME_TICKETS,
+            pause_claims: 0,
+            pause_fee_withdrawals: 0,
+            min_winners_for_fee: 0,
+            fee_rounding: 0,
+            tier_max_total_exposure: [0; 5],
+            tier_changes_disabled: [0; 5],
+            max_active_tiers: 0,
+            tier_change_ticket_cost: [0; 5],
+            max_fee_lamports: 0,
+            verbose_logging: 0,
+        }
+    }
+
+    #[test]
+    fn config_size_matches_serialization() {
+        let cfg = default_config();
 
         let bytes = cfg.try_to_vec().unwrap();
         assert_eq!(bytes.len(), Config::SIZE);
+        assert_eq!(Config::SIZE - Config::SIZE_V1, 300);
+    }
+
+    #[test]
+    fn v1_config_round_trips_after_upgrade() {
+        let mut cfg = default_config();
+        cfg.authority = Pubkey::new_unique();
+        cfg.base_fee_bps = 500;
+        cfg.claim_window_epochs = 30;
+        let current = cfg.try_to_vec().unwrap();
+
+        // A V1 account only holds the original layout; `upgrade_config`
+        // reallocs it to SIZE_V2 with a zero-filled tail
+        let mut grown = current[..Config::SIZE_V1].to_vec();
+        grown.resize(Config::SIZE_V2, 0);
+
+        let upgraded = Config::deserialize(&mut grown.as_slice()).unwrap();
+        assert_eq!(upgraded.authority, cfg.authority);
+        assert_eq!(upgraded.base_fee_bps, 500);
+        assert_eq!(upgraded.claim_window_epochs, 30);
+        assert_eq!(upgraded.pending_authority, Pubkey::default());
+        assert_eq!(upgraded.resolver, Pubkey::default());
+        assert_eq!(upgraded.fee_delay_slots, 0);
+        assert_eq!(upgraded.tier_fees, [TierFeeSettings::default(); 5]);
+        assert_eq!(upgraded.tier_max_winners, [0; 5]);
+        assert!(!upgraded.is_strict_cutoff());
+        assert_eq!(upgraded.tier_bet_cutoff_slots, [0; 5]);
+        assert_eq!(upgraded.welcome_tickets_granted(), 0);
+        assert!(upgraded.assert_changes_allowed(3).is_ok());
+        assert_eq!(upgraded.change_ticket_cost_for(3).unwrap(), 1);
+
+        assert_eq!(upgraded.try_to_vec().unwrap(), grown);
+
+        // `upgrade_config` then writes the non-zero defaults
+        let mut upgraded = upgraded;
+        upgraded.set_upgrade_defaults();
+        assert_eq!(upgraded.welcome_tickets_granted(), DEFAULT_WELCOME_TICKETS as u32);
+        assert!(upgraded.is_strict_cutoff());
+    }
+
+    #[test]
+    fn welcome_tickets_follow_config() {
+        let mut cfg = default_config();
+        assert_eq!(cfg.welcome_tickets_granted(), DEFAULT_WELCOME_TICKETS as u32);
+
+        cfg.welcome_tickets = 3;
+        assert_eq!(cfg.welcome_tickets_granted(), 3);
+
+        // Zero really means no welcome tickets
+        cfg.welcome_tickets = 0;
+        assert_eq!(cfg.welcome_tickets_granted(), 0);
+
+        cfg.welcome_tickets = u8::MAX;
+        assert_eq!(cfg.welcome_tickets_granted(), MAX_TICKETS_PER_PLAYER);
+    }
+
+    #[test]
+    fn type_switch_follows_config_flag() {
+        let mut cfg = default_config();
+        let high_low = crate::state::Prediction::TYPE_HIGH_LOW;
+        let multi = crate::state::Prediction::TYPE_MULTI_NUMBER;
+
+        assert!(cfg.is_type_change_allowed(high_low, high_low));
+        assert!(!cfg.is_type_change_allowed(high_low, multi));
+
+        cfg.allow_type_switch = 1;
+        assert!(cfg.is_type_change_allowed(high_low, high_low));
+        assert!(cfg.is_type_change_allowed(high_low, multi));
+    }
+
+    #[test]
+    fn resolve_threshold_disabled_at_zero() {
+        let mut cfg = default_config();
+        assert!(!cfg.is_pot_below_resolve_threshold(0));
+        assert!(!cfg.is_pot_below_resolve_threshold(1));
+
+        cfg.min_resolve_lamports = 50_000_000;
+        assert!(cfg.is_pot_below_resolve_threshold(49_999_999));
+        assert!(!cfg.is_pot_below_resolve_threshold(50_000_000));
+        assert!(!cfg.is_pot_below_resolve_threshold(u64::MAX));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn tier_fees_fall_back_to_global() {
+        let mut cfg = default_config();
+        cfg.base_fee_bps = 500;
+
+        let tier3 = cfg.fee_settings_for(3).unwrap();
+        assert_eq!(tier3.base_fee_bps, 500);
+        assert_eq!(tier3.min_fee_bps, 300);
+        assert_eq!(tier3.rollover_fee_step_bps, 100);
+
+        cfg.tier_fees[2] = TierFeeSettings {
+            enabled: 1,
+            base_fee_bps: 200,
+            min_fee_bps: 100,
+            rollover_fee_step_bps: 50,
+        };
+        assert_eq!(cfg.fee_settings_for(3).unwrap().base_fee_bps, 200);
+        assert_eq!(cfg.fee_settings_for(1).unwrap().base_fee_bps, 500);
+        assert!(cfg.fee_settings_for(9).is_err());
+    }
+
+    #[test]
+    fn tier_cutoff_closes_betting_earlier() {
+        let mut cfg = default_config();
+        cfg.bet_cutoff_slots = 100;
+        cfg.tier_bet_cutoff_slots[1] = 5_000;
+
+        assert_eq!(cfg.bet_cutoff_for(1).unwrap(), 100);
+        assert_eq!(cfg.bet_cutoff_for(2).unwrap(), 5_000);
+
+        let schedule = EpochSchedule::without_warmup();
+        let slot = schedule.get_last_slot_in_epoch(10) - 1_000;
+        let open = |tier| is_betting_open_at(cfg.bet_cutoff_for(tier).unwrap(), 10, slot, &schedule, true);
+
+        assert!(open(1));
+        assert!(!open(2));
+    }
+
+    #[test]
+    fn winner_cap_falls_back_to_global_max() {
+        let mut cfg = default_config();
+        let global = ResolvedGame::MAX_WINNERS_PER_GAME as u32;
+        assert_eq!(cfg.max_winners_for(2).unwrap(), global);
+
+        cfg.tier_max_winners[1] = 1_000;
+        assert_eq!(cfg.max_winners_for(2).unwrap(), 1_000);
+
+        // A cap at the global max is the same as no cap
+        cfg.tier_max_winners[1] = global;
+        assert_eq!(cfg.max_winners_for(2).unwrap(), global);
+
+        assert_eq!(cfg.max_winners_for(1).unwrap(), global);
+        assert!(cfg.max_winners_for(0).is_err());
+    }
+
+    #[test]
+    fn tier_pause_is_isolated() {
+        let mut cfg = default_config();
+        cfg.tiers[1].paused = 1;
+
+        assert!(!cfg.is_betting_paused());
+        assert!(cfg.get_tier_settings(2).unwrap().is_paused());
+        assert!(!cfg.get_tier_settings(1).unwrap().is_paused());
+        assert!(!cfg.get_tier_settings(3).unwrap().is_paused());
+    }
+
+    #[test]
+    fn number_changes_can_be_disabled_per_tier() {
+        let mut cfg = default_config();
+        assert!((1..=5).all(|t| cfg.assert_changes_allowed(t).is_ok()));
+
+        cfg.tier_change_ticket_cost[1] = 2;
+        assert_eq!(cfg.change_ticket_cost_for(1).unwrap(), 1);
+        assert_eq!(cfg.change_ticket_cost_for(2).unwrap(), 2);
+
+        cfg.tier_changes_disabled[4] = 1;
+        assert_eq!(
+            cfg.assert_changes_allowed(5).unwrap_err(),
+            error!(IC42NErrorCode::ChangesDisabledForTier)
+        );
+        assert!(cfg.assert_changes_allowed(4).is_ok());
+        assert!(cfg.assert_changes_allowed(0).is_err());
+    }
+
+    #[test]
+    fn activating_without_curve_names_the_curve() {
+        let mut cfg = default_config();
+        cfg.tiers[1].max_bet_lamports = 1_000;
+
+        assert_eq!(
+            cfg.set_tier_active(2, 1).unwrap_err(),
+            error!(IC42NErrorCode::CannotActivateWithoutCurve)
+        );
+        assert!(!cfg.get_tier_settings(2).unwrap().is_active());
+
+        // No bet range is still reported as an unusable tier
+        assert_eq!(
+            cfg.set_tier_active(3, 1).unwrap_err(),
+            error!(IC42NErrorCode::InactiveTier)
+        );
+
+        // Curve and activation in one step
+        cfg.tiers[1].curve_factor = 1.5;
+        assert!(cfg.set_tier_active(2, 1).is_ok());
+    }
+
+    #[test]
+    fn activations_stop_at_max_active_tiers() {
+        let mut cfg = default_config();
+        for tier in cfg.tiers.iter_mut() {
+            tier.max_bet_lamports = 1_000;
+            tier.curve_factor = 1.0;
+        }
+        cfg.max_active_tiers = 3;
+
+        for tier_id in 1..=3 {
+            cfg.set_tier_active(tier_id, 1).unwrap();
+        }
+        assert_eq!(cfg.active_tier_count(), 3);
+
+        assert_eq!(
+            cfg.set_tier_active(4, 1).unwrap_err(),
+            error!(IC42NErrorCode::MaxActiveTiersExceeded)
+        );
+        assert!(!cfg.get_tier_settings(4).unwrap().is_active());
+
+        // Re-activating an active tier isn't a new activation
+        assert!(cfg.set_tier_active(2, 1).is_ok());
+
+        // Freeing a slot lets another tier in
+        cfg.set_tier_active(1, 0).unwrap();
+        assert!(cfg.set_tier_active(4, 1).is_ok());
+
+        // 0 = no cap
+        cfg.max_active_tiers = 0;
+        assert!(cfg.set_tier_active(5, 1).is_ok());
+        assert_eq!(cfg.active_tier_count(), 4);
+    }
+
+    #[test]
+    fn resolver_can_resolve_alongside_authority() {
+        let mut cfg = default_config();
+        let authority = Pubkey::new_unique();
+        let resolver = Pubkey::new_unique();
+        cfg.authority = authority;
+
+        assert!(cfg.can_resolve(&authority));
+        assert!(!cfg.can_resolve(&resolver));
+        assert!(!cfg.can_resolve(&Pubkey::default()));
+
+        cfg.resolver = resolver;
+        assert!(cfg.can_resolve(&authority));
+        assert!(cfg.can_resolve(&resolver));
+    }
+
+    #[test]
+    fn fee_vault_accept_requires_vault_or_delay() {
+        let mut cfg = default_config();
+        let authority = Pubkey::new_unique();
+        cfg.authority = authority;
+        let vault = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+
+        // Nothing pending
+        assert!(!cfg.can_accept_fee_vault(&authority, 1_000));
+
+        cfg.pending_fee_vault = vault;
+        cfg.pending_fee_vault_epoch = 500;
+
+        assert!(cfg.can_accept_fee_vault(&vault, 500));
+        assert!(!cfg.can_accept_fee_vault(&stranger, 10_000));
+
+        let unlock = 500 + FEE_VAULT_ACCEPT_DELAY_EPOCHS;
+        assert!(!cfg.can_accept_fee_vault(&authority, unlock - 1));
+        assert!(cfg.can_accept_fee_vault(&authority, unlock));
+    }
+
+    #[test]
+    fn rollovers_allowed_up_to_cap() {
+        let mut cfg = default_config();
+        cfg.max_rollovers = 3;
+
+        let mut carried: u8 = 0;
+        while cfg.is_rollover_allowed(carried) {
+            carried += 1;
+        }
+
+        assert_eq!(carried, 3);
+        assert!(!cfg.is_rollover_allowed(3));
+        assert!(!cfg.is_rollover_allowed(4));
+    }
+
+    #[test]
+    fn emergency_refunds_require_full_pause() {
+        let mut cfg = default_config();
+        cfg.emergency_refunds_open = 1;
+        assert!(!cfg.are_emergency_refunds_open());
+
+        cfg.pause_bet = 1;
+        assert!(!cfg.are_emergency_refunds_open());
+
+        cfg.apply_withdraw_pauses(Some(1), None, None);
+        assert!(cfg.are_emergency_refunds_open());
+
+        cfg.emergency_refunds_open = 0;
+        assert!(!cfg.are_emergency_refunds_open());
+    }
+
+    #[test]
+    fn claim_and_fee_pauses_are_independent() {
+        let mut cfg = default_config();
+
+        cfg.apply_withdraw_pauses(None, Some(1), None);
+        assert!(cfg.are_claims_paused());
+        assert!(!cfg.are_fee_withdrawals_paused());
+        assert_eq!(cfg.pause_withdraw, 0);
+
+        cfg.apply_withdraw_pauses(None, Some(0), Some(1));
+        assert!(!cfg.are_claims_paused());
+        assert!(cfg.are_fee_withdrawals_paused());
+        assert_eq!(cfg.pause_withdraw, 0);
+
+        cfg.apply_withdraw_pauses(None, None, Some(0));
+        assert!(!cfg.are_claims_paused());
+        assert!(!cfg.are_fee_withdrawals_paused());
+    }
+
+    #[test]
+    fn legacy_withdraw_pause_sets_both() {
+        let mut cfg = default_config();
+
+        cfg.apply_withdraw_pauses(Some(1), None, None);
+        assert!(cfg.are_claims_paused() && cfg.are_fee_withdrawals_paused());
+        assert_eq!(cfg.pause_withdraw, 1);
+
+        // Reopening one side clears the legacy flag
+        cfg.apply_withdraw_pauses(None, Some(0), None);
+        assert!(!cfg.are_claims_paused() && cfg.are_fee_withdrawals_paused());
+        assert_eq!(cfg.pause_withdraw, 0);
+
+        // A specific flag passed alongside the alias wins
+        cfg.apply_withdraw_pauses(Some(0), None, Some(1));
+        assert!(!cfg.are_claims_paused() && cfg.are_fee_withdrawals_paused());
+    }
+
+    #[test]
+    fn fee_waived_only_below_min_winners() {
+        let mut cfg = default_config();
+        assert!(!cfg.is_fee_waived(1));
+
+        cfg.min_winners_for_fee = 3;
+        assert!(cfg.is_fee_waived(1));
+        assert!(cfg.is_fee_waived(2));
+        assert!(!cfg.is_fee_waived(3));
+        assert!(!cfg.is_fee_waived(4));
+
+        // No winners is a rollover, not a waived fee
+        assert!(!cfg.is_fee_waived(0));
+    }
+
+    #[test]
+    fn fee_cap_returns_excess_to_the_pot() {
+        use crate::utils::math::bps_of;
+
+        let mut cfg = default_config();
+        let gross = 2_000_000_000_000; // 2_000 SOL at 5%
+        let fee = bps_of(gross, 500, cfg.fee_rounding_mode()).unwrap();
+        assert_eq!(cfg.cap_fee(fee), 100_000_000_000);
+
+        cfg.max_fee_lamports = 10_000_000_000;
+        let capped = cfg.cap_fee(fee);
+        assert_eq!(capped, 10_000_000_000);
+        assert_eq!(gross - capped, gross - fee + 90_000_000_000);
+
+        // Fees under the cap are untouched
+        assert_eq!(cfg.cap_fee(5_000), 5_000);
+    }
+
+    #[test]
+    fn zero_max_rollovers_is_unlimited() {
+        let cfg = default_config();
+        assert!(cfg.is_rollover_allowed(0));
+        assert!(cfg.is_rollover_allowed(u8::MAX));
+    }
+
+    #[test]
+    fn claim_deadline_follows_window() {
+        let mut cfg = default_config();
+        assert_eq!(cfg.claim_deadline_for(700), 0);
+
+        cfg.claim_window_epochs = 30;
+        assert_eq!(cfg.claim_deadline_for(700), 730);
+        assert_eq!(cfg.claim_deadline_for(u64::MAX), u64::MAX);
+    }
+}
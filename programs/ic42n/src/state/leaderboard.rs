@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use crate::utils::prediction::bet_ordering_key;
+
+/// One ranked wagerer on a `Leaderboard`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+
+    /// Player's total stake on the tier's game (`prediction.lamports`).
+    pub lamports: u64,
+
+    /// Slot the prediction was first placed (`prediction.placed_slot`); breaks ties.
+    pub placed_slot: u64,
+}
+
+impl LeaderboardEntry {
+    pub const SIZE: usize = 32 + 8 + 8;
+
+    pub fn ordering_key(&self) -> u128 {
+        bet_ordering_key(self.lamports, self.placed_slot)
+    }
+}
+
+/// ---------------------------------------------------------------------------
+/// Leaderboard
+/// ---------------------------------------------------------------------------
+///
+/// Top wagerers for one (epoch, tier), kept sorted by `lamports` descending,
+/// with equal stakes ordered by earliest `placed_slot`.
+///
+/// Only maintained for tiers with `leaderboard_enabled`, and only when the
+/// leaderboard account is passed to `place_prediction` / `increase_prediction`.
+/// It is a UI snapshot, never used for payouts.
+#[account]
+pub struct Leaderboard {
+    pub epoch: u64,
+    pub tier: u8,
+    pub bump: u8,
+
+    /// Number of valid entries (0..=MAX_ENTRIES).
+    pub len: u8,
+
+    pub entries: [LeaderboardEntry; Leaderboard::MAX_ENTRIES],
+
+    pub _reserved: [u8; 16],
+}
+
+impl Leaderboard {
+    pub const SEED_PREFIX: &'static [u8] = b"leaderboard";
+
+    /// Hard cap to keep the insertion sort cheap.
+    pub const MAX_ENTRIES: usize = 20;
+
+    pub const SIZE: usize =
+        8  // epoch
+            + 1  // tier
+            + 1  // bump
+            + 1  // len
+            + (LeaderboardEntry::SIZE * Self::MAX_ENTRIES) // entries
+            + 16; // reserved
+
+    /// Records `player` with a stake of `lamports` placed at `placed_slot`.
+    ///
+    /// - Existing entries only move up (a lower value is ignored).
+    /// - New players are appended while there is room, otherwise they must
+    ///   outrank the last entry, which is evicted.
+    ///
+    /// Returns true if the board changed.
+    pub fn record(&mut self, player: Pubkey, lamports: u64, placed_slot: u64) -> bool {
+        let entry = LeaderboardEntry { player, lamports, placed_slot };
+        let len = self.len as usize;
+
+        if let Some(pos) = self.entries[..len].iter().position(|e| e.player == player) {
+            if lamports <= self.entries[pos].lamports {
+                return false;
+            }
+            self.entries[pos].lamports = lamports;
+            self.bubble_up(pos);
+            return true;
+        }
+
+        let pos = if len < Self::MAX_ENTRIES {
+            self.len += 1;
+            len
+        } else {
+            let last = Self::MAX_ENTRIES - 1;
+            if entry.ordering_key() <= self.entries[last].ordering_key() {
+                return false;
+            }
+            last
+        };
+
+        self.entries[pos] = entry;
+        self.bubble_up(pos);
+        true
+    }
+
+    /// Insertion step: move entry `i` up while it outranks its predecessor.
+    fn bubble_up(&mut self, mut i: usize) {
+        while i > 0 && self.entries[i].ordering_key() > self.entries[i - 1].ordering_key() {
+            self.entries.swap(i, i - 1);
+            i -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    fn empty_board() -> Leaderboard {
+        Leaderboard {
+            epoch: 0,
+            tier: 0,
+            bump: 0,
+            len: 0,
+            entries: [LeaderboardEntry::default(); Leaderboard::MAX_ENTRIES],
+            _reserved: [0u8; 16],
+        }
+    }
+
+    fn ranked(board: &Leaderboard) -> Vec<u64> {
+        board.entries[..board.len as usize].iter().map(|e| e.lamports).collect()
+    }
+
+    #[test]
+    fn test_leaderboard_size() {
+        let bytes = empty_board().try_to_vec().unwrap();
+        assert_eq!(bytes.len(), Leaderboard::SIZE);
+    }
+
+    #[test]
+    fn keeps_entries_sorted_descending() {
+        let mut board = empty_board();
+        for lamports in [30, 10, 50, 20, 40] {
+            assert!(board.record(Pubkey::new_unique(), lamports, 0));
+        }
+        assert_eq!(ranked(&board), vec![50, 40, 30, 20, 10]);
+    }
+
+    #[test]
+    fn existing_player_moves_up_only() {
+        let mut board = empty_board();
+        let whale = Pubkey::new_unique();
+        board.record(Pubkey::new_unique(), 100, 0);
+        board.record(whale, 50, 0);
+
+        assert!(!board.record(whale, 40, 0));
+        assert!(board.record(whale, 150, 0));
+
+        assert_eq!(board.len, 2);
+        assert_eq!(board.entries[0].player, whale);
+        assert_eq!(ranked(&board), vec![150, 100]);
+    }
+
+    #[test]
+    fn full_board_evicts_smallest() {
+        let mut board = empty_board();
+        for lamports in 1..=Leaderboard::MAX_ENTRIES as u64 {
+            board.record(Pubkey::new_unique(), lamports * 10, 0);
+        }
+        assert_eq!(board.len as usize, Leaderboard::MAX_ENTRIES);
+
+        // Later ties and smaller stakes don't make the cut
+        assert!(!board.record(Pubkey::new_unique(), 10, 1));
+        assert!(!board.record(Pubkey::new_unique(), 5, 0));
+
+        let newcomer = Pubkey::new_unique();
+        assert!(board.record(newcomer, 15, 0));
+
+        assert_eq!(board.len as usize, Leaderboard::MAX_ENTRIES);
+        assert_eq!(board.entries[Leaderboard::MAX_ENTRIES - 2].lamports, 20);
+        assert_eq!(board.entries[Leaderboard::MAX_ENTRIES - 1].player, newcomer);
+    }
+
+    #[test]
+    fn equal_stakes_rank_earliest_first() {
+        let mut board = empty_board();
+        let late = Pubkey::new_unique();
+        let early = Pubkey::new_unique();
+
+        board.record(late, 100, 900);
+        board.record(early, 100, 300);
+
+        assert_eq!(board.entries[0].player, early);
+        assert_eq!(board.entries[1].player, late);
+    }
+}
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
 
 #[account]
 pub struct LiveFeed {
@@ -15,10 +16,10 @@ pub struct LiveFeed {
     pub carried_over_lamports: u64,
 
     /// Total bet count across the current epoch-chain.
-    pub total_bets: u32,
+    pub total_bets: u64,
 
     /// Bet count carried into the current epoch (accounting only).
-    pub carried_over_bets: u32,
+    pub carried_over_bets: u64,
 
     /// Slots-before-epoch-end cutoff enforced for betting.
     pub bet_cutoff_slots: u64,
@@ -39,7 +40,7 @@ pub struct LiveFeed {
     pub lamports_per_number: [u64; 10],
 
     /// Bet count per number index (0..=9).
-    pub bets_per_number: [u32; 10],
+    pub bets_per_number: [u64; 10],
 
     /// Secondary rollover number for this tier’s current game (0 disables).
     pub secondary_rollover_number: u8,
@@ -47,8 +48,36 @@ pub struct LiveFeed {
     /// Current fee rate for this tier’s current game.
     pub current_fee_bps: u16,
 
+    /// Epoch the feed last advanced out of (resolved, rolled over, or reset
+    /// while empty); 0 until the first one.
+    pub last_resolved_epoch: u64,
+
+    /// House liquidity added via `seed_treasury`; part of `total_lamports`
+    /// but never of `total_bets` or the per-number stakes.
+    pub house_seed_lamports: u64,
+
+    /// Epoch whose bets still await a ResolvedGame (0 = none). Set when a bet
+    /// lands, moved along by rollovers, cleared when a payout starts a new
+    /// chain or the last bet is refunded. Blocks `reset_live_feed` past it.
+    pub pending_resolution_epoch: u64,
+
+    /// Lamports ever wagered on this tier (placements and increases); never
+    /// reset by epoch or chain changes.
+    pub lifetime_lamports_wagered: u128,
+
+    /// Predictions ever placed on this tier; never reset.
+    pub lifetime_bets: u64,
+
+    /// Last `entry_nonce` handed to a prediction on this tier. Never reset,
+    /// so entries of a chain are strictly ordered by it.
+    pub prediction_nonce: u64,
+
+    /// 1 = the current chain was cancelled by `deactivate_and_refund_tier`;
+    /// its predictions can only be refunded. Cleared when a new chain starts.
+    pub chain_cancelled: u8,
+
     /// Reserved for future fields.
-    pub _reserved: [u8; 61],
+    pub _reserved: [u8; 4],
 }
 
 impl LiveFeed {
@@ -60,18 +89,67 @@ impl LiveFeed {
             + 8  // first_epoch_in_chain
             + 8  // total_lamports
             + 8  // carried_over_lamports
-            + 4  // total_bets
-            + 4  // carried_over_bets
+            + 8  // total_bets
+            + 8  // carried_over_bets
             + 8  // bet_cutoff_slots
             + 1  // tier
             + 32 // treasury
             + 1  // epochs_carried_over
             + 1  // bump
             + (8 * 10)  // lamports_per_number
-            + (4 * 10)  // bets_per_number
+            + (8 * 10)  // bets_per_number
             + 1  // secondary_rollover_number
             + 2  // current_fee_bps
-            + 61; // reserved
+            + 8  // last_resolved_epoch
+            + 8  // house_seed_lamports
+            + 8  // pending_resolution_epoch
+            + 16 // lifetime_lamports_wagered
+            + 8  // lifetime_bets
+            + 8  // prediction_nonce
+            + 1  // chain_cancelled
+            + 4; // reserved
+
+    /// Size of feeds created while bet counters were u32; grown in place by
+    /// `migrate_live_feed`.
+    pub const LEGACY_SIZE: usize = Self::SIZE - 4 - 4 - (4 * 10);
+
+    /// Rewrites a `LEGACY_SIZE` feed (after the discriminator) into the
+    /// current layout. `data` must already hold `SIZE` bytes.
+    pub fn upgrade_legacy_counters(data: &mut [u8]) -> Result<()> {
+        let legacy = LegacyLiveFeed::deserialize(&mut &data[..Self::LEGACY_SIZE])
+            .map_err(|_| error!(IC42NErrorCode::InvalidInput))?;
+
+        let feed = LiveFeed {
+            epoch: legacy.epoch,
+            first_epoch_in_chain: legacy.first_epoch_in_chain,
+            total_lamports: legacy.total_lamports,
+            carried_over_lamports: legacy.carried_over_lamports,
+            total_bets: legacy.total_bets as u64,
+            carried_over_bets: legacy.carried_over_bets as u64,
+            bet_cutoff_slots: legacy.bet_cutoff_slots,
+            tier: legacy.tier,
+            treasury: legacy.treasury,
+            epochs_carried_over: legacy.epochs_carried_over,
+            bump: legacy.bump,
+            lamports_per_number: legacy.lamports_per_number,
+            bets_per_number: legacy.bets_per_number.map(|b| b as u64),
+            secondary_rollover_number: legacy.secondary_rollover_number,
+            current_fee_bps: legacy.current_fee_bps,
+            last_resolved_epoch: legacy.last_resolved_epoch,
+            house_seed_lamports: legacy.house_seed_lamports,
+            pending_resolution_epoch: legacy.pending_resolution_epoch,
+            lifetime_lamports_wagered: legacy.lifetime_lamports_wagered,
+            lifetime_bets: legacy.lifetime_bets,
+            prediction_nonce: 0,
+            chain_cancelled: 0,
+            _reserved: [0u8; 4],
+        };
+
+        let mut out = &mut data[..Self::SIZE];
+        feed.serialize(&mut out)
+            .map_err(|_| error!(IC42NErrorCode::InvalidInput))?;
+        Ok(())
+    }
 
     pub fn init_new(
         &mut self,
@@ -101,71 +179,803 @@ impl LiveFeed {
         self.current_fee_bps = fee_bps;
 
         self.clear_per_number_state();
-        self._reserved = [0u8; 61];
+        self.last_resolved_epoch = 0;
+        self.house_seed_lamports = 0;
+        self.pending_resolution_epoch = 0;
+        self.lifetime_lamports_wagered = 0;
+        self.lifetime_bets = 0;
+        self.prediction_nonce = 0;
+        self.chain_cancelled = 0;
+        self._reserved = [0u8; 4];
     }
 
     /// Advances the feed into `new_epoch`. If carry values are non-zero, the
     /// current epoch-chain continues; otherwise a new chain begins at `new_epoch`.
+    ///
+    /// Carried values are checked with `check_carried_stakes` before they are
+    /// written, so an inconsistent carry leaves the feed untouched.
     pub fn reset_for_new_epoch(
         &mut self,
         new_epoch: u64,
         cutoff_slots: u64,
         carry_over_lamports: u64,
-        carry_over_bets: u32,
+        carry_over_bets: u64,
         lamports_per_number: [u64; 10],
-        bets_per_number: [u32; 10],
+        bets_per_number: [u64; 10],
         next_secondary_rollover: u8,
         next_fee_bps: u16,
-    ) {
-        self.epoch = new_epoch;
-        self.bet_cutoff_slots = cutoff_slots;
-        self.current_fee_bps = next_fee_bps;
-
+    ) -> Result<()> {
         let is_carry = carry_over_lamports > 0 || carry_over_bets > 0;
 
         if is_carry {
-            self.total_lamports = carry_over_lamports;
-            self.carried_over_lamports = carry_over_lamports;
+            Self::check_carried_stakes(
+                carry_over_lamports,
+                carry_over_bets,
+                &lamports_per_number,
+                &bets_per_number,
+            )?;
 
+            self.total_lamports = carry_over_lamports;
             self.total_bets = carry_over_bets;
-            self.carried_over_bets = carry_over_bets;
 
             self.lamports_per_number = lamports_per_number;
             self.bets_per_number = bets_per_number;
 
-            self.epochs_carried_over = self.epochs_carried_over.saturating_add(1);
-            if self.epochs_carried_over == 0 {
-                self.epochs_carried_over = 1;
-            }
+            self.carry_forward(new_epoch, cutoff_slots, next_fee_bps);
         } else {
-            self.first_epoch_in_chain = new_epoch;
-            self.epochs_carried_over = 0;
+            self.start_new_chain(new_epoch, cutoff_slots, next_secondary_rollover, next_fee_bps);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a carry is consistent under the coverage model, where one
+    /// bet adds its per-number stake to each of the 1..=8 numbers it covers:
+    ///
+    /// - `sum(lamports_per_number[1..=9]) == carry_over_lamports` (the house
+    ///   seed is taken off the feed before carrying, so it isn't in either side)
+    /// - every number's bet count is at most `carry_over_bets`, and
+    ///   `carry_over_bets <= sum(bets_per_number[1..=9]) <= 8 * carry_over_bets`
+    /// - index 0 is empty, and a number holds lamports iff it holds bets
+    pub fn check_carried_stakes(
+        carry_over_lamports: u64,
+        carry_over_bets: u64,
+        lamports_per_number: &[u64; 10],
+        bets_per_number: &[u64; 10],
+    ) -> Result<()> {
+        let mut staked: u64 = 0;
+        let mut covered: u64 = 0;
+        for n in 1..=9 {
+            staked = staked
+                .checked_add(lamports_per_number[n])
+                .ok_or(IC42NErrorCode::LiveFeedAccountingMismatch)?;
+            covered = covered
+                .checked_add(bets_per_number[n])
+                .ok_or(IC42NErrorCode::LiveFeedAccountingMismatch)?;
+            require!(
+                bets_per_number[n] <= carry_over_bets,
+                IC42NErrorCode::LiveFeedAccountingMismatch
+            );
+        }
+        require_eq!(staked, carry_over_lamports, IC42NErrorCode::LiveFeedAccountingMismatch);
+
+        let max_covered = carry_over_bets
+            .checked_mul(8)
+            .ok_or(IC42NErrorCode::LiveFeedAccountingMismatch)?;
+        require!(
+            covered >= carry_over_bets && covered <= max_covered,
+            IC42NErrorCode::LiveFeedAccountingMismatch
+        );
+
+        require!(
+            lamports_per_number[0] == 0 && bets_per_number[0] == 0,
+            IC42NErrorCode::LiveFeedAccountingMismatch
+        );
+        require!(
+            lamports_per_number
+                .iter()
+                .zip(bets_per_number.iter())
+                .all(|(lamports, bets)| (*lamports == 0) == (*bets == 0)),
+            IC42NErrorCode::LiveFeedAccountingMismatch
+        );
+
+        Ok(())
+    }
+
+    /// Continues the current chain into `new_epoch`, carrying the whole pot
+    /// and per-number state over as they are.
+    pub fn carry_forward(&mut self, new_epoch: u64, cutoff_slots: u64, next_fee_bps: u16) {
+        self.last_resolved_epoch = self.epoch;
+        self.epoch = new_epoch;
+        self.bet_cutoff_slots = cutoff_slots;
+        self.current_fee_bps = next_fee_bps;
+
+        self.carried_over_lamports = self.total_lamports;
+        self.carried_over_bets = self.total_bets;
+
+        // The carried bets now resolve with the new epoch
+        self.pending_resolution_epoch = if self.total_bets > 0 { new_epoch } else { 0 };
+
+        self.epochs_carried_over = self.epochs_carried_over.saturating_add(1);
+        if self.epochs_carried_over == 0 {
+            self.epochs_carried_over = 1;
+        }
+    }
+
+    /// Starts a new, empty chain at `new_epoch`.
+    pub fn start_new_chain(
+        &mut self,
+        new_epoch: u64,
+        cutoff_slots: u64,
+        next_secondary_rollover: u8,
+        next_fee_bps: u16,
+    ) {
+        self.last_resolved_epoch = self.epoch;
+        self.epoch = new_epoch;
+        self.bet_cutoff_slots = cutoff_slots;
+        self.current_fee_bps = next_fee_bps;
 
-            self.total_lamports = 0;
-            self.carried_over_lamports = 0;
+        self.first_epoch_in_chain = new_epoch;
+        self.epochs_carried_over = 0;
+        self.chain_cancelled = 0;
+
+        self.total_lamports = 0;
+        self.carried_over_lamports = 0;
+
+        self.total_bets = 0;
+        self.carried_over_bets = 0;
+
+        // A paid-out pot included the seed
+        self.house_seed_lamports = 0;
+        self.pending_resolution_epoch = 0;
+
+        self.secondary_rollover_number = next_secondary_rollover;
+        self.clear_per_number_state();
+    }
 
-            self.total_bets = 0;
-            self.carried_over_bets = 0;
+    /// Moves an empty feed to `new_epoch` without starting a new chain:
+    /// `first_epoch_in_chain` and `epochs_carried_over` are kept for lineage.
+    pub fn resync_epoch(
+        &mut self,
+        new_epoch: u64,
+        cutoff_slots: u64,
+        next_secondary_rollover: u8,
+        next_fee_bps: u16,
+    ) {
+        self.epoch = new_epoch;
+        self.bet_cutoff_slots = cutoff_slots;
+        self.current_fee_bps = next_fee_bps;
+        self.secondary_rollover_number = next_secondary_rollover;
+    }
 
-            self.secondary_rollover_number = next_secondary_rollover;
-            self.clear_per_number_state();
+    /// Corrects `secondary_rollover_number` in place. Only allowed while the
+    /// feed has no bets and no carry, so no live prediction covers the old
+    /// or new blocked number.
+    pub fn set_secondary_rollover(&mut self, number: u8) -> Result<()> {
+        require!(number > 0 && number < 10, IC42NErrorCode::InvalidRollOverNumber);
+        require!(
+            self.total_bets == 0
+                && self.carried_over_bets == 0
+                && self.carried_over_lamports == 0,
+            IC42NErrorCode::LiveFeedNotEmpty
+        );
+        self.secondary_rollover_number = number;
+        Ok(())
+    }
+
+    pub fn is_chain_cancelled(&self) -> bool {
+        self.chain_cancelled != 0
+    }
+
+    /// True if a prediction of the chain starting at `game_epoch` may take
+    /// the cancelled-chain refund.
+    pub fn is_refundable_chain(&self, game_epoch: u64) -> bool {
+        self.is_chain_cancelled() && game_epoch == self.first_epoch_in_chain
+    }
+
+    /// Advances and returns the entry nonce for a newly placed prediction.
+    pub fn next_entry_nonce(&mut self) -> Result<u64> {
+        self.prediction_nonce = self
+            .prediction_nonce
+            .checked_add(1)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        Ok(self.prediction_nonce)
+    }
+
+    /// Adds a wager to the lifetime counters; `new_bet` is false for
+    /// increases to an existing prediction.
+    pub fn record_lifetime_wager(&mut self, lamports: u64, new_bet: bool) {
+        self.lifetime_lamports_wagered = self
+            .lifetime_lamports_wagered
+            .saturating_add(lamports as u128);
+        if new_bet {
+            self.lifetime_bets = self.lifetime_bets.saturating_add(1);
+        }
+    }
+
+    /// Adds a newly placed prediction to the feed: one bet, `total_lamports`
+    /// on the pot and the full `lamports_per_number` on each selected number
+    /// (no split). Returns the pot as it stood before the bet.
+    pub fn record_placement(
+        &mut self,
+        total_lamports: u64,
+        lamports_per_number: u64,
+        selections: &[u8; 8],
+        selection_count: u8,
+    ) -> Result<u64> {
+        let pot_before = self.total_lamports;
+
+        self.total_bets = self
+            .total_bets
+            .checked_add(1)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        self.pending_resolution_epoch = self.epoch;
+        self.record_lifetime_wager(total_lamports, true);
+
+        self.total_lamports = pot_before
+            .checked_add(total_lamports)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+
+        for &number in selections.iter().take(selection_count as usize) {
+            let n = number as usize;
+            require!(
+                n < self.bets_per_number.len() && n < self.lamports_per_number.len(),
+                IC42NErrorCode::InvalidBetNumber
+            );
+
+            self.bets_per_number[n] = self.bets_per_number[n]
+                .checked_add(1)
+                .ok_or(IC42NErrorCode::MathOverflow)?;
+            self.lamports_per_number[n] = self.lamports_per_number[n]
+                .checked_add(lamports_per_number)
+                .ok_or(IC42NErrorCode::MathOverflow)?;
         }
+
+        Ok(pot_before)
+    }
+
+    /// Adds house liquidity to the pot without counting it as a wager.
+    pub fn add_house_seed(&mut self, lamports: u64) -> Result<()> {
+        self.total_lamports = self
+            .total_lamports
+            .checked_add(lamports)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        self.house_seed_lamports = self
+            .house_seed_lamports
+            .checked_add(lamports)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Pulls the house seed out of the pot when a game resolves without
+    /// winners, so only player lamports carry forward. Returns the amount
+    /// to recover to the fee vault.
+    pub fn take_house_seed(&mut self) -> u64 {
+        let seed = self.house_seed_lamports;
+        self.total_lamports = self.total_lamports.saturating_sub(seed);
+        self.house_seed_lamports = 0;
+        seed
+    }
+
+    /// Checks the pot against the per-number state before it is resolved.
+    ///
+    /// Every wager adds its per-number stake to each covered number (1..=9),
+    /// so those stakes plus the house seed must add up to `total_lamports`,
+    /// number 0 never holds a stake, and a number has lamports iff it has bets.
+    pub fn reconcile(&self) -> Result<()> {
+        let mut staked: u64 = self.house_seed_lamports;
+        for n in 1..=9 {
+            staked = staked
+                .checked_add(self.lamports_per_number[n])
+                .ok_or(IC42NErrorCode::LiveFeedAccountingMismatch)?;
+        }
+        require_eq!(staked, self.total_lamports, IC42NErrorCode::LiveFeedAccountingMismatch);
+
+        require!(
+            self.lamports_per_number[0] == 0 && self.bets_per_number[0] == 0,
+            IC42NErrorCode::LiveFeedAccountingMismatch
+        );
+        require!(
+            self.lamports_per_number
+                .iter()
+                .zip(self.bets_per_number.iter())
+                .all(|(lamports, bets)| (*lamports == 0) == (*bets == 0)),
+            IC42NErrorCode::LiveFeedAccountingMismatch
+        );
+
+        Ok(())
+    }
+
+    /// True if moving the feed to `target_epoch` would skip an epoch whose
+    /// bets were never resolved, stranding its pot.
+    pub fn blocks_reset(&self, target_epoch: u64) -> bool {
+        self.pending_resolution_epoch != 0 && self.pending_resolution_epoch < target_epoch
+    }
+
+    /// True if the tracked epoch has completed and is still waiting to be
+    /// resolved or rolled over.
+    pub fn is_behind(&self, current_epoch: u64) -> bool {
+        self.epoch < current_epoch
     }
 
     fn clear_per_number_state(&mut self) {
         self.lamports_per_number = [0u64; 10];
-        self.bets_per_number = [0u32; 10];
+        self.bets_per_number = [0u64; 10];
     }
 }
 
+/// `LiveFeed` as laid out before its bet counters were widened to u64.
+#[derive(AnchorDeserialize)]
+#[cfg_attr(test, derive(AnchorSerialize))]
+struct LegacyLiveFeed {
+    epoch: u64,
+    first_epoch_in_chain: u64,
+    total_lamports: u64,
+    carried_over_lamports: u64,
+    total_bets: u32,
+    carried_over_bets: u32,
+    bet_cutoff_slots: u64,
+    tier: u8,
+    treasury: Pubkey,
+    epochs_carried_over: u8,
+    bump: u8,
+    lamports_per_number: [u64; 10],
+    bets_per_number: [u32; 10],
+    secondary_rollover_number: u8,
+    current_fee_bps: u16,
+    last_resolved_epoch: u64,
+    house_seed_lamports: u64,
+    pending_resolution_epoch: u64,
+    lifetime_lamports_wagered: u128,
+    lifetime_bets: u64,
+    _reserved: [u8; 13],
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use borsh::BorshSerialize;
+    use crate::state::Prediction;
+    use crate::utils::prediction::retract_prediction_from_live;
+
+    fn feed_with_bets() -> LiveFeed {
+        let mut lf = empty_feed();
+        lf.epoch = 700;
+        lf.first_epoch_in_chain = 699;
+        lf.total_lamports = 3_000;
+        lf.total_bets = 3;
+        lf.epochs_carried_over = 1;
+        lf.secondary_rollover_number = 4;
+        lf.lamports_per_number[2] = 1_000;
+        lf.lamports_per_number[7] = 2_000;
+        lf.bets_per_number[2] = 1;
+        lf.bets_per_number[7] = 2;
+        lf
+    }
+
+    fn snapshot(lf: &LiveFeed) -> Vec<u8> {
+        lf.try_to_vec().unwrap()
+    }
+
+    #[test]
+    fn carry_forward_matches_reset_with_full_carry() {
+        let mut in_place = feed_with_bets();
+        let mut copied = feed_with_bets();
+
+        in_place.carry_forward(701, 300, 900);
+        copied.reset_for_new_epoch(
+            701,
+            300,
+            copied.total_lamports,
+            copied.total_bets,
+            copied.lamports_per_number,
+            copied.bets_per_number,
+            9,
+            900,
+        ).unwrap();
+
+        assert_eq!(snapshot(&in_place), snapshot(&copied));
+        assert_eq!(in_place.carried_over_lamports, 3_000);
+        assert_eq!(in_place.epochs_carried_over, 2);
+        assert_eq!(in_place.first_epoch_in_chain, 699);
+    }
+
+    #[test]
+    fn start_new_chain_matches_reset_without_carry() {
+        let mut direct = feed_with_bets();
+        let mut reset = feed_with_bets();
+
+        direct.start_new_chain(701, 300, 7, 1_000);
+        reset.reset_for_new_epoch(701, 300, 0, 0, [0u64; 10], [0u64; 10], 7, 1_000).unwrap();
+
+        assert_eq!(snapshot(&direct), snapshot(&reset));
+        assert_eq!(direct.first_epoch_in_chain, 701);
+        assert_eq!(direct.total_lamports, 0);
+    }
+
+    #[test]
+    fn rollover_keeps_blocked_number_until_new_chain() {
+        let mut feed = feed_with_bets();
+        assert_eq!(feed.secondary_rollover_number, 4);
+
+        // Carry: the proposed next block is ignored while the chain lives on
+        feed.reset_for_new_epoch(
+            701,
+            300,
+            feed.total_lamports,
+            feed.total_bets,
+            feed.lamports_per_number,
+            feed.bets_per_number,
+            7,
+            900,
+        ).unwrap();
+        assert_eq!(feed.secondary_rollover_number, 4);
+        assert_eq!(feed.first_epoch_in_chain, 699);
+
+        // Payout on 7 starts a new chain that blocks 7
+        feed.start_new_chain(702, 300, 7, 1_000);
+        assert_eq!(feed.secondary_rollover_number, 7);
+        assert_eq!(feed.first_epoch_in_chain, 702);
+    }
+
+    #[test]
+    fn reset_preserve_chain_keeps_lineage() {
+        let mut feed = empty_feed();
+        feed.epoch = 700;
+        feed.first_epoch_in_chain = 695;
+        feed.epochs_carried_over = 5;
+
+        // preserve_chain = true
+        let mut preserved = feed.clone();
+        preserved.resync_epoch(710, 300, 6, 1_000);
+        assert_eq!(preserved.epoch, 710);
+        assert_eq!(preserved.first_epoch_in_chain, 695);
+        assert_eq!(preserved.epochs_carried_over, 5);
+        assert_eq!(preserved.secondary_rollover_number, 6);
+
+        // preserve_chain = false
+        let mut fresh = feed.clone();
+        fresh.start_new_chain(710, 300, 6, 1_000);
+        assert_eq!(fresh.epoch, 710);
+        assert_eq!(fresh.first_epoch_in_chain, 710);
+        assert_eq!(fresh.epochs_carried_over, 0);
+    }
+
+    #[test]
+    fn unresolved_epoch_blocks_reset() {
+        let mut feed = feed_with_bets();
+        feed.pending_resolution_epoch = 700;
+
+        // Staying in the bet epoch is fine; skipping past it is not
+        assert!(!feed.blocks_reset(700));
+        assert!(feed.blocks_reset(701));
+
+        // A rollover moves the pending epoch along with the carried bets
+        feed.carry_forward(701, 300, 900);
+        assert_eq!(feed.pending_resolution_epoch, 701);
+        assert!(feed.blocks_reset(702));
+
+        // A payout resolves the chain
+        feed.start_new_chain(702, 300, 7, 1_000);
+        assert_eq!(feed.pending_resolution_epoch, 0);
+        assert!(!feed.blocks_reset(710));
+    }
+
+    #[test]
+    fn last_resolved_epoch_tracks_resolution_and_rollover() {
+        let mut feed = feed_with_bets();
+        assert!(feed.is_behind(701));
+        assert!(!feed.is_behind(700));
+
+        // Rollover of epoch 700
+        feed.carry_forward(701, 300, 900);
+        assert_eq!(feed.last_resolved_epoch, 700);
+        assert!(!feed.is_behind(701));
+
+        // Paid-out resolution of epoch 701
+        feed.start_new_chain(702, 300, 5, 1_000);
+        assert_eq!(feed.last_resolved_epoch, 701);
+        assert_eq!(feed.epoch, 702);
+
+        // Epochs 702 and 703 completed without being resolved yet
+        assert!(feed.is_behind(704));
+    }
+
+    #[test]
+    fn no_winner_carry_leaves_house_seed_behind() {
+        let mut feed = feed_with_bets();
+        feed.add_house_seed(500).unwrap();
+        assert_eq!(feed.total_lamports, 3_500);
+        assert_eq!(feed.total_bets, 3);
+
+        let recovered = feed.take_house_seed();
+        feed.carry_forward(701, 300, 900);
+
+        assert_eq!(recovered, 500);
+        assert_eq!(feed.house_seed_lamports, 0);
+        assert_eq!(feed.carried_over_lamports, 3_000);
+        assert_eq!(feed.total_lamports, 3_000);
+        assert_eq!(feed.lamports_per_number.iter().sum::<u64>(), 3_000);
+    }
+
+    #[test]
+    fn paid_out_pot_consumes_house_seed() {
+        let mut feed = feed_with_bets();
+        feed.add_house_seed(500).unwrap();
+
+        feed.start_new_chain(701, 300, 7, 1_000);
+        assert_eq!(feed.house_seed_lamports, 0);
+        assert_eq!(feed.total_lamports, 0);
+    }
+
+    #[test]
+    fn reconcile_accepts_consistent_feed() {
+        let mut feed = feed_with_bets();
+        assert!(feed.reconcile().is_ok());
+
+        feed.add_house_seed(500).unwrap();
+        assert!(feed.reconcile().is_ok());
+        assert!(empty_feed().reconcile().is_ok());
+    }
+
+    #[test]
+    fn reconcile_rejects_corrupted_per_number_state() {
+        let mismatch: Error = IC42NErrorCode::LiveFeedAccountingMismatch.into();
+
+        // Stake retracted from one number but never applied to another
+        let mut drifted = feed_with_bets();
+        drifted.lamports_per_number[7] -= 1_000;
+        assert_eq!(drifted.reconcile().unwrap_err(), mismatch);
+
+        // Stake on the reserved 0 slot
+        let mut zero_slot = feed_with_bets();
+        zero_slot.lamports_per_number[2] = 0;
+        zero_slot.lamports_per_number[0] = 1_000;
+        assert_eq!(zero_slot.reconcile().unwrap_err(), mismatch);
+
+        // Lamports left behind on a number with no bets
+        let mut orphaned = feed_with_bets();
+        orphaned.bets_per_number[2] = 0;
+        assert_eq!(orphaned.reconcile().unwrap_err(), mismatch);
+
+        // Overflowing stakes
+        let mut overflow = feed_with_bets();
+        overflow.lamports_per_number[3] = u64::MAX;
+        overflow.bets_per_number[3] = 1;
+        assert_eq!(overflow.reconcile().unwrap_err(), mismatch);
+    }
+
+    #[test]
+    fn carried_stakes_must_match_carried_totals() {
+        let mismatch = error!(IC42NErrorCode::LiveFeedAccountingMismatch);
+
+        // Three bets: 600 on {1}, 900 split 300 each over {1, 2, 3}, 400 on {3}
+        let mut lamports = [0u64; 10];
+        let mut bets = [0u64; 10];
+        lamports[1] = 900;
+        lamports[2] = 300;
+        lamports[3] = 700;
+        bets[1] = 2;
+        bets[2] = 1;
+        bets[3] = 2;
+
+        let mut lf = feed_with_bets();
+        lf.reset_for_new_epoch(701, 300, 1_900, 3, lamports, bets, 0, 1_000).unwrap();
+        assert_eq!(lf.total_lamports, 1_900);
+        assert_eq!(lf.lamports_per_number, lamports);
+        assert!(lf.reconcile().is_ok());
+
+        let check = |total: u64, count: u64, l: [u64; 10], b: [u64; 10]| {
+            LiveFeed::check_carried_stakes(total, count, &l, &b)
+        };
+
+        // Per-number sum off by one lamport
+        assert_eq!(check(1_901, 3, lamports, bets).unwrap_err(), mismatch);
+        // More bets carried than were ever placed on the numbers
+        assert_eq!(check(1_900, 6, lamports, bets).unwrap_err(), mismatch);
+        // A single number with more bets than the carry holds
+        assert_eq!(check(1_900, 1, lamports, bets).unwrap_err(), mismatch);
+
+        // Lamports on a number without bets
+        let mut orphan = bets;
+        orphan[2] = 0;
+        assert_eq!(check(1_900, 3, lamports, orphan).unwrap_err(), mismatch);
+
+        // Stake parked on index 0
+        let mut slot0 = lamports;
+        slot0[0] = 1;
+        assert_eq!(check(1_900, 3, slot0, bets).unwrap_err(), mismatch);
+
+        // A rejected carry leaves the feed untouched
+        let mut kept = feed_with_bets();
+        let before = snapshot(&kept);
+        assert!(kept.reset_for_new_epoch(701, 300, 1_901, 3, lamports, bets, 0, 1_000).is_err());
+        assert_eq!(snapshot(&kept), before);
+    }
+
+    #[test]
+    fn lifetime_counters_survive_resets() {
+        let mut lf = feed_with_bets();
+        lf.record_lifetime_wager(2_000, true);
+        lf.record_lifetime_wager(1_000, false);
+
+        // Carry, then a fresh chain
+        lf.reset_for_new_epoch(701, 300, 3_000, 3, lf.lamports_per_number, lf.bets_per_number, 0, 1_000).unwrap();
+        lf.reset_for_new_epoch(702, 300, 0, 0, [0u64; 10], [0u64; 10], 0, 1_000).unwrap();
+        assert_eq!(lf.total_lamports, 0);
+        assert_eq!(lf.total_bets, 0);
+
+        lf.record_lifetime_wager(500, true);
+        assert_eq!(lf.lifetime_lamports_wagered, 3_500);
+        assert_eq!(lf.lifetime_bets, 2);
+    }
+
+    #[test]
+    fn entry_nonces_strictly_increase_within_a_chain() {
+        let mut lf = feed_with_bets();
+        let mut nonces = vec![lf.next_entry_nonce().unwrap(), lf.next_entry_nonce().unwrap()];
+
+        // Carried epochs stay in the same chain
+        lf.reset_for_new_epoch(701, 300, 3_000, 3, lf.lamports_per_number, lf.bets_per_number, 0, 1_000).unwrap();
+        nonces.push(lf.next_entry_nonce().unwrap());
+        lf.reset_for_new_epoch(702, 300, 3_000, 3, lf.lamports_per_number, lf.bets_per_number, 0, 1_000).unwrap();
+        nonces.push(lf.next_entry_nonce().unwrap());
+
+        assert_eq!(nonces[0], 1);
+        assert!(nonces.windows(2).all(|w| w[0] < w[1]));
+
+        // A fresh chain keeps counting rather than reusing nonces
+        lf.reset_for_new_epoch(703, 300, 0, 0, [0u64; 10], [0u64; 10], 0, 1_000).unwrap();
+        assert!(lf.next_entry_nonce().unwrap() > *nonces.last().unwrap());
+
+        lf.prediction_nonce = u64::MAX;
+        assert!(lf.next_entry_nonce().is_err());
+    }
+
+    #[test]
+    fn secondary_rollover_can_be_corrected_on_an_empty_feed() {
+        let mut lf = empty_feed();
+        lf.secondary_rollover_number = 4;
+        lf.add_house_seed(1_000).unwrap();
+
+        lf.set_secondary_rollover(6).unwrap();
+        assert_eq!(lf.secondary_rollover_number, 6);
+
+        let invalid = error!(IC42NErrorCode::InvalidRollOverNumber);
+        assert_eq!(lf.set_secondary_rollover(0).unwrap_err(), invalid);
+        assert_eq!(lf.set_secondary_rollover(10).unwrap_err(), invalid);
+        assert_eq!(lf.secondary_rollover_number, 6);
+    }
+
+    #[test]
+    fn secondary_rollover_is_locked_while_bets_or_carry_exist() {
+        let not_empty = error!(IC42NErrorCode::LiveFeedNotEmpty);
+
+        let mut with_bets = feed_with_bets();
+        assert_eq!(with_bets.set_secondary_rollover(6).unwrap_err(), not_empty);
+        assert_eq!(with_bets.secondary_rollover_number, 4);
+
+        let mut with_carry = empty_feed();
+        with_carry.carried_over_lamports = 500;
+        assert_eq!(with_carry.set_secondary_rollover(6).unwrap_err(), not_empty);
+
+        let mut with_carried_bets = empty_feed();
+        with_carried_bets.carried_over_bets = 1;
+        assert_eq!(with_carried_bets.set_secondary_rollover(6).unwrap_err(), not_empty);
+    }
+
+    #[test]
+    fn cancelled_chain_refunds_open_prediction() {
+        let mut lf = feed_with_bets();
+        assert!(!lf.is_refundable_chain(699));
+
+        // Open single-number prediction on 2 (the feed's only bet there)
+        let mut pred = Prediction::deserialize(&mut &[0u8; Prediction::SIZE][..]).unwrap();
+        pred.game_epoch = 699;
+        pred.selection_count = 1;
+        pred.selections[0] = 2;
+        pred.selections_mask = 1 << 2;
+        pred.lamports = 1_000;
+        pred.lamports_per_number = 1_000;
+
+        lf.chain_cancelled = 1;
+        assert!(lf.is_refundable_chain(pred.game_epoch));
+        assert!(!lf.is_refundable_chain(698));
+
+        retract_prediction_from_live(&mut lf, &pred).unwrap();
+        assert_eq!(lf.total_bets, 2);
+        assert_eq!(lf.total_lamports, 2_000);
+        assert_eq!(lf.bets_per_number[2], 0);
+        lf.reconcile().unwrap();
+
+        // A fresh chain is open for betting again
+        lf.reset_for_new_epoch(702, 300, 0, 0, [0u64; 10], [0u64; 10], 3, 1_000).unwrap();
+        assert!(!lf.is_chain_cancelled());
+    }
+
+    #[test]
+    fn placement_snapshots_the_pot_before_the_bet() {
+        let mut lf = feed_with_bets();
+        let mut pred = Prediction::deserialize(&mut &[0u8; Prediction::SIZE][..]).unwrap();
+        pred.selection_count = 2;
+        pred.selections[..2].copy_from_slice(&[2, 5]);
+
+        pred.placed_pot_snapshot = lf
+            .record_placement(1_000, 500, &pred.selections, pred.selection_count)
+            .unwrap();
+
+        assert_eq!(pred.placed_pot_snapshot, 3_000);
+        assert_eq!(lf.total_lamports, 4_000);
+        assert_eq!(lf.total_bets, 4);
+        assert_eq!(lf.bets_per_number[2], 2);
+        assert_eq!(lf.lamports_per_number[5], 500);
+        assert_eq!(lf.pending_resolution_epoch, 700);
+    }
 
     #[test]
     fn test_live_feed_size() {
-        let lf = LiveFeed {
+        let lf = empty_feed();
+
+        let bytes = lf.try_to_vec().unwrap();
+        assert_eq!(bytes.len(), LiveFeed::SIZE);
+    }
+
+    #[test]
+    fn legacy_counters_upgrade_in_place() {
+        let mut bets_per_number = [0u32; 10];
+        bets_per_number[2] = 1;
+        bets_per_number[7] = u32::MAX;
+
+        let legacy = LegacyLiveFeed {
+            epoch: 700,
+            first_epoch_in_chain: 699,
+            total_lamports: 3_000,
+            carried_over_lamports: 1_000,
+            total_bets: u32::MAX,
+            carried_over_bets: 2,
+            bet_cutoff_slots: 300,
+            tier: 2,
+            treasury: Pubkey::new_unique(),
+            epochs_carried_over: 1,
+            bump: 254,
+            lamports_per_number: [5u64; 10],
+            bets_per_number,
+            secondary_rollover_number: 4,
+            current_fee_bps: 500,
+            last_resolved_epoch: 698,
+            house_seed_lamports: 10,
+            pending_resolution_epoch: 700,
+            lifetime_lamports_wagered: 1 << 70,
+            lifetime_bets: 42,
+            _reserved: [0u8; 13],
+        };
+        let mut data = legacy.try_to_vec().unwrap();
+        assert_eq!(data.len(), LiveFeed::LEGACY_SIZE);
+
+        data.resize(LiveFeed::SIZE, 0);
+        LiveFeed::upgrade_legacy_counters(&mut data).unwrap();
+        let feed = LiveFeed::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(feed.total_bets, u32::MAX as u64);
+        assert_eq!(feed.carried_over_bets, 2);
+        assert_eq!(feed.bets_per_number[7], u32::MAX as u64);
+        assert_eq!(feed.bets_per_number[2], 1);
+        assert_eq!(feed.treasury, legacy.treasury);
+        assert_eq!(feed.current_fee_bps, 500);
+        assert_eq!(feed.pending_resolution_epoch, 700);
+        assert_eq!(feed.lifetime_lamports_wagered, 1 << 70);
+        assert_eq!(feed.lifetime_bets, 42);
+        assert_eq!(feed.prediction_nonce, 0);
+        assert_eq!(feed.try_to_vec().unwrap(), data);
+
+        // The wider counters keep counting past the old limit
+        let mut feed = feed;
+        feed.total_bets += 1;
+        assert_eq!(feed.total_bets, 1 << 32);
+    }
+
+    fn empty_feed() -> LiveFeed {
+        LiveFeed {
             epoch: 0,
             first_epoch_in_chain: 0,
             total_lamports: 0,
@@ -178,13 +988,17 @@ mod tests {
             epochs_carried_over: 0,
             bump: 0,
             lamports_per_number: [0u64; 10],
-            bets_per_number: [0u32; 10],
+            bets_per_number: [0u64; 10],
             secondary_rollover_number: 0,
             current_fee_bps: 0,
-            _reserved: [0u8; 61],
-        };
-
-        let bytes = lf.try_to_vec().unwrap();
-        assert_eq!(bytes.len(), LiveFeed::SIZE);
+            last_resolved_epoch: 0,
+            house_seed_lamports: 0,
+            pending_resolution_epoch: 0,
+            lifetime_lamports_wagered: 0,
+            lifetime_bets: 0,
+            prediction_nonce: 0,
+            chain_cancelled: 0,
+            _reserved: [0u8; 4],
+        }
     }
 }
\ No newline at end of file
@@ -5,6 +5,7 @@ pub mod tiers;
 pub mod treasury;
 pub mod player_profile;
 pub mod prediction;
+pub mod leaderboard;
 
 pub use config::*;
 pub use resolved_game::*;
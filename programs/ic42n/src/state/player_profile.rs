@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::constants::RECENT_BETS_CAP;
+use crate::errors::IC42NErrorCode;
 
 #[account]
 pub struct PlayerProfile {
@@ -25,13 +26,14 @@ pub struct PlayerProfile {
     // ─────────────────────────────
     // Recent bets ring buffer
     // ─────────────────────────────
-    /// Circular buffer of the last N bet pubkeys
-    pub recent_bets: [Pubkey; RECENT_BETS_CAP], // 32 * 40 = 1280
+    /// Circular buffer of the last `recent_bets_cap` bet pubkeys.
+    /// Allocated in full at creation (`len() == recent_bets_cap`).
+    pub recent_bets: Vec<Pubkey>, // 4 + 32 * cap
 
-    /// Number of valid entries currently stored (0~RECENT_BETS_CAP)
+    /// Number of valid entries currently stored (0~recent_bets_cap)
     pub recent_bets_len: u16, // 2
 
-    /// Next index to write (wraps around 0..RECENT_BETS_CAP-1)
+    /// Next index to write (wraps around 0..recent_bets_cap-1)
     pub recent_bets_head: u16, // 2
 
     /// Prevent closer if player is in game. 
@@ -39,18 +41,40 @@ pub struct PlayerProfile {
 
     /// first game played
     pub first_played_epoch: u64,
-    
+
+    /// Predictions placed during `last_played_epoch` (across all tiers).
+    pub epoch_predictions: u16, // 2
+
+    /// Lifetime tickets credited to this profile (after caps).
+    pub tickets_awarded_total: u64, // 8
+
+    /// Level derived from `xp_points` (see `utils::xp::level_for_xp`).
+    pub level: u16, // 2
+
+    /// Predictions placed but not yet settled (claimed, refunded, or closed
+    /// as a loss). The profile can't be closed while this is non-zero.
+    pub unclaimed_predictions: u16, // 2
+
+    /// Ring size chosen at creation (0..=RECENT_BETS_CAP; 0 = no history).
+    /// Took over the old 2-byte `_reserved` slot.
+    pub recent_bets_cap: u16, // 2
+
     // ─────────────────────────────
-    // Reserved for future upgrades
+    // Referrals (appended; legacy profiles use `migrate_player_profile`)
     // ─────────────────────────────
-    pub _reserved: [u8; 16],
+    /// Wallet that referred this player (default = none). Set once at creation.
+    pub referrer: Pubkey, // 32
+
+    /// Number of players who created their profile with this player as referrer.
+    pub referred_count: u32, // 4
 }
 
 impl PlayerProfile {
     pub const SEED_PREFIX: &'static [u8] = b"profile";
 
-    /// Total serialized size (not including the 8-byte discriminator)
-    pub const SIZE: usize =
+    /// Serialized size without any ring entries (not including the 8-byte
+    /// discriminator); each entry adds 32 bytes.
+    pub const BASE_SIZE: usize =
         32  // player
             + 1   // bump
             + 4   // tickets_available
@@ -60,23 +84,293 @@ impl PlayerProfile {
             + 1   // last_played_tier
             + 8   // last_played_timestamp
             + 4   // xp_points
-            + (32 * RECENT_BETS_CAP) // recent_bets
+            + 4   // recent_bets (Vec length prefix)
             + 2   // recent_bets_len
             + 2   // recent_bets_head
             + 8   // locked_until_epoch
             + 8   // first_played_epoch
-            + 16; // reserved
+            + 2   // epoch_predictions
+            + 8   // tickets_awarded_total
+            + 2   // level
+            + 2   // unclaimed_predictions
+            + 2   // recent_bets_cap
+            + 32  // referrer
+            + 4;  // referred_count
+
+    /// Size of a profile at the default (max) ring size.
+    pub const SIZE: usize = Self::BASE_SIZE + 32 * RECENT_BETS_CAP;
+
+    /// Size of profiles whose ring was a fixed `[Pubkey; RECENT_BETS_CAP]`
+    /// (no length prefix). Upgraded by `migrate_player_profile`.
+    pub const FIXED_RING_SIZE: usize = Self::SIZE - 4;
+
+    /// Size of fixed-ring profiles created before referral fields were appended.
+    pub const LEGACY_SIZE: usize = Self::FIXED_RING_SIZE - 32 - 4;
+
+    /// Byte offset of `recent_bets` in account data (incl. discriminator).
+    const RECENT_BETS_OFFSET: usize = 8 + 32 + 1 + 4 + 8 + 8 + 8 + 1 + 8 + 4;
+
+    /// Bytes between the end of the ring and `recent_bets_cap`.
+    const CAP_AFTER_RING: usize = 2 + 2 + 8 + 8 + 2 + 8 + 2 + 2;
+
+    /// Rewrites fixed-ring account data (first `old_len` bytes) in place into
+    /// the current layout. `data` must already be `8 + SIZE` bytes long.
+    ///
+    /// The ring is shifted to make room for its Vec length prefix, the tail
+    /// past the old data is zeroed, and `recent_bets_cap` is set.
+    pub fn upgrade_fixed_ring(data: &mut [u8], old_len: usize) {
+        let ring = Self::RECENT_BETS_OFFSET;
+
+        data.copy_within(ring..old_len, ring + 4);
+        data[old_len + 4..].fill(0);
+        data[ring..ring + 4].copy_from_slice(&(RECENT_BETS_CAP as u32).to_le_bytes());
+
+        let cap = ring + 4 + 32 * RECENT_BETS_CAP + Self::CAP_AFTER_RING;
+        data[cap..cap + 2].copy_from_slice(&(RECENT_BETS_CAP as u16).to_le_bytes());
+    }
+
+    /// Serialized size of a profile whose ring holds `cap` entries.
+    pub fn size_for_cap(cap: u16) -> usize {
+        Self::BASE_SIZE + 32 * cap as usize
+    }
 
-    /// Push a bet pubkey into the ring buffer (keeps only the last N)
+    /// Allocates an empty ring of `cap` entries.
+    pub fn init_recent_bets(&mut self, cap: u16) -> Result<()> {
+        require!(
+            cap as usize <= RECENT_BETS_CAP,
+            IC42NErrorCode::InvalidRecentBetsCap
+        );
+        self.recent_bets = vec![Pubkey::default(); cap as usize];
+        self.recent_bets_cap = cap;
+        self.recent_bets_len = 0;
+        self.recent_bets_head = 0;
+        Ok(())
+    }
+
+    /// Push a bet pubkey into the ring buffer (keeps only the last
+    /// `recent_bets_cap`). No-op for profiles without history.
     pub fn push_recent_bet(&mut self, bet: Pubkey) {
-        let head = self.recent_bets_head as usize;
+        let cap = (self.recent_bets_cap as usize).min(self.recent_bets.len());
+        if cap == 0 {
+            return;
+        }
+
+        let head = self.recent_bets_head as usize % cap;
         self.recent_bets[head] = bet;
 
-        let next = (head + 1) % RECENT_BETS_CAP;
+        let next = (head + 1) % cap;
         self.recent_bets_head = next as u16;
 
-        if (self.recent_bets_len as usize) < RECENT_BETS_CAP {
+        if (self.recent_bets_len as usize) < cap {
             self.recent_bets_len += 1;
         }
     }
+
+    /// Predictions placed during `epoch`. The counter is only meaningful for
+    /// `last_played_epoch`, so any other epoch starts from zero.
+    pub fn predictions_in_epoch(&self, epoch: u64) -> u16 {
+        if self.last_played_epoch == epoch {
+            self.epoch_predictions
+        } else {
+            0
+        }
+    }
+
+    /// Marks one open prediction as settled. Saturates so predictions placed
+    /// before the counter existed can't underflow it.
+    pub fn settle_prediction(&mut self) {
+        self.unclaimed_predictions = self.unclaimed_predictions.saturating_sub(1);
+    }
+
+    /// Spends one change ticket.
+    pub fn consume_ticket(&mut self) -> Result<()> {
+        self.consume_tickets(1)
+    }
+
+    /// Spends `cost` change tickets, or none if fewer are available.
+    pub fn consume_tickets(&mut self, cost: u32) -> Result<()> {
+        require!(self.tickets_available >= cost, IC42NErrorCode::NoChangeTickets);
+        self.tickets_available -= cost;
+        Ok(())
+    }
+
+    /// Validates a referral for `player`, given the `player` field stored in
+    /// the referrer's profile. Self-referrals and uninitialized profiles are rejected.
+    pub fn check_referral(
+        player: &Pubkey,
+        referrer: &Pubkey,
+        referrer_profile_player: &Pubkey,
+    ) -> Result<()> {
+        require_keys_neq!(*referrer, *player, IC42NErrorCode::SelfReferral);
+        require!(
+            *referrer_profile_player != Pubkey::default() && referrer_profile_player == referrer,
+            IC42NErrorCode::InvalidReferrer
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    fn profile_with_cap(cap: u16) -> PlayerProfile {
+        let mut profile = PlayerProfile {
+            player: Pubkey::default(),
+            bump: 0,
+            tickets_available: 0,
+            total_bets: 0,
+            total_lamports_wagered: 0,
+            last_played_epoch: 0,
+            last_played_tier: 0,
+            last_played_timestamp: 0,
+            xp_points: 0,
+            recent_bets: Vec::new(),
+            recent_bets_len: 0,
+            recent_bets_head: 0,
+            locked_until_epoch: 0,
+            first_played_epoch: 0,
+            epoch_predictions: 0,
+            tickets_awarded_total: 0,
+            level: 0,
+            unclaimed_predictions: 0,
+            recent_bets_cap: 0,
+            referrer: Pubkey::default(),
+            referred_count: 0,
+        };
+        profile.init_recent_bets(cap).unwrap();
+        profile
+    }
+
+    #[test]
+    fn test_player_profile_size() {
+        for cap in [0u16, 8, RECENT_BETS_CAP as u16] {
+            let bytes = profile_with_cap(cap).try_to_vec().unwrap();
+            assert_eq!(bytes.len(), PlayerProfile::size_for_cap(cap));
+        }
+        assert_eq!(PlayerProfile::size_for_cap(RECENT_BETS_CAP as u16), PlayerProfile::SIZE);
+    }
+
+    #[test]
+    fn fixed_ring_sizes_never_match_a_vec_layout() {
+        // `migrate_player_profile` tells layouts apart by length alone
+        for cap in 0..=RECENT_BETS_CAP as u16 {
+            let size = PlayerProfile::size_for_cap(cap);
+            assert_ne!(size, PlayerProfile::FIXED_RING_SIZE);
+            assert_ne!(size, PlayerProfile::LEGACY_SIZE);
+        }
+    }
+
+    #[test]
+    fn fixed_ring_profiles_upgrade_in_place() {
+        let mut profile = profile_with_cap(RECENT_BETS_CAP as u16);
+        profile.tickets_available = 3;
+        profile.push_recent_bet(Pubkey::new_unique());
+        profile.unclaimed_predictions = 2;
+        profile.referrer = Pubkey::new_unique();
+        profile.referred_count = 7;
+
+        let mut current = PlayerProfile::DISCRIMINATOR.to_vec();
+        profile.serialize(&mut current).unwrap();
+
+        // Fixed ring = current bytes without the Vec prefix and with cap 0
+        let ring = PlayerProfile::RECENT_BETS_OFFSET;
+        let mut fixed = current.clone();
+        fixed.drain(ring..ring + 4);
+        let cap = ring + 32 * RECENT_BETS_CAP + PlayerProfile::CAP_AFTER_RING;
+        fixed[cap..cap + 2].fill(0);
+        assert_eq!(fixed.len(), 8 + PlayerProfile::FIXED_RING_SIZE);
+
+        let mut upgraded = fixed.clone();
+        upgraded.resize(8 + PlayerProfile::SIZE, 0xAA);
+        PlayerProfile::upgrade_fixed_ring(&mut upgraded, fixed.len());
+        assert_eq!(upgraded, current);
+
+        // Pre-referral profiles come out with referral fields zeroed
+        let legacy_len = 8 + PlayerProfile::LEGACY_SIZE;
+        let mut legacy = fixed[..legacy_len].to_vec();
+        legacy.resize(8 + PlayerProfile::SIZE, 0xAA);
+        PlayerProfile::upgrade_fixed_ring(&mut legacy, legacy_len);
+
+        let decoded = PlayerProfile::try_deserialize(&mut &legacy[..]).unwrap();
+        assert_eq!(decoded.recent_bets_cap, RECENT_BETS_CAP as u16);
+        assert_eq!(decoded.recent_bets, profile.recent_bets);
+        assert_eq!(decoded.unclaimed_predictions, 2);
+        assert_eq!(decoded.referrer, Pubkey::default());
+        assert_eq!(decoded.referred_count, 0);
+    }
+
+    #[test]
+    fn recent_bets_wrap_at_small_cap() {
+        let mut profile = profile_with_cap(3);
+        let bets: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+        for bet in bets.iter() {
+            profile.push_recent_bet(*bet);
+        }
+
+        // Last three kept; the 4th and 5th overwrote slots 0 and 1
+        assert_eq!(profile.recent_bets_len, 3);
+        assert_eq!(profile.recent_bets_head, 2);
+        assert_eq!(profile.recent_bets, vec![bets[3], bets[4], bets[2]]);
+    }
+
+    #[test]
+    fn zero_cap_keeps_no_history() {
+        let mut profile = profile_with_cap(0);
+        profile.push_recent_bet(Pubkey::new_unique());
+
+        assert!(profile.recent_bets.is_empty());
+        assert_eq!(profile.recent_bets_len, 0);
+        assert_eq!(profile.recent_bets_head, 0);
+
+        assert!(profile.init_recent_bets(RECENT_BETS_CAP as u16 + 1).is_err());
+    }
+
+    #[test]
+    fn consume_ticket_stops_at_zero() {
+        let mut profile = profile_with_cap(0);
+        profile.tickets_available = 2;
+
+        profile.consume_ticket().unwrap();
+        profile.consume_ticket().unwrap();
+        assert_eq!(profile.tickets_available, 0);
+        assert_eq!(
+            profile.consume_ticket().unwrap_err(),
+            IC42NErrorCode::NoChangeTickets.into()
+        );
+    }
+
+    #[test]
+    fn change_costing_two_tickets_needs_two() {
+        let mut profile = profile_with_cap(0);
+        profile.tickets_available = 1;
+
+        // Not enough for a 2-ticket tier: nothing is spent
+        assert_eq!(
+            profile.consume_tickets(2).unwrap_err(),
+            IC42NErrorCode::NoChangeTickets.into()
+        );
+        assert_eq!(profile.tickets_available, 1);
+
+        profile.tickets_available = 3;
+        profile.consume_tickets(2).unwrap();
+        assert_eq!(profile.tickets_available, 1);
+    }
+
+    #[test]
+    fn referral_rejects_self_and_uninitialized() {
+        let player = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+
+        assert!(PlayerProfile::check_referral(&player, &referrer, &referrer).is_ok());
+
+        // Self-referral
+        assert!(PlayerProfile::check_referral(&player, &player, &player).is_err());
+
+        // Referrer profile not initialized / belongs to someone else
+        assert!(PlayerProfile::check_referral(&player, &referrer, &Pubkey::default()).is_err());
+        assert!(PlayerProfile::check_referral(&player, &referrer, &Pubkey::new_unique()).is_err());
+    }
 }
\ No newline at end of file
@@ -26,6 +26,9 @@ pub struct Prediction {
     /// 1 = split (any number of numbers)
     /// 2 = high_low
     /// 3 = even_odd
+    /// 4 = multi_number
+    /// 5 = range (contiguous band, e.g. 4..=7)
+    /// 6 = all_except (every eligible number minus the excluded digits)
     pub prediction_type: u8,
 
     /// How many entries in `selections` are active (1..=8).
@@ -84,19 +87,49 @@ pub struct Prediction {
 
     /// Lamports wagered per selected number.
     pub lamports_per_number: u64,
-    
-    /// Reserved for future use.
-    pub _reserved: [u8; 8],
+
+    /// 1 if the prediction was refunded via the emergency path.
+    /// Refunds also set `has_claimed` so the prediction is settled for good.
+    pub refunded: u8,
+
+    /// Win-probability hint: share of the 9 playable numbers covered, in bps.
+    /// `selection_count * 10_000 / 9` (single number = 1111).
+    pub coverage_bps: u16,
+
+    /// Merkle leaf index of this prediction in its game's winner tree,
+    /// written by `assign_leaf_indices` (valid only if `leaf_index_assigned`).
+    pub leaf_index: u32,
+
+    /// 1 once `leaf_index` has been assigned.
+    pub leaf_index_assigned: u8,
+
+    // ─────────────────────────────
+    // Version 3 tail, zeroed by `migrate_prediction` on older predictions
+    // ─────────────────────────────
+
+    /// Position of this entry in its tier's placement order
+    /// (`LiveFeed.prediction_nonce` at placement, starting at 1).
+    pub entry_nonce: u64,
+
+    /// Slot of the last mutation (placement, change, increase, reroll,
+    /// refund).
+    pub last_updated_slot: u64,
+
+    /// `LiveFeed.total_lamports` just before this prediction was placed, so
+    /// later pot growth can be read off against it.
+    pub placed_pot_snapshot: u64,
 }
 
 impl Prediction {
     pub const SEED_PREFIX: &'static [u8] = b"prediction";
-    pub const VERSION: u8 = 2;
+    pub const VERSION: u8 = 3;
     pub const TYPE_SINGLE_NUMBER: u8 = 0;
     pub const TYPE_TWO_NUMBERS: u8 = 1;
     pub const TYPE_HIGH_LOW: u8 = 2;
     pub const TYPE_EVEN_ODD: u8 = 3;
     pub const TYPE_MULTI_NUMBER: u8 = 4;
+    pub const TYPE_RANGE: u8 = 5;
+    pub const TYPE_ALL_EXCEPT: u8 = 6;
 
     /// Space excluding the 8-byte discriminator.
     ///
@@ -121,7 +154,28 @@ impl Prediction {
             1 +  // bump
             1 +  // version
             8 +  // lamports per number
-            8;  // _reserved
+            1 +  // refunded
+            2 +  // coverage_bps
+            4 +  // leaf_index
+            1 +  // leaf_index_assigned
+            8 +  // entry_nonce
+            8 +  // last_updated_slot
+            8;   // placed_pot_snapshot
+
+    /// Size of version 2 predictions, without the version 3 tail; grown in
+    /// place by `migrate_prediction`.
+    pub const LEGACY_SIZE: usize = Self::SIZE - 8 - 8 - 8;
+
+    /// Share of numbers 1..=9 covered by `selection_count` selections, in bps.
+    pub fn coverage_bps_for(selection_count: u8) -> u16 {
+        ((selection_count as u32 * 10_000) / 9) as u16
+    }
+
+    /// Stamps a mutation at `slot` / `unix_timestamp`.
+    pub fn touch(&mut self, slot: u64, unix_timestamp: i64) {
+        self.last_updated_slot = slot;
+        self.last_updated_at_ts = unix_timestamp;
+    }
 
     pub fn per_selection_lamports(&self) -> u64 {
         self.lamports_per_number
@@ -146,6 +200,43 @@ impl Prediction {
         (self.selections_mask & (1u16 << n)) != 0
     }
 
+    /// True if this prediction belongs to the chain starting at
+    /// `first_epoch_in_chain` and was placed no later than `game_epoch`,
+    /// the epoch that resolved it.
+    pub fn is_in_chain(&self, first_epoch_in_chain: u64, game_epoch: u64) -> bool {
+        self.game_epoch == first_epoch_in_chain
+            && self.epoch >= first_epoch_in_chain
+            && self.epoch <= game_epoch
+    }
+
+    /// Fails unless this prediction belongs to the live chain starting at
+    /// `first_epoch_in_chain`. A prediction from an earlier chain was settled
+    /// by that chain's game (`PredictionGameResolved`: claim it instead).
+    pub fn assert_in_live_chain(&self, first_epoch_in_chain: u64) -> Result<()> {
+        require!(
+            self.game_epoch >= first_epoch_in_chain,
+            IC42NErrorCode::PredictionGameResolved
+        );
+        require_eq!(self.game_epoch, first_epoch_in_chain, IC42NErrorCode::EpochMismatch);
+        Ok(())
+    }
+
+    /// Fails unless this prediction may take the emergency refund: it is
+    /// unsettled and its stake is still in the live pot of the chain starting
+    /// at `first_epoch_in_chain`. A resolved chain already put the stake in
+    /// its prize pool, so its players claim instead.
+    pub fn assert_refundable(&self, first_epoch_in_chain: u64) -> Result<()> {
+        // Claimed (or already refunded) predictions have nothing left to return
+        require!(self.has_claimed == 0, IC42NErrorCode::AlreadyClaimed);
+        require!(self.lamports > 0, IC42NErrorCode::InvalidBetAmount);
+        self.assert_in_live_chain(first_epoch_in_chain)
+    }
+
+    /// True if `index` is the leaf index assigned to this prediction.
+    pub fn is_leaf_index(&self, index: u32) -> bool {
+        self.leaf_index_assigned != 0 && self.leaf_index == index
+    }
+
     pub fn recompute_mask_from_selections(&self) -> u16 {
         let mut m: u16 = 0;
         let n = self.selection_count.min(8) as usize;
@@ -156,4 +247,157 @@ impl Prediction {
         }
         m
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+    use crate::utils::prediction::derive_prediction_selections;
+
+    fn empty_prediction() -> Prediction {
+        Prediction {
+            game_epoch: 0,
+            epoch: 0,
+            player: Pubkey::default(),
+            tier: 0,
+            prediction_type: 0,
+            selection_count: 0,
+            selections_mask: 0,
+            selections: [0u8; 8],
+            lamports: 0,
+            changed_count: 0,
+            placed_slot: 0,
+            placed_at_ts: 0,
+            last_updated_at_ts: 0,
+            has_claimed: 0,
+            claimed_at_ts: 0,
+            bump: 0,
+            version: 0,
+            lamports_per_number: 0,
+            refunded: 0,
+            coverage_bps: 0,
+            leaf_index: 0,
+            leaf_index_assigned: 0,
+            entry_nonce: 0,
+            last_updated_slot: 0,
+            placed_pot_snapshot: 0,
+        }
+    }
+
+    #[test]
+    fn test_prediction_size() {
+        let pred = empty_prediction();
+
+        let bytes = pred.try_to_vec().unwrap();
+        assert_eq!(bytes.len(), Prediction::SIZE);
+
+        // The deployed version 2 layout, grown in one step by migrate_prediction
+        assert_eq!(Prediction::LEGACY_SIZE, 121);
+        let mut migrated = bytes[..Prediction::LEGACY_SIZE].to_vec();
+        migrated.resize(Prediction::SIZE, 0);
+        let pred = Prediction::try_from_slice(&migrated).unwrap();
+        assert_eq!(pred.entry_nonce, 0);
+        assert_eq!(pred.placed_pot_snapshot, 0);
+    }
+
+    #[test]
+    fn mutations_advance_last_updated_slot() {
+        let mut pred = empty_prediction();
+
+        // Placement
+        pred.placed_slot = 1_000;
+        pred.touch(1_000, 50);
+        assert_eq!(pred.last_updated_slot, 1_000);
+
+        // A number change, then an increase, in later slots
+        pred.touch(1_250, 51);
+        assert_eq!(pred.last_updated_slot, 1_250);
+        pred.touch(1_900, 53);
+        assert_eq!(pred.last_updated_slot, 1_900);
+        assert_eq!(pred.last_updated_at_ts, 53);
+
+        // The original placement slot is kept
+        assert_eq!(pred.placed_slot, 1_000);
+    }
+
+    #[test]
+    fn increase_after_resolution_points_to_claim() {
+        let mut pred = empty_prediction();
+        pred.game_epoch = 700;
+        pred.epoch = 701;
+
+        assert!(pred.assert_in_live_chain(700).is_ok());
+
+        // Epoch 702 paid out; the live feed moved on to a chain starting at 703
+        assert_eq!(
+            pred.assert_in_live_chain(703).unwrap_err(),
+            error!(IC42NErrorCode::PredictionGameResolved)
+        );
+        assert_eq!(
+            pred.assert_in_live_chain(699).unwrap_err(),
+            error!(IC42NErrorCode::EpochMismatch)
+        );
+    }
+
+    #[test]
+    fn refund_is_rejected_once_the_chain_resolved() {
+        let mut pred = empty_prediction();
+        pred.game_epoch = 700;
+        pred.epoch = 700;
+        pred.lamports = 1_000;
+        assert!(pred.assert_refundable(700).is_ok());
+
+        // The chain's game paid out (this prediction lost); the feed moved on
+        assert_eq!(
+            pred.assert_refundable(701).unwrap_err(),
+            error!(IC42NErrorCode::PredictionGameResolved)
+        );
+
+        pred.has_claimed = 1;
+        assert_eq!(
+            pred.assert_refundable(700).unwrap_err(),
+            error!(IC42NErrorCode::AlreadyClaimed)
+        );
+    }
+
+    #[test]
+    fn claim_requires_prediction_inside_resolved_chain() {
+        let mut pred = empty_prediction();
+        pred.game_epoch = 700;
+        pred.epoch = 702;
+
+        // Chain 700..=702 resolved at 702, or later after more rollovers
+        assert!(pred.is_in_chain(700, 702));
+        assert!(pred.is_in_chain(700, 705));
+
+        // Placed after the game it tries to claim against
+        assert!(!pred.is_in_chain(700, 701));
+
+        // Different chain, or a placement epoch before the chain began
+        assert!(!pred.is_in_chain(699, 702));
+        pred.epoch = 699;
+        assert!(!pred.is_in_chain(700, 702));
+    }
+
+    #[test]
+    fn coverage_bps_per_prediction_type() {
+        let coverage = |prediction_type: u8, choice: u32, blocked: u8| {
+            let (count, _, _) =
+                derive_prediction_selections(prediction_type, choice, blocked).unwrap();
+            Prediction::coverage_bps_for(count)
+        };
+
+        assert_eq!(coverage(Prediction::TYPE_SINGLE_NUMBER, 7, 3), 1_111);
+        assert_eq!(coverage(Prediction::TYPE_TWO_NUMBERS, 27, 3), 2_222);
+        assert_eq!(coverage(Prediction::TYPE_MULTI_NUMBER, 123, 9), 3_333);
+        assert_eq!(coverage(Prediction::TYPE_HIGH_LOW, 1, 3), 4_444);
+
+        // Even/odd sets shrink when the blocked number falls in them
+        assert_eq!(coverage(Prediction::TYPE_EVEN_ODD, 1, 2), 5_555); // 1,3,5,7,9
+        assert_eq!(coverage(Prediction::TYPE_EVEN_ODD, 1, 3), 4_444); // 1,5,7,9
+        assert_eq!(coverage(Prediction::TYPE_EVEN_ODD, 0, 2), 3_333); // 4,6,8
+
+        assert_eq!(Prediction::coverage_bps_for(8), 8_888);
+    }
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::utils::bitmap::set_claimed;
 
 #[repr(u8)]
 pub enum GameStatus {
@@ -12,11 +14,22 @@ pub enum RolloverReason {
     None = 0,
     NoWinners = 1,
     RolloverNumber = 2,
+    PotBelowThreshold = 3,
 }
 impl RolloverReason {
     pub fn as_u8(self) -> u8 { self as u8 }
 }
 
+/// How internal Merkle nodes are ordered for a game's `merkle_root`.
+#[repr(u8)]
+pub enum MerkleScheme {
+    Indexed = 0, // Pair order follows leaf index parity
+    Sorted  = 1, // Pair order is min(a, b) || max(a, b)
+}
+impl MerkleScheme {
+    pub fn as_u8(self) -> u8 { self as u8 }
+}
+
 /// ---------------------------------------------------------------------------
 /// ResolvedGame
 /// ---------------------------------------------------------------------------
@@ -53,7 +66,8 @@ pub struct ResolvedGame {
     pub last_updated_slot: u64,
     pub last_updated_ts: i64,
 
-    // Accounting
+    // Accounting. Bet counts stay u32 (the LiveFeed counters are u64) so
+    // existing games keep their layout; see `bet_count`.
     pub carry_over_bets: u32,
     pub total_bets: u32,
     pub carry_in_lamports: u64,
@@ -77,7 +91,28 @@ pub struct ResolvedGame {
     pub rollover_reason: u8,
     pub secondary_rollover_number: u8,
     pub fee_bps: u16,
-    pub _reserved: [u8; 12],
+    pub merkle_scheme: u8,
+
+    // Claim expiry (0 = no deadline); set once unclaimed funds are swept
+    pub claim_deadline_epoch: u64,
+    pub unclaimed_swept: u8,
+
+    // Claims stay locked until this slot so a bad result can be reverted (0 = none)
+    pub dispute_until_slot: u64,
+
+    // Deferred protocol fee (see `collect_protocol_fee`)
+    pub fee_collectable_slot: u64,
+    pub fee_collected: u8,
+
+    // House seed pulled from a no-winner pot; 0 once recovered
+    pub house_seed_lamports: u64,
+
+    // Bytes of `results_uri` past the 128-byte field, stored in the account
+    // right after this struct (see `results_uri_ext`); 0 = none
+    pub results_uri_ext_len: u8,
+
+    // 1 = claims held by the authority while the result is disputed
+    pub frozen: u8,
 }
 
 impl ResolvedGame {
@@ -85,6 +120,17 @@ impl ResolvedGame {
     pub const MAX_WINNERS_PER_GAME: usize = 50_000;
     pub const MAX_BITMAP_LEN: usize = (Self::MAX_WINNERS_PER_GAME + 7) / 8;
 
+    /// First `version` whose fee is deferred; older games paid it at resolve.
+    pub const DEFERRED_FEE_VERSION: u8 = 3;
+
+    /// First `version` whose Merkle leaves and nodes are domain-separated
+    /// (`IC42N_V3`); older games keep the `IC42N_V2` hashing.
+    pub const DOMAIN_SEPARATED_MERKLE_VERSION: u8 = 3;
+
+    /// First `version` whose claims must match the prediction's assigned
+    /// `leaf_index`; older games are claimable by proof alone.
+    pub const LEAF_INDEX_VERSION: u8 = 4;
+
     // Fixed fields + Vec length prefix (u32). Excludes bitmap bytes themselves.
     pub const BASE_SIZE: usize =
         8   + // epoch
@@ -115,22 +161,241 @@ impl ResolvedGame {
             1   + // rollover_reason
             1   + // secondary_rollover_number
             2   + // feeBps
-            12;   // reserved
+            1   + // merkle_scheme
+            8   + // claim_deadline_epoch
+            1   + // unclaimed_swept
+            8   + // dispute_until_slot
+            8   + // fee_collectable_slot
+            1   + // fee_collected
+            8   + // house_seed_lamports
+            1   + // results_uri_ext_len
+            1;    // frozen
+
+    /// Longest `results_uri` tail kept past the 128-byte field.
+    pub const MAX_RESULTS_URI_EXT_LEN: usize = u8::MAX as usize;
 
+    /// Upper bound for a game with `MAX_WINNERS_PER_GAME` winners.
     pub const SIZE: usize = Self::BASE_SIZE + Self::MAX_BITMAP_LEN;
 
+    /// Bitmap bytes needed to track `total_winners` claims.
+    pub fn bitmap_len(total_winners: u32) -> usize {
+        (total_winners as usize).div_ceil(8)
+    }
+
+    /// Exact account size (excluding discriminator) once the winner count is known.
+    ///
+    /// Games are created with `BASE_SIZE` and grown to this size at resolve,
+    /// instead of always paying rent for `MAX_BITMAP_LEN`.
+    ///
+    /// Rent-exempt minimum at 6,960 lamports/byte (incl. 128-byte account overhead):
+    /// - MAX (50k winners): 6,580 bytes → 46,687,680 lamports (~0.0467 SOL)
+    /// - 100 winners:         343 bytes →  3,278,160 lamports (~0.0033 SOL)
+    ///
+    /// i.e. ~0.0434 SOL (~93%) saved per typical 100-winner game.
+    pub fn size_for_winners(total_winners: u32) -> usize {
+        Self::BASE_SIZE + Self::bitmap_len(total_winners)
+    }
+
+    /// Claims are accepted until `claim_deadline_epoch` (inclusive) and never
+    /// after unclaimed funds were swept.
+    pub fn is_claim_window_open(&self, current_epoch: u64) -> bool {
+        self.unclaimed_swept == 0
+            && (self.claim_deadline_epoch == 0 || current_epoch <= self.claim_deadline_epoch)
+    }
+
+    /// Narrows a LiveFeed bet counter for storage, failing rather than
+    /// truncating past `u32::MAX`.
+    pub fn bet_count(bets: u64) -> Result<u32> {
+        u32::try_from(bets).map_err(|_| error!(IC42NErrorCode::MathOverflow))
+    }
+
+    /// Writes the `results_uri` tail of a game resolved with `total_winners`
+    /// winners into its account data (discriminator included), right after
+    /// the serialized struct. The account must already be sized for it.
+    pub fn write_results_uri_ext(account_data: &mut [u8], total_winners: u32, ext: &[u8]) -> Result<()> {
+        let start = 8 + Self::size_for_winners(total_winners);
+        let end = start + ext.len();
+        require!(account_data.len() >= end, IC42NErrorCode::InvalidInput);
+        account_data[start..end].copy_from_slice(ext);
+        Ok(())
+    }
+
+    /// The `results_uri` tail stored after this game's struct in `account_data`.
+    pub fn results_uri_ext<'a>(&self, account_data: &'a [u8]) -> &'a [u8] {
+        let start = 8 + Self::BASE_SIZE + self.claimed_bitmap.len();
+        account_data
+            .get(start..start + self.results_uri_ext_len as usize)
+            .unwrap_or(&[])
+    }
+
+    /// The complete results URI: the unpadded 128-byte field plus its tail.
+    pub fn full_results_uri(&self, account_data: &[u8]) -> Vec<u8> {
+        let len = self.results_uri.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+        let mut uri = self.results_uri[..len].to_vec();
+        uri.extend_from_slice(self.results_uri_ext(account_data));
+        uri
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen != 0
+    }
+
+    /// Claims are rejected while the authority holds the game (see `freeze_game`).
+    pub fn assert_not_frozen(&self) -> Result<()> {
+        require!(!self.is_frozen(), IC42NErrorCode::GameFrozen);
+        Ok(())
+    }
+
+    /// Gives claimers a full window again after a freeze: the deadline moves
+    /// out to `new_deadline` if that is later. Games without a deadline (or
+    /// without winners) keep none.
+    pub fn extend_claim_deadline(&mut self, new_deadline: u64) {
+        if self.claim_deadline_epoch != 0 && self.total_winners > 0 {
+            self.claim_deadline_epoch = self.claim_deadline_epoch.max(new_deadline);
+        }
+    }
+
+    /// Claimed totals never pass what the game committed to pay out.
+    pub fn assert_claim_accounting(&self) -> Result<()> {
+        require!(
+            self.claimed_lamports <= self.net_prize_pool
+                && self.claimed_winners <= self.total_winners,
+            IC42NErrorCode::ClaimAccountingOverflow
+        );
+        Ok(())
+    }
+
+    /// Records one paid claim: sets its bitmap bit and advances the claimed
+    /// totals. Nothing is written unless the new totals pass
+    /// `assert_claim_accounting`, so callers can apply this before moving
+    /// lamports. The index must already be checked with `check_claim_index`.
+    pub fn record_claim(&mut self, index: u32, amount: u64) -> Result<()> {
+        let claimed_lamports = self
+            .claimed_lamports
+            .checked_add(amount)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        let claimed_winners = self
+            .claimed_winners
+            .checked_add(1)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        require!(
+            claimed_lamports <= self.net_prize_pool && claimed_winners <= self.total_winners,
+            IC42NErrorCode::ClaimAccountingOverflow
+        );
+
+        set_claimed(&mut self.claimed_bitmap, index);
+        self.claimed_lamports = claimed_lamports;
+        self.claimed_winners = claimed_winners;
+        Ok(())
+    }
+
+    pub fn uses_domain_separated_merkle(&self) -> bool {
+        self.version >= Self::DOMAIN_SEPARATED_MERKLE_VERSION
+    }
+
+    pub fn requires_leaf_index(&self) -> bool {
+        self.version >= Self::LEAF_INDEX_VERSION
+    }
+
+    /// True while the result can still be reverted and claims are locked.
+    pub fn is_in_dispute_window(&self, slot: u64) -> bool {
+        slot < self.dispute_until_slot
+    }
+
+    /// True while this game owes a deferred protocol fee: it paid out
+    /// (rollovers carry the whole pot, so any stored fee was never taken), and
+    /// the fee was neither paid at resolve (legacy) nor collected yet.
+    pub fn owes_fee(&self) -> bool {
+        self.version >= Self::DEFERRED_FEE_VERSION
+            && self.total_winners > 0
+            && self.protocol_fee_lamports > 0
+            && self.fee_collected == 0
+    }
+
+    /// True once the owed protocol fee may move to the fee vault: the game is
+    /// resolved, `owes_fee`, out of its dispute window and past
+    /// `fee_collectable_slot`.
+    pub fn can_collect_fee(&self, slot: u64) -> bool {
+        self.status == GameStatus::Resolved as u8
+            && !self.is_frozen()
+            && self.owes_fee()
+            && !self.is_in_dispute_window(slot)
+            && slot >= self.fee_collectable_slot
+    }
+
+    /// True if a committed result was reverted and awaits a corrected commit.
+    pub fn is_reverted(&self) -> bool {
+        self.status == GameStatus::Processing as u8 && self.resolved_at != 0
+    }
+
+    /// Lamports the treasury still owes for this game: unclaimed winnings
+    /// (until swept), the deferred fee until collected, and any unrecovered
+    /// house seed. A reverted game still owes its pool.
+    pub fn outstanding_lamports(&self) -> u64 {
+        let committed = self.status == GameStatus::Resolved as u8 || self.is_reverted();
+        if !committed {
+            return 0;
+        }
+
+        let unclaimed = if self.unclaimed_swept == 0 {
+            self.net_prize_pool.saturating_sub(self.claimed_lamports)
+        } else {
+            0
+        };
+        let owed_fee = if self.owes_fee() { self.protocol_fee_lamports } else { 0 };
+
+        unclaimed
+            .saturating_add(owed_fee)
+            .saturating_add(self.house_seed_lamports)
+    }
+
+    /// True once a resolved game holds nothing anyone can still collect, so
+    /// its account may be closed: no claim can land anymore (every winner
+    /// claimed, or the claim window closed and the remainder was swept), the
+    /// deferred fee was collected and any house seed was recovered.
+    pub fn is_closable(&self, current_epoch: u64) -> bool {
+        let claims_settled = self.total_winners == 0
+            || self.claimed_winners >= self.total_winners
+            || (self.unclaimed_swept == 1 && !self.is_claim_window_open(current_epoch));
+        let fee_settled = !self.owes_fee();
+
+        self.status == GameStatus::Resolved as u8
+            && !self.is_frozen()
+            && claims_settled
+            && fee_settled
+            && self.house_seed_lamports == 0
+    }
+
+    /// True if this game paid out and ended its chain (no carry-over).
+    pub fn ends_chain(&self) -> bool {
+        self.status == GameStatus::Resolved as u8
+            && self.rollover_reason == RolloverReason::None as u8
+            && self.total_winners > 0
+    }
+
+    /// Whether a prediction on this game's chain no longer needs its account.
+    ///
+    /// Requires the chain to have ended, and then either the prediction was
+    /// claimed, it did not cover the winning number, or its claim window closed.
+    pub fn allows_prediction_close(
+        &self,
+        has_claimed: bool,
+        covers_winner: bool,
+        current_epoch: u64,
+    ) -> bool {
+        self.ends_chain()
+            && (has_claimed || !covers_winner || !self.is_claim_window_open(current_epoch))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use borsh::BorshSerialize;
+    use crate::utils::bitmap::is_claimed;
 
-    #[test]
-    fn test_resolved_game_max_size() {
-        let bitmap_len = ResolvedGame::MAX_BITMAP_LEN;
-
-        let game = ResolvedGame {
+    fn game_with_bitmap(bitmap_len: usize) -> ResolvedGame {
+        ResolvedGame {
             // core + status
             epoch: 0,
             tier: 0,
@@ -170,10 +435,357 @@ mod tests {
             rollover_reason: 0,
             secondary_rollover_number: 0,
             fee_bps: 0,
-            _reserved: [0u8; 12],
-        };
+            merkle_scheme: 0,
+            claim_deadline_epoch: 0,
+            unclaimed_swept: 0,
+            dispute_until_slot: 0,
+            fee_collectable_slot: 0,
+            fee_collected: 0,
+            house_seed_lamports: 0,
+            results_uri_ext_len: 0,
+            frozen: 0,
+        }
+    }
+
+    #[test]
+    fn test_resolved_game_max_size() {
+        let bitmap_len = ResolvedGame::MAX_BITMAP_LEN;
+        assert_eq!(bitmap_len, ResolvedGame::bitmap_len(ResolvedGame::MAX_WINNERS_PER_GAME as u32));
+
+        let game = game_with_bitmap(bitmap_len);
 
         let bytes = game.try_to_vec().unwrap();
         assert_eq!(bytes.len(), ResolvedGame::SIZE);
     }
+
+    #[test]
+    fn test_resolved_game_size_for_winners() {
+        assert_eq!(ResolvedGame::size_for_winners(0), ResolvedGame::BASE_SIZE);
+        assert_eq!(ResolvedGame::size_for_winners(1), ResolvedGame::BASE_SIZE + 1);
+        assert_eq!(ResolvedGame::size_for_winners(8), ResolvedGame::BASE_SIZE + 1);
+        assert_eq!(ResolvedGame::size_for_winners(100), ResolvedGame::BASE_SIZE + 13);
+        assert_eq!(
+            ResolvedGame::size_for_winners(ResolvedGame::MAX_WINNERS_PER_GAME as u32),
+            ResolvedGame::SIZE
+        );
+    }
+
+    #[test]
+    fn claim_window_closes_after_deadline_or_sweep() {
+        let mut game = game_with_bitmap(1);
+        assert!(game.is_claim_window_open(u64::MAX));
+
+        game.claim_deadline_epoch = 730;
+        assert!(game.is_claim_window_open(730));
+        assert!(!game.is_claim_window_open(731));
+
+        game.unclaimed_swept = 1;
+        assert!(!game.is_claim_window_open(700));
+    }
+
+    #[test]
+    fn dispute_window_ends_at_slot() {
+        let mut game = game_with_bitmap(1);
+        assert!(!game.is_in_dispute_window(0));
+
+        game.dispute_until_slot = 1_000;
+        assert!(game.is_in_dispute_window(999));
+        assert!(!game.is_in_dispute_window(1_000));
+    }
+
+    #[test]
+    fn bet_counts_fail_past_u32() {
+        assert_eq!(ResolvedGame::bet_count(7).unwrap(), 7);
+        assert_eq!(ResolvedGame::bet_count(u32::MAX as u64).unwrap(), u32::MAX);
+        assert_eq!(
+            ResolvedGame::bet_count(u32::MAX as u64 + 1).unwrap_err(),
+            error!(IC42NErrorCode::MathOverflow)
+        );
+        assert_eq!(
+            ResolvedGame::bet_count(u64::MAX).unwrap_err(),
+            error!(IC42NErrorCode::MathOverflow)
+        );
+    }
+
+    #[test]
+    fn fee_collects_once_after_delay() {
+        let mut game = game_with_bitmap(1);
+        game.status = GameStatus::Resolved as u8;
+        game.version = ResolvedGame::DEFERRED_FEE_VERSION;
+        game.total_winners = 2;
+        game.protocol_fee_lamports = 500;
+        game.dispute_until_slot = 100;
+        game.fee_collectable_slot = 200;
+
+        assert!(!game.can_collect_fee(99));
+        assert!(!game.can_collect_fee(199));
+        assert!(game.can_collect_fee(200));
+
+        game.fee_collected = 1;
+        assert!(!game.can_collect_fee(200));
+
+        // Legacy games already paid the fee at resolve
+        game.fee_collected = 0;
+        game.version = 2;
+        assert!(!game.can_collect_fee(200));
+    }
+
+    #[test]
+    fn rollover_fee_is_never_owed() {
+        // A rollover stored with a fee (as rollovers did before carrying the
+        // fee as 0): the whole pot moved on, so nothing is collectable
+        let mut game = game_with_bitmap(0);
+        game.status = GameStatus::Resolved as u8;
+        game.version = ResolvedGame::DEFERRED_FEE_VERSION;
+        game.rollover_reason = RolloverReason::NoWinners as u8;
+        game.protocol_fee_lamports = 450;
+
+        assert!(!game.owes_fee());
+        assert!(!game.can_collect_fee(u64::MAX));
+        assert!(game.is_closable(0));
+    }
+
+    #[test]
+    fn record_claim_writes_all_or_nothing() {
+        let mut game = game_with_bitmap(1);
+        game.total_winners = 2;
+        game.net_prize_pool = 1_000;
+
+        game.record_claim(1, 600).unwrap();
+        assert!(is_claimed(&game.claimed_bitmap, 1));
+        assert_eq!(game.claimed_lamports, 600);
+        assert_eq!(game.claimed_winners, 1);
+        game.assert_claim_accounting().unwrap();
+
+        // A failing claim leaves the bitmap and totals untouched
+        let before = game.try_to_vec().unwrap();
+        assert_eq!(
+            game.record_claim(0, 401).unwrap_err(),
+            error!(IC42NErrorCode::ClaimAccountingOverflow)
+        );
+        assert_eq!(game.try_to_vec().unwrap(), before);
+        assert!(!is_claimed(&game.claimed_bitmap, 0));
+
+        game.record_claim(0, 400).unwrap();
+        assert_eq!(game.claimed_lamports, game.net_prize_pool);
+        assert_eq!(game.claimed_winners, game.total_winners);
+    }
+
+    #[test]
+    fn frozen_game_rejects_claims_until_unfrozen() {
+        let mut game = game_with_bitmap(1);
+        game.status = GameStatus::Resolved as u8;
+        game.total_winners = 1;
+        game.net_prize_pool = 1_000;
+        game.assert_not_frozen().unwrap();
+
+        game.frozen = 1;
+        assert_eq!(
+            game.assert_not_frozen().unwrap_err(),
+            error!(IC42NErrorCode::GameFrozen)
+        );
+
+        game.frozen = 0;
+        game.assert_not_frozen().unwrap();
+        game.record_claim(0, 1_000).unwrap();
+        assert_eq!(game.claimed_winners, 1);
+    }
+
+    #[test]
+    fn frozen_game_holds_fee_and_close() {
+        let mut game = game_with_bitmap(1);
+        game.status = GameStatus::Resolved as u8;
+        game.version = ResolvedGame::DEFERRED_FEE_VERSION;
+        game.total_winners = 1;
+        game.claimed_winners = 1;
+        game.protocol_fee_lamports = 500;
+        game.fee_collectable_slot = 200;
+
+        game.frozen = 1;
+        assert!(!game.can_collect_fee(200));
+        game.fee_collected = 1;
+        assert!(!game.is_closable(0));
+
+        game.frozen = 0;
+        assert!(game.is_closable(0));
+        game.fee_collected = 0;
+        assert!(game.can_collect_fee(200));
+    }
+
+    #[test]
+    fn unfreeze_only_pushes_the_deadline_out() {
+        let mut game = game_with_bitmap(1);
+        game.total_winners = 1;
+        game.claim_deadline_epoch = 40;
+
+        game.extend_claim_deadline(30);
+        assert_eq!(game.claim_deadline_epoch, 40);
+        game.extend_claim_deadline(55);
+        assert_eq!(game.claim_deadline_epoch, 55);
+
+        // No deadline stays no deadline
+        game.claim_deadline_epoch = 0;
+        game.extend_claim_deadline(55);
+        assert_eq!(game.claim_deadline_epoch, 0);
+    }
+
+    #[test]
+    fn outstanding_covers_pool_fee_and_seed() {
+        let mut game = game_with_bitmap(1);
+        game.version = ResolvedGame::DEFERRED_FEE_VERSION;
+        game.total_winners = 2;
+        game.net_prize_pool = 9_000;
+        game.claimed_lamports = 4_000;
+        game.protocol_fee_lamports = 500;
+        game.house_seed_lamports = 50;
+
+        // Nothing is owed before the result is committed
+        assert_eq!(game.outstanding_lamports(), 0);
+
+        game.status = GameStatus::Resolved as u8;
+        game.resolved_at = 1;
+        assert_eq!(game.outstanding_lamports(), 5_550);
+
+        game.fee_collected = 1;
+        game.unclaimed_swept = 1;
+        assert_eq!(game.outstanding_lamports(), 50);
+
+        // Reverted games keep their pool owed
+        game.status = GameStatus::Processing as u8;
+        game.unclaimed_swept = 0;
+        assert_eq!(game.outstanding_lamports(), 5_050);
+    }
+
+    #[test]
+    fn batch_close_skips_missing_tier_and_open_games() {
+        let resolved = |winners: u32, claimed: u32| {
+            let mut game = game_with_bitmap(1);
+            game.status = GameStatus::Resolved as u8;
+            game.version = ResolvedGame::DEFERRED_FEE_VERSION;
+            game.total_winners = winners;
+            game.claimed_winners = claimed;
+            game
+        };
+
+        // Tier 1 fully claimed, tier 2 never initialized, tier 3 a rollover
+        let epoch_games = [Some(resolved(3, 3)), None, Some(resolved(0, 0))];
+        let closed = epoch_games.iter().flatten().filter(|g| g.is_closable(50)).count();
+        assert_eq!(closed, 2);
+
+        // Unclaimed winners keep a game open until swept
+        let mut game = resolved(3, 1);
+        game.claim_deadline_epoch = 40;
+        assert!(!game.is_closable(50));
+        game.unclaimed_swept = 1;
+        assert!(game.is_closable(50));
+
+        // Owed fee or house seed also keep it open
+        game.protocol_fee_lamports = 10;
+        assert!(!game.is_closable(50));
+        game.fee_collected = 1;
+        game.house_seed_lamports = 5;
+        assert!(!game.is_closable(50));
+    }
+
+    #[test]
+    fn close_waits_for_claims_or_window() {
+        let mut game = game_with_bitmap(1);
+        game.status = GameStatus::Resolved as u8;
+        game.total_winners = 3;
+        game.claim_deadline_epoch = 40;
+
+        // Fully claimed: closable at any time
+        game.claimed_winners = 3;
+        assert!(game.is_closable(30));
+
+        // Partially claimed: open inside the window and until swept after it
+        game.claimed_winners = 1;
+        assert!(!game.is_closable(30));
+        assert!(!game.is_closable(41));
+        game.unclaimed_swept = 1;
+        assert!(game.is_closable(41));
+
+        // Unfinished and reverted games can't be closed (and re-initialized)
+        game.status = GameStatus::Processing as u8;
+        assert!(!game.is_closable(41));
+        game.resolved_at = 1_000;
+        assert!(game.is_reverted());
+        assert!(!game.is_closable(41));
+    }
+
+    #[test]
+    fn prediction_close_requires_final_game() {
+        let mut game = game_with_bitmap(1);
+        game.status = GameStatus::Resolved as u8;
+        game.total_winners = 1;
+        game.claim_deadline_epoch = 730;
+
+        // Claimed, lost, or expired
+        assert!(game.allows_prediction_close(true, true, 700));
+        assert!(game.allows_prediction_close(false, false, 700));
+        assert!(game.allows_prediction_close(false, true, 731));
+
+        // Unclaimed winner inside the window keeps the account
+        assert!(!game.allows_prediction_close(false, true, 700));
+
+        // Rolled-over games keep the chain (and its predictions) alive
+        game.rollover_reason = RolloverReason::NoWinners as u8;
+        assert!(!game.allows_prediction_close(true, false, 700));
+    }
+
+    #[test]
+    fn claim_accounting_allows_exact_totals_only() {
+        let mut game = game_with_bitmap(1);
+        game.total_winners = 2;
+        game.net_prize_pool = 1_000;
+
+        // Two claims that exactly drain the pool
+        for amount in [400, 600] {
+            game.claimed_lamports += amount;
+            game.claimed_winners += 1;
+            assert!(game.assert_claim_accounting().is_ok());
+        }
+
+        game.claimed_lamports += 1;
+        assert_eq!(
+            game.assert_claim_accounting().unwrap_err(),
+            IC42NErrorCode::ClaimAccountingOverflow.into()
+        );
+
+        game.claimed_lamports = 1_000;
+        game.claimed_winners = 3;
+        assert_eq!(
+            game.assert_claim_accounting().unwrap_err(),
+            IC42NErrorCode::ClaimAccountingOverflow.into()
+        );
+    }
+
+    #[test]
+    fn results_uri_tail_round_trips_after_struct() {
+        let total_winners = 20;
+        let ext = b"/part-2?sig=abcdef";
+
+        let mut game = game_with_bitmap(ResolvedGame::bitmap_len(total_winners));
+        game.total_winners = total_winners;
+        game.results_uri = [b'a'; 128];
+        game.results_uri_ext_len = ext.len() as u8;
+
+        // Account as sized by `complete_resolve_game`'s realloc
+        let mut data = vec![0u8; 8];
+        data.extend(game.try_to_vec().unwrap());
+        assert_eq!(data.len(), 8 + ResolvedGame::size_for_winners(total_winners));
+        data.resize(data.len() + ext.len(), 0);
+
+        ResolvedGame::write_results_uri_ext(&mut data, total_winners, ext).unwrap();
+        let stored = ResolvedGame::deserialize(&mut &data[8..]).unwrap();
+
+        assert_eq!(stored.results_uri_ext(&data), ext);
+        let full = stored.full_results_uri(&data);
+        assert_eq!(full.len(), 128 + ext.len());
+        assert!(full.ends_with(ext));
+
+        // No room left for the tail
+        let short = data.len() - 1;
+        assert!(ResolvedGame::write_results_uri_ext(&mut data[..short], total_winners, ext).is_err());
+    }
 }
\ No newline at end of file
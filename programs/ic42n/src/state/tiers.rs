@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub struct TierSettings {
@@ -20,7 +21,41 @@ pub struct TierSettings {
     /// Number of tickets to award per selected recipient.
     pub tickets_per_recipient: u8,
 
-    pub _reserved: [u8; 10],
+    /// Max live lamports any single number may hold in this tier (0 disables).
+    pub max_lamports_per_number: u64,
+
+    /// 1 = maintain a per-epoch `Leaderboard` for this tier, 0 = disabled.
+    pub leaderboard_enabled: u8,
+
+    /// 1 = betting paused for this tier only, 0 = follows the global `pause_bet`.
+    pub paused: u8,
+}
+
+/// Fee schedule for one tier, stored in `Config::tier_fees` (same order as
+/// `Config::tiers`).
+///
+/// Kept out of `TierSettings` because the tiers array sits in the middle of
+/// the original Config layout and can't grow in place.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TierFeeSettings {
+    /// 1 = use this schedule, 0 = fall back to the global Config fees.
+    pub enabled: u8,
+    pub base_fee_bps: u16,
+    pub min_fee_bps: u16,
+    pub rollover_fee_step_bps: u16,
+}
+
+impl TierFeeSettings {
+    pub const SIZE: usize =
+        1  // enabled
+            + 2  // base_fee_bps
+            + 2  // min_fee_bps
+            + 2; // rollover_fee_step_bps
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled != 0
+    }
 }
 
 impl TierSettings {
@@ -33,15 +68,34 @@ impl TierSettings {
             + 2  // ticket_reward_bps
             + 2  // ticket_reward_max
             + 1  // tickets_per_recipient
-            + 10; // _reserved
+            + 8  // max_lamports_per_number
+            + 1  // leaderboard_enabled
+            + 1; // paused
 
     #[inline]
     pub fn is_active(&self) -> bool {
         self.active != 0
     }
 
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused != 0
+    }
+
+    #[inline]
+    pub fn is_leaderboard_enabled(&self) -> bool {
+        self.leaderboard_enabled != 0
+    }
+
     #[inline]
     pub fn is_valid_bet(&self, lamports: u64) -> bool {
         lamports >= self.min_bet_lamports && lamports <= self.max_bet_lamports
     }
+
+    /// A tier can only be activated with a bet range and a positive curve.
+    pub fn check_activatable(&self) -> Result<()> {
+        require!(self.max_bet_lamports > 0, IC42NErrorCode::InactiveTier);
+        require!(self.curve_factor > 0.0, IC42NErrorCode::CannotActivateWithoutCurve);
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::utils::transfers::min_treasury_balance;
 
 /// ---------------------------------------------------------------------------
 /// Treasury
@@ -40,13 +42,28 @@ pub struct Treasury {
     /// Versioning for future migrations.
     pub version: u8,
 
+    /// Total lamports refunded back to players (e.g. coverage reductions).
+    pub total_refunded_lamports: u64,
+
+    /// Total lamports of expired, unclaimed prizes swept to the fee vault.
+    pub total_swept_lamports: u64,
+
+    /// Lamports currently owed out of this treasury: live pots, unclaimed
+    /// prizes, uncollected fees and house seeds. Raised when lamports come in
+    /// for a player or the house, lowered when they are paid out.
+    /// Only exact from `OBLIGATIONS_VERSION` on (see `upgrade_treasury`).
+    pub obligations_lamports: u64,
+
     /// Padding / reserved bytes for future use (config, extra flags).
-    pub _reserved: [u8; 32],
+    pub _reserved: [u8; 8],
 }
 
 impl Treasury {
 
     pub const SEED: &'static [u8] = b"treasury";
+
+    /// First `version` that maintains `obligations_lamports`.
+    pub const OBLIGATIONS_VERSION: u8 = 2;
     pub const SIZE: usize =
         32 + // authority
             1  + // tier
@@ -55,9 +72,49 @@ impl Treasury {
             8  + // total_out_lamports
             8  + // total_fees_withdrawn
             1  + // version
-            32;  // reserved
+            8  + // total_refunded_lamports
+            8  + // total_swept_lamports
+            8  + // obligations_lamports
+            8;   // reserved
     // When allocating:
     // space = 8 (discriminator) + Treasury::SIZE
+
+    pub fn tracks_obligations(&self) -> bool {
+        self.version >= Self::OBLIGATIONS_VERSION
+    }
+
+    pub fn add_obligation(&mut self, lamports: u64) -> Result<()> {
+        self.obligations_lamports = self
+            .obligations_lamports
+            .checked_add(lamports)
+            .ok_or(IC42NErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Saturates: a payout must never fail on the counter, and an upgraded
+    /// treasury starts from an over-estimate anyway.
+    pub fn release_obligation(&mut self, lamports: u64) {
+        self.obligations_lamports = self.obligations_lamports.saturating_sub(lamports);
+    }
+
+    /// `obligations_lamports`, or the whole balance above the rent-exempt
+    /// reserve while the treasury doesn't track them yet.
+    pub fn owed_lamports(&self, balance: u64) -> u64 {
+        if self.tracks_obligations() {
+            self.obligations_lamports
+        } else {
+            balance.saturating_sub(min_treasury_balance())
+        }
+    }
+
+    /// Starts tracking obligations on a treasury created before
+    /// `OBLIGATIONS_VERSION`. Every lamport above the rent-exempt reserve is
+    /// presumed owed, so dust already held stays put; only dust arriving
+    /// afterwards becomes sweepable.
+    pub fn start_tracking_obligations(&mut self, balance: u64) {
+        self.obligations_lamports = balance.saturating_sub(min_treasury_balance());
+        self.version = Self::OBLIGATIONS_VERSION;
+    }
 }
 
 
@@ -77,7 +134,10 @@ mod tests {
             total_out_lamports: 0,
             total_fees_withdrawn: 0,
             version: 0,
-            _reserved: [0u8; 32],
+            total_refunded_lamports: 0,
+            total_swept_lamports: 0,
+            obligations_lamports: 0,
+            _reserved: [0u8; 8],
         };
 
         let bytes = t.try_to_vec().unwrap();
@@ -90,4 +150,31 @@ mod tests {
             bytes.len()
         );
     }
+
+    #[test]
+    fn obligations_follow_lamports_in_and_out() {
+        let mut t = Treasury::try_from_slice(&[0u8; Treasury::SIZE]).unwrap();
+        t.version = 1;
+        let reserve = min_treasury_balance();
+
+        // Upgrading presumes everything above the reserve is owed
+        let balance = reserve + 5_000;
+        assert_eq!(t.owed_lamports(balance), 5_000);
+        t.start_tracking_obligations(balance);
+        assert!(t.tracks_obligations());
+        assert_eq!(t.owed_lamports(balance + 1_000), 5_000);
+
+        // A bet in, a claim and a fee out
+        t.add_obligation(2_000).unwrap();
+        t.release_obligation(1_500);
+        t.release_obligation(300);
+        assert_eq!(t.obligations_lamports, 5_200);
+
+        // Releasing more than is tracked never fails a payout
+        t.release_obligation(10_000);
+        assert_eq!(t.obligations_lamports, 0);
+
+        t.obligations_lamports = u64::MAX;
+        assert!(t.add_obligation(1).is_err());
+    }
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::msg;
+use anchor_lang::require;
 use anchor_lang::solana_program::clock::Clock;
 use anchor_lang::solana_program::sysvar::epoch_schedule::EpochSchedule;
 use anchor_lang::solana_program::sysvar::Sysvar;
+use crate::constants::EPOCH_SETTLE_SLOTS;
+use crate::errors::IC42NErrorCode;
 use crate::state::tiers::TierSettings;
 
 
@@ -10,11 +13,40 @@ pub fn is_amount_in_tier(amount: u64, tier: &TierSettings) -> bool {
     amount >= tier.min_bet_lamports && amount <= tier.max_bet_lamports
 }
 
+/// Returns the per-number stake after adding `additional` to an existing
+/// prediction, if it fits the tier's *current* bounds.
+///
+/// Tier bounds policy when a tier is reconfigured:
+/// - Existing positions are grandfathered: they stay valid and keep working
+///   for claims, refunds and coverage changes, even if now out of range.
+/// - An increase is new money, so the resulting per-number stake must satisfy
+///   the current [min, max]; otherwise `BetOutOfTierRange`.
+pub fn increased_stake_in_tier(
+    current_per_number: u64,
+    additional: u64,
+    tier: &TierSettings,
+) -> anchor_lang::Result<u64> {
+    let total = current_per_number
+        .checked_add(additional)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+    require!(is_amount_in_tier(total, tier), IC42NErrorCode::BetOutOfTierRange);
+    Ok(total)
+}
+
+/// Returns true if a prediction staking `total_lamports` across all its
+/// numbers fits the tier's total exposure cap (0 disables the cap).
+pub fn is_within_exposure_cap(total_lamports: u64, cap: u64) -> bool {
+    cap == 0 || total_lamports <= cap
+}
+
 /// Returns true if betting is still open given a minimum remaining-slots cutoff.
 ///
 /// This is used to prevent bets near the end of an epoch, where off-chain
-/// resolution may be imminent.
-pub fn is_betting_still_open(min_slots_cutoff: u64) -> bool {
+/// resolution may be imminent. With `strict` (`Config.strict_cutoff`), a
+/// clock/schedule mismatch closes betting instead of allowing it. The
+/// per-call cutoff trace is only logged with `verbose`
+/// (`Config.verbose_logging`).
+pub fn is_betting_still_open(min_slots_cutoff: u64, strict: bool, verbose: bool) -> bool {
 
     let Ok(clock) = Clock::get() else {
         // If sysvars are unavailable (unexpected), fail closed.
@@ -26,48 +58,320 @@ pub fn is_betting_still_open(min_slots_cutoff: u64) -> bool {
         return false;
     };
 
-    let current_slot = clock.slot;
+    let open = is_betting_open_at(min_slots_cutoff, clock.epoch, clock.slot, &schedule, strict);
+    if let Some(line) =
+        cutoff_trace(verbose, min_slots_cutoff, clock.epoch, clock.slot, &schedule, open)
+    {
+        msg!("{}", line);
+    }
+    open
+}
+
+/// Trace line for a cutoff check, or `None` unless `verbose`, so the string is
+/// never built (nor logged) on the quiet path.
+pub fn cutoff_trace(
+    verbose: bool,
+    min_slots_cutoff: u64,
+    clock_epoch: u64,
+    current_slot: u64,
+    schedule: &EpochSchedule,
+    open: bool,
+) -> Option<String> {
+    if !verbose {
+        return None;
+    }
+
+    let last_slot = schedule.get_last_slot_in_epoch(clock_epoch);
+    Some(format!(
+        "Betting cutoff check: epoch={} slot={} slots_remaining={} cutoff={} -> {}",
+        clock_epoch,
+        current_slot,
+        last_slot.saturating_sub(current_slot),
+        min_slots_cutoff,
+        if open { "ALLOWED" } else { "CLOSED" }
+    ))
+}
+
+/// Pure core of `is_betting_still_open`.
+///
+/// The epoch's slot range is derived from `clock_epoch` itself, so the
+/// cutoff is measured against the epoch the clock reports. If `current_slot`
+/// falls outside that range the schedule disagrees with the clock (seen on
+/// devnet, whose first epochs are shorter): strict mode fails closed, while
+/// the lenient fallback keeps the old allow-on-mismatch behaviour.
+pub fn is_betting_open_at(
+    min_slots_cutoff: u64,
+    clock_epoch: u64,
+    current_slot: u64,
+    schedule: &EpochSchedule,
+    strict: bool,
+) -> bool {
+    let first_slot = schedule.get_first_slot_in_epoch(clock_epoch);
+    let slots_in_epoch = schedule.get_slots_in_epoch(clock_epoch);
+    let last_slot = first_slot.saturating_add(slots_in_epoch).saturating_sub(1);
+
+    if current_slot < first_slot || current_slot > last_slot {
+        msg!(
+            "Epoch mismatch: clock.epoch={} spans slots {}..={} but slot={}. strict={}",
+            clock_epoch,
+            first_slot,
+            last_slot,
+            current_slot,
+            strict
+        );
+        return !strict;
+    }
+
+    let slots_remaining = last_slot - current_slot;
+    slots_remaining > min_slots_cutoff
+}
+
+/// Returns true once `epoch` is safely over: the clock has moved past it AND
+/// the current slot is at least `EPOCH_SETTLE_SLOTS` beyond its last slot.
+///
+/// Guards resolution against the brief boundary where the epoch number has
+/// advanced but the schedule disagrees. Fails closed if sysvars are unavailable.
+pub fn epoch_fully_settled(epoch: u64) -> bool {
+    let Ok(clock) = Clock::get() else {
+        return false;
+    };
+
+    let Ok(schedule) = EpochSchedule::get() else {
+        return false;
+    };
+
+    is_epoch_settled(epoch, clock.epoch, clock.slot, &schedule)
+}
+
+/// Pure core of `epoch_fully_settled`.
+pub fn is_epoch_settled(
+    epoch: u64,
+    clock_epoch: u64,
+    current_slot: u64,
+    schedule: &EpochSchedule,
+) -> bool {
+    if clock_epoch <= epoch {
+        return false;
+    }
+
+    // Devnet Configuration: same mismatch as in `is_betting_still_open`.
+    // The schedule can't be trusted for slot math, so fall back to the
+    // clock's epoch number alone.
     let epoch_from_slot = schedule.get_epoch(current_slot);
-    let slots_per_epoch = schedule.slots_per_epoch;
+    if epoch_from_slot != clock_epoch {
+        msg!(
+            "Epoch mismatch: clock.epoch={} vs schedule.get_epoch(slot)={}. \
+             Falling back to clock.epoch for settlement.",
+            clock_epoch,
+            epoch_from_slot
+        );
+        return true;
+    }
+
+    let last_slot = schedule.get_last_slot_in_epoch(epoch);
+    current_slot >= last_slot.saturating_add(EPOCH_SETTLE_SLOTS)
+}
+
+/// Returns true if `rng_slot` lies inside `epoch` and, when `max_age_slots`
+/// is non-zero, no more than `max_age_slots` before the epoch's last slot.
+///
+/// Skips the check (returns true) on a clock/schedule mismatch, like the
+/// other epoch helpers. Fails closed if sysvars are unavailable.
+pub fn rng_slot_fresh(epoch: u64, rng_slot: u64, max_age_slots: u64) -> bool {
+    let Ok(clock) = Clock::get() else {
+        return false;
+    };
+
+    let Ok(schedule) = EpochSchedule::get() else {
+        return false;
+    };
 
-    // Devnet Configuration: Devnet was deliberately configured with a shorter
-    // initial epoch length, reportedly around 8,192 slots, to allow for
-    // faster epoch transitions. If they do not match, then we skip the cutoff for devnet.
-    // We must test and confirm once on the mainnet.
+    // Devnet Configuration: same mismatch as in `is_betting_still_open`.
+    let epoch_from_slot = schedule.get_epoch(clock.slot);
     if epoch_from_slot != clock.epoch {
         msg!(
             "Epoch mismatch: clock.epoch={} vs schedule.get_epoch(slot)={}. \
-             slots_per_epoch={}.
-             Skipping cutoff and allowing bet.",
+             Skipping RNG slot freshness check.",
             clock.epoch,
-            epoch_from_slot,
-            slots_per_epoch
+            epoch_from_slot
         );
         return true;
     }
-    
-    // Compute timing within this epoch (now that we know it's consistent)
-    let first_slot = schedule.get_first_slot_in_epoch(epoch_from_slot);
-    let slots_in_epoch = schedule.get_slots_in_epoch(epoch_from_slot);
-    let last_slot = first_slot + slots_in_epoch - 1;
-    let slots_remaining = last_slot.saturating_sub(current_slot);
-
-    // ── Essential logging only ─────────────────────────────
-    msg!(
-        "Betting cutoff check: epoch={} slot={} slots_in_epoch={} slots_remaining={} cutoff={}",
-        epoch_from_slot,
-        current_slot,
-        slots_in_epoch,
-        slots_remaining,
-        min_slots_cutoff
-    );
 
-    let open = slots_remaining > min_slots_cutoff;
-    if open {
-        msg!("Betting ALLOWED");
-    } else {
-        msg!("Betting CLOSED");
+    is_rng_slot_fresh(epoch, rng_slot, max_age_slots, &schedule)
+}
+
+/// Pure core of `rng_slot_fresh`.
+pub fn is_rng_slot_fresh(
+    epoch: u64,
+    rng_slot: u64,
+    max_age_slots: u64,
+    schedule: &EpochSchedule,
+) -> bool {
+    let first_slot = schedule.get_first_slot_in_epoch(epoch);
+    let last_slot = schedule.get_last_slot_in_epoch(epoch);
+
+    if rng_slot < first_slot || rng_slot > last_slot {
+        return false;
     }
-    open
+
+    max_age_slots == 0 || last_slot - rng_slot <= max_age_slots
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLOTS: u64 = 432_000;
+
+    fn tier_with_bounds(min_bet_lamports: u64, max_bet_lamports: u64) -> TierSettings {
+        TierSettings {
+            tier_id: 1,
+            active: 1,
+            min_bet_lamports,
+            max_bet_lamports,
+            curve_factor: 1.0,
+            ticket_reward_bps: 0,
+            ticket_reward_max: 0,
+            tickets_per_recipient: 0,
+            max_lamports_per_number: 0,
+            leaderboard_enabled: 0,
+            paused: 0,
+        }
+    }
+
+    #[test]
+    fn raised_min_rejects_small_increases() {
+        // Bet placed at the old 0.01 SOL minimum, then the min is raised to 0.1 SOL
+        let existing = 10_000_000;
+        let tier = tier_with_bounds(100_000_000, 1_000_000_000);
+
+        assert_eq!(
+            increased_stake_in_tier(existing, 1_000_000, &tier).unwrap_err(),
+            IC42NErrorCode::BetOutOfTierRange.into()
+        );
+        assert_eq!(increased_stake_in_tier(existing, 90_000_000, &tier).unwrap(), 100_000_000);
+    }
+
+    #[test]
+    fn lowered_max_freezes_existing_position() {
+        let existing = 500_000_000;
+        let tier = tier_with_bounds(10_000_000, 100_000_000);
+
+        assert_eq!(
+            increased_stake_in_tier(existing, 1, &tier).unwrap_err(),
+            IC42NErrorCode::BetOutOfTierRange.into()
+        );
+        assert_eq!(
+            increased_stake_in_tier(u64::MAX, 1, &tier).unwrap_err(),
+            IC42NErrorCode::MathOverflow.into()
+        );
+    }
+
+    #[test]
+    fn exposure_cap_bounds_total_not_per_number() {
+        let tier = tier_with_bounds(10_000_000, 100_000_000);
+        let cap = 400_000_000;
+
+        // Single number at the tier max is far below the cap
+        assert!(is_amount_in_tier(100_000_000, &tier));
+        assert!(is_within_exposure_cap(100_000_000, cap));
+
+        // 8 numbers: exactly at the cap passes, one lamport per number more does not
+        assert!(is_within_exposure_cap(50_000_000 * 8, cap));
+        assert!(!is_within_exposure_cap(50_000_001 * 8, cap));
+        assert!(!is_within_exposure_cap(100_000_000 * 8, cap));
+
+        // 0 disables the cap
+        assert!(is_within_exposure_cap(u64::MAX, 0));
+    }
+
+    #[test]
+    fn betting_cutoff_uses_clock_epoch() {
+        let schedule = EpochSchedule::without_warmup();
+        let last = schedule.get_last_slot_in_epoch(10);
+        let cutoff = 100;
+
+        assert!(is_betting_open_at(cutoff, 10, last - cutoff - 1, &schedule, true));
+        assert!(!is_betting_open_at(cutoff, 10, last - cutoff, &schedule, true));
+        assert!(!is_betting_open_at(cutoff, 10, last, &schedule, false));
+    }
+
+    #[test]
+    fn cutoff_trace_only_when_verbose() {
+        let schedule = EpochSchedule::without_warmup();
+        let slot = schedule.get_last_slot_in_epoch(10) - 500;
+
+        assert_eq!(cutoff_trace(false, 100, 10, slot, &schedule, true), None);
+
+        let line = cutoff_trace(true, 100, 10, slot, &schedule, true).unwrap();
+        assert!(line.contains("slots_remaining=500"));
+        assert!(line.ends_with("ALLOWED"));
+        assert!(cutoff_trace(true, 600, 10, slot, &schedule, false).unwrap().ends_with("CLOSED"));
+    }
+
+    #[test]
+    fn betting_mismatch_respects_strict_cutoff() {
+        let schedule = EpochSchedule::without_warmup();
+
+        // Clock says epoch 10, but the slot belongs to epoch 11 per the schedule
+        let slot = schedule.get_first_slot_in_epoch(11);
+        assert!(!is_betting_open_at(0, 10, slot, &schedule, true));
+        assert!(is_betting_open_at(0, 10, slot, &schedule, false));
+    }
+
+    #[test]
+    fn epoch_settles_only_after_margin() {
+        let schedule = EpochSchedule::without_warmup();
+        let last_slot_of_10 = schedule.get_last_slot_in_epoch(10);
+        assert_eq!(last_slot_of_10, 11 * SLOTS - 1);
+
+        // Still inside epoch 10
+        assert!(!is_epoch_settled(10, 10, last_slot_of_10, &schedule));
+
+        // Epoch 11 started but within the settle margin
+        assert!(!is_epoch_settled(10, 11, last_slot_of_10 + 1, &schedule));
+        assert!(!is_epoch_settled(10, 11, last_slot_of_10 + EPOCH_SETTLE_SLOTS - 1, &schedule));
+
+        assert!(is_epoch_settled(10, 11, last_slot_of_10 + EPOCH_SETTLE_SLOTS, &schedule));
+        assert!(is_epoch_settled(10, 12, 12 * SLOTS + 5, &schedule));
+    }
+
+    #[test]
+    fn rng_slot_must_be_inside_epoch() {
+        let schedule = EpochSchedule::without_warmup();
+        let first = schedule.get_first_slot_in_epoch(10);
+        let last = schedule.get_last_slot_in_epoch(10);
+
+        assert!(is_rng_slot_fresh(10, first, 0, &schedule));
+        assert!(is_rng_slot_fresh(10, last, 0, &schedule));
+
+        // Boundary slots of the neighbouring epochs
+        assert!(!is_rng_slot_fresh(10, first - 1, 0, &schedule));
+        assert!(!is_rng_slot_fresh(10, last + 1, 0, &schedule));
+
+        // An ancient slot is rejected outright
+        assert!(!is_rng_slot_fresh(10, 5, 0, &schedule));
+    }
+
+    #[test]
+    fn rng_slot_must_be_near_epoch_end() {
+        let schedule = EpochSchedule::without_warmup();
+        let last = schedule.get_last_slot_in_epoch(10);
+        let max_age = 150;
+
+        assert!(is_rng_slot_fresh(10, last, max_age, &schedule));
+        assert!(is_rng_slot_fresh(10, last - max_age, max_age, &schedule));
+        assert!(!is_rng_slot_fresh(10, last - max_age - 1, max_age, &schedule));
+    }
+
+    #[test]
+    fn schedule_mismatch_falls_back_to_clock_epoch() {
+        // Slot says epoch 10, clock says 12 (devnet-style disagreement)
+        let schedule = EpochSchedule::without_warmup();
+        let slot = 10 * SLOTS + 5;
+
+        assert!(is_epoch_settled(11, 12, slot, &schedule));
+        assert!(!is_epoch_settled(12, 12, slot, &schedule));
+    }
+}
\ No newline at end of file
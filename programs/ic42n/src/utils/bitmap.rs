@@ -1,3 +1,6 @@
+use anchor_lang::require;
+use crate::errors::IC42NErrorCode;
+
 /// ---------------------------------------------------------------------------
 /// Check whether a given claim index has already been claimed.
 ///
@@ -43,4 +46,70 @@ pub fn set_claimed(bitmap: &mut Vec<u8>, index: u32) {
         let mask = 1 << bit_index;
         bitmap[byte_index] |= mask;
     }
+}
+
+/// ---------------------------------------------------------------------------
+/// Validate a claim index against a game's bitmap, one invariant per error:
+///
+///   - `InvalidBitmapLen`:  bitmap isn't exactly ceil(total_winners / 8) bytes
+///   - `InvalidClaimIndex`: index >= total_winners
+///   - `BitmapOutOfBounds`: index / 8 falls outside the bitmap
+///   - `AlreadyClaimed`:    the index's bit is already set
+///
+/// With a well-formed bitmap the third check can't fire; it stays as a
+/// guard for corrupted accounts.
+/// ---------------------------------------------------------------------------
+pub fn check_claim_index(
+    bitmap: &Vec<u8>,
+    total_winners: u32,
+    index: u32,
+) -> anchor_lang::Result<()> {
+    require!(
+        bitmap.len() == (total_winners as usize).div_ceil(8),
+        IC42NErrorCode::InvalidBitmapLen
+    );
+    require!(index < total_winners, IC42NErrorCode::InvalidClaimIndex);
+    require!(
+        ((index / 8) as usize) < bitmap.len(),
+        IC42NErrorCode::BitmapOutOfBounds
+    );
+    require!(!is_claimed(bitmap, index), IC42NErrorCode::AlreadyClaimed);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::error::Error;
+
+    fn err(code: IC42NErrorCode) -> Error {
+        code.into()
+    }
+
+    #[test]
+    fn valid_unclaimed_index_passes() {
+        let bitmap = vec![0u8; 2];
+        assert!(check_claim_index(&bitmap, 10, 0).is_ok());
+        assert!(check_claim_index(&bitmap, 10, 9).is_ok());
+    }
+
+    #[test]
+    fn each_broken_invariant_has_its_own_error() {
+        let mut bitmap = vec![0u8; 2];
+
+        assert_eq!(check_claim_index(&vec![0u8; 3], 10, 0).unwrap_err(), err(IC42NErrorCode::InvalidBitmapLen));
+        assert_eq!(check_claim_index(&bitmap, 10, 10).unwrap_err(), err(IC42NErrorCode::InvalidClaimIndex));
+        assert_eq!(check_claim_index(&bitmap, 10, u32::MAX).unwrap_err(), err(IC42NErrorCode::InvalidClaimIndex));
+
+        set_claimed(&mut bitmap, 9);
+        assert_eq!(check_claim_index(&bitmap, 10, 9).unwrap_err(), err(IC42NErrorCode::AlreadyClaimed));
+    }
+
+    #[test]
+    fn out_of_range_index_is_not_reported_as_already_claimed() {
+        // `is_claimed` alone says "claimed" for anything past the bitmap
+        let bitmap = vec![0u8; 2];
+        assert!(is_claimed(&bitmap, 16));
+        assert_eq!(check_claim_index(&bitmap, 16, 16).unwrap_err(), err(IC42NErrorCode::InvalidClaimIndex));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::errors::IC42NErrorCode;
+use crate::state::{Prediction, ResolvedGame};
+use crate::utils::bitmap::check_claim_index;
+use crate::utils::merkle::{hash_claim_leaf, hash_claim_leaf_v2};
+use crate::utils::prediction::is_eligible_winner;
+
+/// Per-prediction checks shared by `claim_prediction` and
+/// `claim_multiple_winners`: the prediction is unclaimed, belongs to the
+/// game's chain, covers the winning number and (from `LEAF_INDEX_VERSION`)
+/// sits at `index`. Returns the Merkle leaf the proof must cover, hashed the
+/// way the game's tree was built.
+///
+/// Game-level checks (status, windows, pauses, freeze) stay with the caller.
+pub fn check_winning_prediction(
+    game: &ResolvedGame,
+    pred: &Prediction,
+    claimer: &Pubkey,
+    index: u32,
+    amount: u64,
+) -> Result<[u8; 32]> {
+    // Make sure values are correct.
+    pred.assert_invariant()?;
+
+    // Claim must not have been processed already
+    require!(pred.has_claimed == 0, IC42NErrorCode::AlreadyClaimed);
+    require!(
+        pred.is_in_chain(game.first_epoch_in_chain, game.epoch),
+        IC42NErrorCode::EpochMismatch
+    );
+    require!(amount > 0, IC42NErrorCode::InvalidClaimAmount);
+
+    // Bitmap integrity, index bounds, then the claimed bit
+    check_claim_index(&game.claimed_bitmap, game.total_winners, index)?;
+    if game.requires_leaf_index() {
+        require!(pred.is_leaf_index(index), IC42NErrorCode::LeafIndexMismatch);
+    }
+
+    // Sanity-check the prediction selection data: ensures the account isn't
+    // corrupted (and helps prevent weird proof binding issues).
+    let k = pred.selection_count as usize;
+    require!((1..=8).contains(&k), IC42NErrorCode::InvalidBetNumber);
+
+    let mut recomputed: u16 = 0;
+    for &n in pred.selections[..k].iter() {
+        require!((1..=9).contains(&n), IC42NErrorCode::InvalidBetNumber);
+        recomputed |= 1u16 << n;
+    }
+    require!(recomputed == pred.selections_mask, IC42NErrorCode::InvalidBetNumber);
+    require!(
+        is_eligible_winner(pred.selections_mask, game.winning_number, game.secondary_rollover_number),
+        IC42NErrorCode::IneligibleWinner
+    );
+
+    // Rebuild the Merkle leaf. Binds the proof to the exact coverage set the
+    // user had for this chain; the resolver (Merkle builder) must match this
+    // exactly.
+    let hash_leaf = if game.uses_domain_separated_merkle() {
+        hash_claim_leaf
+    } else {
+        hash_claim_leaf_v2
+    };
+    Ok(hash_leaf(game.epoch, game.tier, index, claimer, amount, pred.selections_mask))
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::constants::FEE_BPS_DENOM;
+use crate::errors::IC42NErrorCode;
+use crate::state::config::FeeRounding;
+
+/// `amount * bps / FEE_BPS_DENOM`, rounded per `rounding`.
+///
+/// Multiplies in u128 so no pot size can overflow the product; only a result
+/// that doesn't fit back into u64 (possible when `bps > FEE_BPS_DENOM`)
+/// returns `MathOverflow`. All fee amounts go through here; callers take the
+/// net as `amount - fee`, so `fee + net` stays equal to `amount` in every mode.
+pub fn bps_of(amount: u64, bps: u16, rounding: FeeRounding) -> Result<u64> {
+    let denom = FEE_BPS_DENOM as u128;
+    let product = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(IC42NErrorCode::MathOverflow)?;
+
+    let scaled = match rounding {
+        FeeRounding::Floor => product / denom,
+        FeeRounding::HalfUp => (product + denom / 2) / denom,
+        FeeRounding::Ceil => product.div_ceil(denom),
+    };
+
+    u64::try_from(scaled).map_err(|_| error!(IC42NErrorCode::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bps_of_rounds_down() {
+        assert_eq!(bps_of(10_000, 500, FeeRounding::Floor).unwrap(), 500);
+        assert_eq!(bps_of(1_999, 500, FeeRounding::Floor).unwrap(), 99);
+        assert_eq!(bps_of(19, 500, FeeRounding::Floor).unwrap(), 0);
+        assert_eq!(bps_of(0, 10_000, FeeRounding::Floor).unwrap(), 0);
+        assert_eq!(bps_of(12_345, 0, FeeRounding::Floor).unwrap(), 0);
+    }
+
+    #[test]
+    fn rounding_modes_split_an_ambiguous_pot() {
+        // 5% of 1_010 is 50.5 lamports; 5% of 1_001 is 50.05
+        assert_eq!(bps_of(1_010, 500, FeeRounding::Floor).unwrap(), 50);
+        assert_eq!(bps_of(1_010, 500, FeeRounding::HalfUp).unwrap(), 51);
+        assert_eq!(bps_of(1_010, 500, FeeRounding::Ceil).unwrap(), 51);
+
+        assert_eq!(bps_of(1_001, 500, FeeRounding::Floor).unwrap(), 50);
+        assert_eq!(bps_of(1_001, 500, FeeRounding::HalfUp).unwrap(), 50);
+        assert_eq!(bps_of(1_001, 500, FeeRounding::Ceil).unwrap(), 51);
+
+        // Exact results are the same in every mode
+        for mode in [FeeRounding::Floor, FeeRounding::HalfUp, FeeRounding::Ceil] {
+            assert_eq!(bps_of(10_000, 500, mode).unwrap(), 500);
+        }
+    }
+
+    #[test]
+    fn rounded_fee_never_exceeds_the_pot() {
+        for mode in [FeeRounding::Floor, FeeRounding::HalfUp, FeeRounding::Ceil] {
+            for pot in [0u64, 1, 7, 1_001, u64::MAX] {
+                assert!(bps_of(pot, FEE_BPS_DENOM as u16, mode).unwrap() <= pot);
+                assert!(bps_of(pot, 9_999, mode).unwrap() <= pot);
+            }
+        }
+    }
+
+    #[test]
+    fn bps_of_handles_max_values() {
+        assert_eq!(bps_of(u64::MAX, FEE_BPS_DENOM as u16, FeeRounding::Floor).unwrap(), u64::MAX);
+        assert_eq!(bps_of(u64::MAX, 5_000, FeeRounding::Floor).unwrap(), u64::MAX / 2);
+        assert_eq!(bps_of(u64::MAX, 5_000, FeeRounding::Ceil).unwrap(), u64::MAX / 2 + 1);
+        assert_eq!(
+            bps_of(u64::MAX, u16::MAX, FeeRounding::Floor).unwrap_err(),
+            error!(IC42NErrorCode::MathOverflow)
+        );
+    }
+}
\ No newline at end of file
@@ -1,11 +1,105 @@
+use anchor_lang::prelude::Pubkey;
 use sha2::{Sha256, Digest};
 
+/// Domain prefix for leaf hashes: leaf = SHA256(0x00 || data)
+pub const MERKLE_LEAF_PREFIX: u8 = 0x00;
+
+/// Domain prefix for internal nodes: node = SHA256(0x01 || left || right)
+pub const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+/// Version tag committed into every claim leaf.
+pub const CLAIM_LEAF_TAG: &[u8] = b"IC42N_V3";
+
+/// Leaf tag of games resolved before domain separation (see
+/// `ResolvedGame::uses_domain_separated_merkle`).
+pub const LEGACY_CLAIM_LEAF_TAG: &[u8] = b"IC42N_V2";
+
+/// Hash an internal node. The 0x01 prefix keeps nodes from ever colliding
+/// with a leaf (second-preimage protection).
+pub fn hash_merkle_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([MERKLE_NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Build the canonical claim leaf for a winner.
+///
+/// leaf = SHA256(0x00 || "IC42N_V3" || epoch || tier || index || wallet || amount || selections_mask)
+///
+/// All integers are little-endian. The resolver's Merkle builder must match
+/// this exactly.
+pub fn hash_claim_leaf(
+    epoch: u64,
+    tier: u8,
+    index: u32,
+    claimer: &Pubkey,
+    amount: u64,
+    selections_mask: u16,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([MERKLE_LEAF_PREFIX]);
+    hasher.update(CLAIM_LEAF_TAG);
+    hasher.update(epoch.to_le_bytes());
+    hasher.update([tier]);
+    hasher.update(index.to_le_bytes());
+    hasher.update(claimer.as_ref());
+    hasher.update(amount.to_le_bytes());
+    hasher.update(selections_mask.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Claim leaf of a game resolved before domain separation:
+///
+/// leaf = SHA256("IC42N_V2" || epoch || tier || index || wallet || amount || selections_mask)
+pub fn hash_claim_leaf_v2(
+    epoch: u64,
+    tier: u8,
+    index: u32,
+    claimer: &Pubkey,
+    amount: u64,
+    selections_mask: u16,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(LEGACY_CLAIM_LEAF_TAG);
+    hasher.update(epoch.to_le_bytes());
+    hasher.update([tier]);
+    hasher.update(index.to_le_bytes());
+    hasher.update(claimer.as_ref());
+    hasher.update(amount.to_le_bytes());
+    hasher.update(selections_mask.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Internal node of a game resolved before domain separation:
+/// SHA256(left || right).
+pub fn hash_merkle_node_v2(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Depth of a Merkle tree over `total_winners` leaves: `ceil(log2(total_winners))`.
+///
+/// Every `Indexed` proof has exactly this many siblings (odd levels are
+/// padded). `Sorted` trees are OpenZeppelin-style and may place a leaf one
+/// level higher, so their proofs can be one shorter but never longer.
+pub fn expected_proof_len(total_winners: u32) -> u32 {
+    if total_winners <= 1 {
+        0
+    } else {
+        32 - (total_winners - 1).leading_zeros()
+    }
+}
+
 /// Verify a Merkle proof using SHA-256
 ///
 /// Tree rule:
-///     parent = SHA256(left_child || right_child)
+///     parent = SHA256(0x01 || left_child || right_child)
 ///
-/// - `leaf` = 32-byte hash of the leaf (from `hash_winner_leaf`)
+/// - `leaf` = 32-byte hash of the leaf (from `hash_claim_leaf`)
 /// - `proof` = vector of sibling hashes from leaf → root
 /// - `root` = expected Merkle root (from on-chain `ResolvedGame`)
 /// - `index` = leaf index in the original sorted winner list
@@ -13,24 +107,371 @@ pub fn verify_merkle_proof(
     leaf: &[u8; 32],
     proof: &[[u8; 32]],
     root: &[u8; 32],
+    index: u32,
+) -> bool {
+    fold_indexed_proof(leaf, proof, index, hash_merkle_node) == *root
+}
+
+/// `verify_merkle_proof` for games resolved before domain separation, whose
+/// nodes are `hash_merkle_node_v2`.
+pub fn verify_merkle_proof_v2(
+    leaf: &[u8; 32],
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+    index: u32,
+) -> bool {
+    fold_indexed_proof(leaf, proof, index, hash_merkle_node_v2) == *root
+}
+
+/// Walks an index-ordered proof from `leaf` up to the root it implies.
+fn fold_indexed_proof(
+    leaf: &[u8; 32],
+    proof: &[[u8; 32]],
     mut index: u32,
+    hash_node: fn(&[u8; 32], &[u8; 32]) -> [u8; 32],
+) -> [u8; 32] {
+    let mut computed = *leaf;
+
+    for sibling in proof {
+        computed = if index % 2 == 0 {
+            hash_node(&computed, sibling)
+        } else {
+            hash_node(sibling, &computed)
+        };
+        index /= 2;
+    }
+
+    computed
+}
+
+/// Verify a Merkle proof where each pair is hashed in sorted order
+///
+/// Tree rule:
+///     parent = SHA256(0x01 || min(a, b) || max(a, b))
+///
+/// Sibling order is derived from the hashes themselves, so the proof does not
+/// depend on the leaf index (OpenZeppelin-style trees).
+pub fn verify_merkle_proof_sorted(
+    leaf: &[u8; 32],
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
 ) -> bool {
     let mut computed = *leaf;
 
     for sibling in proof {
-        let mut hasher = Sha256::new();
+        computed = if computed <= *sibling {
+            hash_merkle_node(&computed, sibling)
+        } else {
+            hash_merkle_node(sibling, &computed)
+        };
+    }
+
+    computed == *root
+}
+
+/// Verify several leaves against one root using a flag-based multiproof
+/// (OpenZeppelin `processMultiProof` convention, sorted-pair nodes).
+///
+/// - `leaves` must be in the order the multiproof consumes them
+/// - `proof_flags[i] == true` means "hash two values from the leaves/hashes
+///   queue", `false` means "hash one queued value with the next `proof` item"
+///
+/// Requires `leaves.len() + proof.len() == proof_flags.len() + 1`; malformed
+/// inputs return false.
+pub fn verify_merkle_multiproof(
+    leaves: &[[u8; 32]],
+    proof_flags: &[bool],
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+) -> bool {
+    let leaves_len = leaves.len();
+    let flags_len = proof_flags.len();
+
+    if leaves_len + proof.len() != flags_len + 1 {
+        return false;
+    }
 
-        if index % 2 == 0 {
-            hasher.update(&computed);
-            hasher.update(sibling);
+    let mut hashes: Vec<[u8; 32]> = Vec::with_capacity(flags_len);
+    let mut leaf_pos = 0usize;
+    let mut hash_pos = 0usize;
+    let mut proof_pos = 0usize;
+
+    for &flag in proof_flags {
+        let a = if leaf_pos < leaves_len {
+            leaf_pos += 1;
+            leaves[leaf_pos - 1]
+        } else if hash_pos < hashes.len() {
+            hash_pos += 1;
+            hashes[hash_pos - 1]
         } else {
-            hasher.update(sibling);
-            hasher.update(&computed);
-        }
+            return false;
+        };
 
-        computed = hasher.finalize().into();
-        index /= 2;
+        let b = if flag {
+            if leaf_pos < leaves_len {
+                leaf_pos += 1;
+                leaves[leaf_pos - 1]
+            } else if hash_pos < hashes.len() {
+                hash_pos += 1;
+                hashes[hash_pos - 1]
+            } else {
+                return false;
+            }
+        } else if proof_pos < proof.len() {
+            proof_pos += 1;
+            proof[proof_pos - 1]
+        } else {
+            return false;
+        };
+
+        hashes.push(if a <= b {
+            hash_merkle_node(&a, &b)
+        } else {
+            hash_merkle_node(&b, &a)
+        });
     }
 
+    let computed = if flags_len > 0 {
+        if proof_pos != proof.len() {
+            return false;
+        }
+        hashes[flags_len - 1]
+    } else if leaves_len > 0 {
+        leaves[0]
+    } else {
+        proof[0]
+    };
+
     computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update([MERKLE_LEAF_PREFIX]);
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+
+    fn sorted_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b { hash_merkle_node(a, b) } else { hash_merkle_node(b, a) }
+    }
+
+    fn test_leaves() -> [[u8; 32]; 4] {
+        [leaf(b"leaf0"), leaf(b"leaf1"), leaf(b"leaf2"), leaf(b"leaf3")]
+    }
+
+    // Roots over SHA256(0x00 || "leaf0".."leaf3"), computed independently off-chain.
+    const INDEXED_ROOT: [u8; 32] = [
+        0x86, 0xf9, 0xec, 0x25, 0xa8, 0xa2, 0xb3, 0x2a,
+        0x4b, 0xd7, 0x33, 0xe0, 0x4c, 0x21, 0x3d, 0xe6,
+        0x3c, 0x8b, 0x06, 0x55, 0xbc, 0xb8, 0x87, 0xb7,
+        0x5c, 0xfd, 0x8b, 0x02, 0x69, 0x1b, 0xe0, 0xe5,
+    ];
+
+    const SORTED_ROOT: [u8; 32] = [
+        0x77, 0xd4, 0x81, 0x7f, 0x36, 0x0b, 0x81, 0xba,
+        0xe3, 0x83, 0x52, 0xec, 0x10, 0x0d, 0x3f, 0xaf,
+        0xc6, 0x50, 0x66, 0x3e, 0x99, 0x55, 0x5b, 0xca,
+        0x96, 0xe3, 0x72, 0xf2, 0xce, 0x8c, 0xf7, 0x89,
+    ];
+
+    #[test]
+    fn proof_len_for_power_of_two_winners() {
+        assert_eq!(expected_proof_len(1), 0);
+        assert_eq!(expected_proof_len(2), 1);
+        assert_eq!(expected_proof_len(4), 2);
+        assert_eq!(expected_proof_len(1024), 10);
+        assert_eq!(expected_proof_len(1 << 31), 31);
+    }
+
+    #[test]
+    fn proof_len_rounds_up_between_powers_of_two() {
+        assert_eq!(expected_proof_len(0), 0);
+        assert_eq!(expected_proof_len(3), 2);
+        assert_eq!(expected_proof_len(5), 3);
+        assert_eq!(expected_proof_len(1000), 10);
+        assert_eq!(expected_proof_len(1025), 11);
+        assert_eq!(expected_proof_len(u32::MAX), 32);
+    }
+
+    #[test]
+    fn indexed_proofs_verify_for_every_leaf() {
+        let l = test_leaves();
+        let n01 = hash_merkle_node(&l[0], &l[1]);
+        let n23 = hash_merkle_node(&l[2], &l[3]);
+        assert_eq!(hash_merkle_node(&n01, &n23), INDEXED_ROOT);
+
+        let proofs = [
+            [l[1], n23],
+            [l[0], n23],
+            [l[3], n01],
+            [l[2], n01],
+        ];
+
+        for (i, (leaf, proof)) in l.iter().zip(proofs.iter()).enumerate() {
+            assert!(verify_merkle_proof(leaf, proof, &INDEXED_ROOT, i as u32));
+        }
+        // Wrong index flips the pair order and must fail
+        assert!(!verify_merkle_proof(&l[0], &proofs[0], &INDEXED_ROOT, 1));
+    }
+
+    #[test]
+    fn sorted_tree_matches_known_root() {
+        let l = test_leaves();
+        let root = sorted_parent(&sorted_parent(&l[0], &l[1]), &sorted_parent(&l[2], &l[3]));
+        assert_eq!(root, SORTED_ROOT);
+    }
+
+    #[test]
+    fn sorted_proofs_verify_for_every_leaf() {
+        let l = test_leaves();
+        let n01 = sorted_parent(&l[0], &l[1]);
+        let n23 = sorted_parent(&l[2], &l[3]);
+
+        let proofs = [
+            [l[1], n23],
+            [l[0], n23],
+            [l[3], n01],
+            [l[2], n01],
+        ];
+
+        for (leaf, proof) in l.iter().zip(proofs.iter()) {
+            assert!(verify_merkle_proof_sorted(leaf, proof, &SORTED_ROOT));
+        }
+    }
+
+    #[test]
+    fn sorted_proof_rejects_wrong_leaf() {
+        let l = test_leaves();
+        let n23 = sorted_parent(&l[2], &l[3]);
+        let bogus = leaf(b"leaf9");
+        assert!(!verify_merkle_proof_sorted(&bogus, &[l[1], n23], &SORTED_ROOT));
+    }
+
+    #[test]
+    fn internal_node_cannot_pose_as_leaf() {
+        let l = test_leaves();
+        let n01 = hash_merkle_node(&l[0], &l[1]);
+        let n23 = hash_merkle_node(&l[2], &l[3]);
+
+        // Hashing the 64-byte node preimage as leaf data never yields the node
+        let mut preimage = [0u8; 64];
+        preimage[..32].copy_from_slice(&l[0]);
+        preimage[32..].copy_from_slice(&l[1]);
+        assert_ne!(leaf(&preimage), n01);
+
+        // A forged leaf built from the node preimage fails the shortened proof
+        assert!(!verify_merkle_proof(&leaf(&preimage), &[n23], &INDEXED_ROOT, 0));
+    }
+
+    #[test]
+    fn claim_leaf_binds_every_field() {
+        let wallet = Pubkey::new_from_array([7u8; 32]);
+        let base = hash_claim_leaf(10, 1, 3, &wallet, 500, 0b10);
+
+        assert_eq!(base, hash_claim_leaf(10, 1, 3, &wallet, 500, 0b10));
+        assert_ne!(base, hash_claim_leaf(11, 1, 3, &wallet, 500, 0b10));
+        assert_ne!(base, hash_claim_leaf(10, 2, 3, &wallet, 500, 0b10));
+        assert_ne!(base, hash_claim_leaf(10, 1, 4, &wallet, 500, 0b10));
+        assert_ne!(base, hash_claim_leaf(10, 1, 3, &Pubkey::default(), 500, 0b10));
+        assert_ne!(base, hash_claim_leaf(10, 1, 3, &wallet, 501, 0b10));
+        assert_ne!(base, hash_claim_leaf(10, 1, 3, &wallet, 500, 0b100));
+    }
+
+    #[test]
+    fn v2_proofs_still_verify() {
+        let wallet = Pubkey::new_from_array([7u8; 32]);
+        let leaves: Vec<[u8; 32]> = (0..4u32)
+            .map(|i| hash_claim_leaf_v2(10, 1, i, &wallet, 500 + i as u64, 0b10))
+            .collect();
+        let left = hash_merkle_node_v2(&leaves[0], &leaves[1]);
+        let right = hash_merkle_node_v2(&leaves[2], &leaves[3]);
+        let root = hash_merkle_node_v2(&left, &right);
+
+        assert!(verify_merkle_proof_v2(&leaves[0], &[leaves[1], right], &root, 0));
+        assert!(verify_merkle_proof_v2(&leaves[3], &[leaves[2], left], &root, 3));
+        assert!(!verify_merkle_proof_v2(&leaves[3], &[leaves[2], left], &root, 2));
+        // A V2 tree never verifies under the domain-separated node hash.
+        assert!(!verify_merkle_proof(&leaves[0], &[leaves[1], right], &root, 0));
+        assert_ne!(leaves[0], hash_claim_leaf(10, 1, 0, &wallet, 500, 0b10));
+    }
+
+    // OpenZeppelin-style tree: leaves stored in reverse at the end of the array.
+    fn make_sorted_tree(leaves: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        let len = 2 * leaves.len() - 1;
+        let mut tree = vec![[0u8; 32]; len];
+        for (i, l) in leaves.iter().enumerate() {
+            tree[len - 1 - i] = *l;
+        }
+        for i in (0..len - leaves.len()).rev() {
+            tree[i] = sorted_parent(&tree[2 * i + 1], &tree[2 * i + 2]);
+        }
+        tree
+    }
+
+    // OpenZeppelin `getMultiProof` over tree indices.
+    fn make_multiproof(
+        tree: &[[u8; 32]],
+        mut indices: Vec<usize>,
+    ) -> (Vec<[u8; 32]>, Vec<bool>, Vec<[u8; 32]>) {
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        let leaves = indices.iter().map(|i| tree[*i]).collect();
+
+        let mut stack: std::collections::VecDeque<usize> = indices.into_iter().collect();
+        let mut proof = Vec::new();
+        let mut flags = Vec::new();
+
+        while let Some(&j) = stack.front() {
+            if j == 0 {
+                break;
+            }
+            stack.pop_front();
+            let sibling = if j % 2 == 1 { j + 1 } else { j - 1 };
+            let parent = (j - 1) / 2;
+
+            if stack.front() == Some(&sibling) {
+                flags.push(true);
+                stack.pop_front();
+            } else {
+                flags.push(false);
+                proof.push(tree[sibling]);
+            }
+            stack.push_back(parent);
+        }
+
+        (leaves, flags, proof)
+    }
+
+    #[test]
+    fn multiproof_claims_three_of_five_winners() {
+        let wallets: Vec<Pubkey> = (1..=5u8).map(|b| Pubkey::new_from_array([b; 32])).collect();
+        let leaves: Vec<[u8; 32]> = wallets
+            .iter()
+            .enumerate()
+            .map(|(i, w)| hash_claim_leaf(42, 1, i as u32, w, 1_000 + i as u64, 0b10))
+            .collect();
+
+        let tree = make_sorted_tree(&leaves);
+        let root = tree[0];
+        let n = tree.len();
+
+        // Winners 0, 2 and 4 claim together
+        let (claim_leaves, flags, proof) =
+            make_multiproof(&tree, vec![n - 1, n - 3, n - 5]);
+
+        assert_eq!(claim_leaves.len(), 3);
+        assert!(verify_merkle_multiproof(&claim_leaves, &flags, &proof, &root));
+
+        // Tampering with any leaf breaks the proof
+        let mut forged = claim_leaves.clone();
+        forged[1] = hash_claim_leaf(42, 1, 2, &wallets[2], 9_999, 0b10);
+        assert!(!verify_merkle_multiproof(&forged, &flags, &proof, &root));
+
+        // Malformed flag/proof lengths are rejected
+        assert!(!verify_merkle_multiproof(&claim_leaves, &flags[1..], &proof, &root));
+    }
 }
\ No newline at end of file
@@ -1,11 +1,15 @@
 pub mod bitmap;
+pub mod claim;
 pub mod merkle;
 pub mod betting;
 pub mod transfers;
 pub mod ticket;
 pub mod resolve;
 pub mod prediction;
+pub mod xp;
+pub mod math;
 
 pub use bitmap::*;
 pub use merkle::*;
 pub use prediction::*;
+pub use resolve::{validate_results_uri, validate_results_uri_with_ext};
@@ -1,4 +1,6 @@
 use anchor_lang::{err, require};
+use anchor_lang::prelude::{msg, Pubkey};
+use sha2::{Digest, Sha256};
 use crate::errors::IC42NErrorCode;
 use crate::state::{LiveFeed, Prediction};
 
@@ -17,6 +19,8 @@ use crate::state::{LiveFeed, Prediction};
 /// For some prediction types, `choice` is interpreted as a mode:
 /// - HIGH_LOW: choice = 0 (low) or 1 (high), selections are derived from eligible list
 /// - EVEN_ODD: choice = 0 (even) or 1 (odd), selections are derived from eligible list
+/// - RANGE: choice = two digits `low high` (e.g. 47 => 4..=7), blocked number skipped
+/// - ALL_EXCEPT: choice = digits to exclude (e.g. 28 => every eligible number but 2 and 8)
 pub fn derive_prediction_selections(
     prediction_type: u8,
     choice: u32,
@@ -125,6 +129,47 @@ pub fn derive_prediction_selections(
             return Ok((count, out, mask));
         }
 
+        // ------------------------------------------------------------
+        // RANGE
+        // choice encodes (low, high) as two digits: 47 => 4..=7
+        // the blocked number is skipped; the band must keep at least one number
+        // ------------------------------------------------------------
+        x if x == Prediction::TYPE_RANGE => {
+            require!((11..=99).contains(&choice), IC42NErrorCode::InvalidBetNumber);
+
+            let low = (choice / 10) as u8;
+            let high = (choice % 10) as u8;
+            require!(low >= 1 && high <= 9, IC42NErrorCode::InvalidBetNumber);
+            require!(low <= high, IC42NErrorCode::InvalidBetNumber);
+
+            let mut idx = 0usize;
+            for &v in eligible.iter().filter(|&&v| v >= low && v <= high) {
+                out[idx] = v;
+                idx += 1;
+            }
+
+            require!(idx > 0, IC42NErrorCode::InvalidBetNumber);
+            count = idx as u8;
+        }
+
+        // ------------------------------------------------------------
+        // ALL_EXCEPT
+        // choice encodes the excluded digits (same rules as the digit
+        // types); selections are the eligible list minus those digits
+        // ------------------------------------------------------------
+        x if x == Prediction::TYPE_ALL_EXCEPT => {
+            let (_, _, excluded_mask) = decode_choice_digits(choice, blocked_secondary)?;
+
+            let mut idx = 0usize;
+            for &v in eligible.iter().filter(|&&v| excluded_mask & (1u16 << v) == 0) {
+                out[idx] = v;
+                idx += 1;
+            }
+
+            require!(idx > 0, IC42NErrorCode::InvalidBetNumber);
+            count = idx as u8;
+        }
+
         _ => return err!(IC42NErrorCode::InvalidBetNumber),
     }
 
@@ -205,8 +250,39 @@ fn decode_choice_digits(
     Ok((count, out, mask))
 }
 
+/// Canonical form of a digit-encoded choice, exactly as the program stores it:
+/// `(selections, selection_count, selections_mask)`, selections ascending and
+/// zero-padded. e.g. 7895 => [5,7,8,9].
+///
+/// Same rules as `decode_choice_digits`; `blocked` must be a real number 1~9.
+pub fn canonicalize_choice(
+    choice: u32,
+    blocked: u8,
+) -> anchor_lang::Result<([u8; 8], u8, u16)> {
+    require!((1..=9).contains(&blocked), IC42NErrorCode::InvalidBetNumber);
+
+    let (count, selections, mask) = decode_choice_digits(choice, blocked)?;
+    Ok((selections, count, mask))
+}
 
 
+/// Returns true if `choice` re-encodes `selections_mask` for `prediction_type`.
+///
+/// The blocked secondary number may differ from the one in force when the
+/// prediction was placed (e.g. a `tier_reset` resync), so every possible
+/// blocked number is tried.
+pub fn choice_matches_selections(
+    prediction_type: u8,
+    choice: u32,
+    selections_mask: u16,
+) -> bool {
+    (1u8..=9u8).any(|blocked| {
+        derive_prediction_selections(prediction_type, choice, blocked)
+            .map(|(_, _, mask)| mask == selections_mask)
+            .unwrap_or(false)
+    })
+}
+
 pub fn retract_per_number_from_live(
     live: &mut LiveFeed,
     lamports_per_number: u64,
@@ -259,3 +335,539 @@ pub fn apply_per_number_to_live(
 
     Ok(())
 }
+
+/// Moves one bet count per number from `old_mask` to `new_mask`.
+///
+/// Numbers only in the old set lose a bet, numbers only in the new set gain
+/// one, and numbers in both are left untouched.
+pub fn apply_mask_diff_to_bets_per_number(
+    live: &mut LiveFeed,
+    old_mask: u16,
+    new_mask: u16,
+) -> anchor_lang::Result<()> {
+    apply_mask_diff(&mut live.bets_per_number, old_mask, new_mask)
+}
+
+/// `apply_mask_diff_to_bets_per_number` on the raw per-number counts.
+///
+/// Afterwards the total count must have moved by exactly
+/// `added - removed` numbers; anything else (e.g. mask bits outside 1..=9
+/// that the loop skips) is rejected as `LiveFeedAccountingMismatch`.
+pub fn apply_mask_diff(
+    bets_per_number: &mut [u64; 10],
+    old_mask: u16,
+    new_mask: u16,
+) -> anchor_lang::Result<()> {
+    let removed = old_mask & !new_mask;
+    let added = new_mask & !old_mask;
+
+    let total_before: i128 = bets_per_number.iter().map(|b| *b as i128).sum();
+
+    for n in 1u8..=9u8 {
+        let bit = 1u16 << n;
+        let idx = n as usize;
+
+        if (removed & bit) != 0 {
+            require!(bets_per_number[idx] >= 1, IC42NErrorCode::InvalidLiveFeedState);
+            bets_per_number[idx] = bets_per_number[idx]
+                .checked_sub(1)
+                .ok_or(IC42NErrorCode::MathOverflow)?;
+        }
+
+        if (added & bit) != 0 {
+            bets_per_number[idx] = bets_per_number[idx]
+                .checked_add(1)
+                .ok_or(IC42NErrorCode::MathOverflow)?;
+        }
+    }
+
+    let total_after: i128 = bets_per_number.iter().map(|b| *b as i128).sum();
+    require!(
+        total_after - total_before
+            == added.count_ones() as i128 - removed.count_ones() as i128,
+        IC42NErrorCode::LiveFeedAccountingMismatch
+    );
+
+    Ok(())
+}
+
+/// Removes a whole prediction from the live feed: its bet, its total lamports,
+/// and its per-number lamports and bet counts.
+///
+/// Carry-over counters are clamped so they never exceed the new totals.
+pub fn retract_prediction_from_live(
+    live: &mut LiveFeed,
+    pred: &Prediction,
+) -> anchor_lang::Result<()> {
+    retract_per_number_from_live(
+        live,
+        pred.lamports_per_number,
+        &pred.selections,
+        pred.selection_count,
+    )?;
+    apply_mask_diff_to_bets_per_number(live, pred.selections_mask, 0)?;
+
+    require!(live.total_bets >= 1, IC42NErrorCode::InvalidLiveFeedState);
+    require!(live.total_lamports >= pred.lamports, IC42NErrorCode::InvalidLiveFeedState);
+
+    live.total_bets -= 1;
+    live.total_lamports -= pred.lamports;
+
+    live.carried_over_bets = live.carried_over_bets.min(live.total_bets);
+    live.carried_over_lamports = live.carried_over_lamports.min(live.total_lamports);
+
+    // Nothing left to resolve once every bet is gone
+    if live.total_bets == 0 {
+        live.pending_resolution_epoch = 0;
+    }
+
+    Ok(())
+}
+
+/// True if a prediction covering `selections_mask` is a legitimate winner of
+/// a game drawn on `winning_number` with `blocked` as its secondary rollover.
+///
+/// The blocked number is fixed for the life of a chain: rollovers carry it
+/// forward unchanged and only a new chain (after a payout) picks the next
+/// one, so honest predictions never cover it. A mask that does (legacy or
+/// corrupted state) is ineligible as a whole rather than paid pro rata, and
+/// the resolver must leave it out of the winner tree.
+pub fn is_eligible_winner(selections_mask: u16, winning_number: u8, blocked: u8) -> bool {
+    let covers = |n: u8| n <= 9 && selections_mask & (1u16 << n) != 0;
+    (1..=9).contains(&winning_number) && covers(winning_number) && !covers(blocked)
+}
+
+/// Numbers of `selections_mask` that would still win a draw under `blocked`
+/// (bit n set = winning on n pays). Follows `is_eligible_winner`, so a mask
+/// covering the blocked number has no eligible numbers at all.
+pub fn eligible_selections_mask(selections_mask: u16, blocked: u8) -> u16 {
+    (1u8..=9)
+        .filter(|&n| is_eligible_winner(selections_mask, n, blocked))
+        .fold(0u16, |mask, n| mask | (1u16 << n))
+}
+
+/// Domain tag for `reroll_seed`.
+pub const REROLL_SEED_TAG: &[u8] = b"IC42N_REROLL";
+
+/// Seed for `reroll_selections`:
+///
+/// seed = SHA256("IC42N_REROLL" || slot_hash || prediction || changed_count)
+///
+/// `slot_hash` is the newest SlotHashes entry (the parent slot's bank hash),
+/// so the outcome is fixed once that slot lands and anyone can recompute it.
+/// That is acceptable here: every number pays the same, so a predictable
+/// re-pick gives no edge. `changed_count` makes repeated rerolls in one slot differ.
+pub fn reroll_seed(slot_hash: &[u8; 32], prediction: &Pubkey, changed_count: u8) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(REROLL_SEED_TAG);
+    hasher.update(slot_hash);
+    hasher.update(prediction.as_ref());
+    hasher.update([changed_count]);
+    hasher.finalize().into()
+}
+
+/// Picks `count` distinct numbers from 1..=9 minus `blocked`, driven by `seed`.
+///
+/// Partial Fisher-Yates over the eligible list, one seed byte per draw
+/// (the slight modulo bias is irrelevant since all numbers pay the same).
+/// Returns the selections in canonical ascending order and their mask.
+pub fn reroll_selections(
+    seed: &[u8; 32],
+    count: u8,
+    blocked: u8,
+) -> anchor_lang::Result<([u8; 8], u16)> {
+    require!((1..=9).contains(&blocked), IC42NErrorCode::InvalidBetNumber);
+    require!((1..=8).contains(&count), IC42NErrorCode::InvalidChoiceCount);
+
+    let mut eligible = [0u8; 8];
+    for (slot, n) in eligible.iter_mut().zip((1u8..=9).filter(|&n| n != blocked)) {
+        *slot = n;
+    }
+
+    let count = count as usize;
+    for (i, &byte) in seed.iter().take(count).enumerate() {
+        let j = i + (byte as usize) % (eligible.len() - i);
+        eligible.swap(i, j);
+    }
+
+    let mut out = [0u8; 8];
+    out[..count].copy_from_slice(&eligible[..count]);
+    out[..count].sort_unstable();
+
+    let mask = out[..count].iter().fold(0u16, |m, &n| m | (1u16 << n));
+    Ok((out, mask))
+}
+
+/// Digit-encoded prediction type for a rerolled set of `count` numbers, so
+/// the new selections can still be re-encoded as a `choice` (see
+/// `choice_matches_selections`).
+pub fn reroll_prediction_type(count: u8) -> u8 {
+    match count {
+        1 => Prediction::TYPE_SINGLE_NUMBER,
+        2 => Prediction::TYPE_TWO_NUMBERS,
+        _ => Prediction::TYPE_MULTI_NUMBER,
+    }
+}
+
+/// Sort key for ranking predictions: higher wager first, then earliest slot.
+///
+/// Lamports fill the high 64 bits and the inverted slot the low 64 bits, so
+/// comparing keys (larger = ranks higher) is total and deterministic.
+pub fn bet_ordering_key(lamports: u64, placed_slot: u64) -> u128 {
+    ((lamports as u128) << 64) | (u64::MAX - placed_slot) as u128
+}
+
+/// Returns the first selected number whose live lamports would exceed
+/// `max_lamports_per_number` once `add_per_number` is added to it.
+///
+/// A cap of 0 disables the check.
+pub fn find_number_over_cap(
+    lamports_per_number: &[u64; 10],
+    add_per_number: u64,
+    selections: &[u8; 8],
+    selection_count: u8,
+    max_lamports_per_number: u64,
+) -> Option<u8> {
+    if max_lamports_per_number == 0 {
+        return None;
+    }
+
+    selections
+        .iter()
+        .take(selection_count as usize)
+        .copied()
+        .find(|&n| {
+            lamports_per_number
+                .get(n as usize)
+                .map(|current| current.saturating_add(add_per_number) > max_lamports_per_number)
+                .unwrap_or(true)
+        })
+}
+
+/// Rejects a bet that would push any selected number past the tier's
+/// per-number cap. The offending number is logged.
+pub fn enforce_number_cap(
+    live: &LiveFeed,
+    add_per_number: u64,
+    selections: &[u8; 8],
+    selection_count: u8,
+    max_lamports_per_number: u64,
+) -> anchor_lang::Result<()> {
+    if let Some(n) = find_number_over_cap(
+        &live.lamports_per_number,
+        add_per_number,
+        selections,
+        selection_count,
+        max_lamports_per_number,
+    ) {
+        msg!("Number {} would exceed the per-number cap of {} lamports", n, max_lamports_per_number);
+        return err!(IC42NErrorCode::NumberConcentrationCap);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAP: u64 = 10_000_000;
+
+    /// Random valid coverage mask (numbers 1..=9, at least one).
+    fn random_mask(state: &mut u64) -> u16 {
+        // xorshift64
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        let mask = ((*state % 511) as u16 + 1) << 1;
+        debug_assert!(mask & 1 == 0 && mask < (1 << 10));
+        mask
+    }
+
+    #[test]
+    fn mask_diff_keeps_counts_in_sync_under_random_toggles() {
+        let mut state = 0x1c42_u64;
+        let mut masks: Vec<u16> = (0..16).map(|_| random_mask(&mut state)).collect();
+
+        let mut bets = [0u64; 10];
+        for &mask in masks.iter() {
+            apply_mask_diff(&mut bets, 0, mask).unwrap();
+        }
+
+        for step in 0..2_000 {
+            let i = step % masks.len();
+            let new_mask = random_mask(&mut state);
+            apply_mask_diff(&mut bets, masks[i], new_mask).unwrap();
+            masks[i] = new_mask;
+
+            // Every number's count equals the predictions covering it
+            for (n, count) in bets.iter().enumerate().skip(1) {
+                let covering = masks.iter().filter(|m| *m & (1 << n) != 0).count() as u64;
+                assert_eq!(*count, covering);
+            }
+        }
+
+        // Retracting everything returns to zero
+        for &mask in masks.iter() {
+            apply_mask_diff(&mut bets, mask, 0).unwrap();
+        }
+        assert_eq!(bets, [0u64; 10]);
+    }
+
+    #[test]
+    fn mask_diff_rejects_bits_outside_numbers() {
+        let mismatch: anchor_lang::error::Error =
+            IC42NErrorCode::LiveFeedAccountingMismatch.into();
+        let mut bets = [0u64; 10];
+
+        // Bit 0 and bits past 9 are never counted, so the totals would drift
+        assert_eq!(apply_mask_diff(&mut bets, 0, 1 << 0).unwrap_err(), mismatch);
+        assert_eq!(apply_mask_diff(&mut bets, 0, 1 << 10).unwrap_err(), mismatch);
+
+        // Removing a number nobody covers
+        assert!(apply_mask_diff(&mut bets, 1 << 4, 0).is_err());
+    }
+
+    #[test]
+    fn blocked_number_stays_fixed_across_rollovers() {
+        use crate::utils::resolve::get_next_rollover_number;
+
+        // Chain blocks 4; the {2,7} prediction is placed before two rollovers
+        let blocked = 4;
+        let (_, _, mask) =
+            derive_prediction_selections(Prediction::TYPE_TWO_NUMBERS, 27, blocked).unwrap();
+
+        // Rolling over on 0 or on the blocked number keeps the block
+        assert_eq!(get_next_rollover_number(0, blocked), blocked);
+        assert_eq!(get_next_rollover_number(4, blocked), blocked);
+
+        // The payout on 7 ends the chain; only the next chain blocks 7
+        assert!(is_eligible_winner(mask, 7, blocked));
+        assert!(!is_eligible_winner(mask, 3, blocked));
+        assert_eq!(get_next_rollover_number(7, blocked), 7);
+
+        // A mask holding the game's blocked number is never a winner
+        let covers_blocked = (1 << 4) | (1 << 7);
+        assert!(!is_eligible_winner(covers_blocked, 7, blocked));
+        assert!(is_eligible_winner(covers_blocked, 7, 0));
+        assert!(!is_eligible_winner(mask, 0, blocked));
+    }
+
+    #[test]
+    fn blocking_a_covered_number_voids_eligibility() {
+        // {2, 5, 7} placed while 4 was blocked
+        let (_, _, mask) =
+            derive_prediction_selections(Prediction::TYPE_MULTI_NUMBER, 257, 4).unwrap();
+        assert_eq!(eligible_selections_mask(mask, 4), mask);
+        assert_eq!(eligible_selections_mask(mask, 4).count_ones(), 3);
+
+        // The block moves onto 5 (mid-chain correction): nothing can win
+        assert_eq!(eligible_selections_mask(mask, 5), 0);
+
+        // A block elsewhere leaves the prediction untouched
+        assert_eq!(eligible_selections_mask(mask, 9), mask);
+    }
+
+    #[test]
+    fn ordering_key_prefers_wager_then_earliest_slot() {
+        // Equal lamports: earlier slot ranks higher
+        assert!(bet_ordering_key(100, 10) > bet_ordering_key(100, 11));
+        assert!(bet_ordering_key(100, 0) > bet_ordering_key(100, u64::MAX));
+
+        // Larger wager always wins, whatever the slot
+        assert!(bet_ordering_key(101, u64::MAX) > bet_ordering_key(100, 0));
+
+        // Only identical bets tie
+        assert_eq!(bet_ordering_key(100, 10), bet_ordering_key(100, 10));
+    }
+
+    #[test]
+    fn number_cap_rejects_bet_past_full_number() {
+        let mut per_number = [0u64; 10];
+        let selections = [3u8, 0, 0, 0, 0, 0, 0, 0];
+
+        // Fill number 3 up to the cap in equal bets
+        let bet = 2_500_000;
+        for _ in 0..4 {
+            assert_eq!(find_number_over_cap(&per_number, bet, &selections, 1, CAP), None);
+            per_number[3] += bet;
+        }
+        assert_eq!(per_number[3], CAP);
+
+        // Next bet on 3 fails, even for a single lamport
+        assert_eq!(find_number_over_cap(&per_number, 1, &selections, 1, CAP), Some(3));
+    }
+
+    #[test]
+    fn number_cap_names_the_offending_number() {
+        let mut per_number = [0u64; 10];
+        per_number[7] = CAP;
+
+        // 2 and 5 still have room, 7 is full
+        let selections = [2u8, 5, 7, 0, 0, 0, 0, 0];
+        assert_eq!(find_number_over_cap(&per_number, 1, &selections, 3, CAP), Some(7));
+        assert_eq!(find_number_over_cap(&per_number, 1, &selections, 2, CAP), None);
+    }
+
+    #[test]
+    fn increase_choice_must_match_stored_selections() {
+        let (_, _, mask) =
+            derive_prediction_selections(Prediction::TYPE_TWO_NUMBERS, 37, 5).unwrap();
+
+        assert!(choice_matches_selections(Prediction::TYPE_TWO_NUMBERS, 37, mask));
+        assert!(choice_matches_selections(Prediction::TYPE_TWO_NUMBERS, 73, mask));
+
+        // Mismatched choice
+        assert!(!choice_matches_selections(Prediction::TYPE_TWO_NUMBERS, 38, mask));
+        assert!(!choice_matches_selections(Prediction::TYPE_SINGLE_NUMBER, 3, mask));
+    }
+
+    #[test]
+    fn increase_choice_survives_blocked_number_change() {
+        // LOW placed while 3 was blocked: [1, 2, 4, 5]
+        let (_, _, low_mask) =
+            derive_prediction_selections(Prediction::TYPE_HIGH_LOW, 0, 3).unwrap();
+
+        assert!(choice_matches_selections(Prediction::TYPE_HIGH_LOW, 0, low_mask));
+        assert!(!choice_matches_selections(Prediction::TYPE_HIGH_LOW, 1, low_mask));
+    }
+
+    #[test]
+    fn canonical_choice_matches_documented_examples() {
+        let cases: [(u32, [u8; 8], u8); 4] = [
+            (3, [3, 0, 0, 0, 0, 0, 0, 0], 1),
+            (37, [3, 7, 0, 0, 0, 0, 0, 0], 2),
+            (356, [3, 5, 6, 0, 0, 0, 0, 0], 3),
+            (7895, [5, 7, 8, 9, 0, 0, 0, 0], 4),
+        ];
+
+        for (choice, expected, expected_count) in cases {
+            let (selections, count, mask) = canonicalize_choice(choice, 1).unwrap();
+            assert_eq!(selections, expected);
+            assert_eq!(count, expected_count);
+
+            let expected_mask = expected[..count as usize]
+                .iter()
+                .fold(0u16, |m, n| m | (1 << n));
+            assert_eq!(mask, expected_mask);
+        }
+
+        // Digit order never changes the result
+        assert_eq!(canonicalize_choice(9875, 1).unwrap(), canonicalize_choice(7895, 1).unwrap());
+    }
+
+    #[test]
+    fn canonical_choice_rejects_invalid_digits() {
+        // Zero digit, duplicate, blocked number, empty choice, unset blocked number
+        for (choice, blocked) in [(307, 1), (337, 1), (37, 7), (0, 1), (37, 0)] {
+            assert!(canonicalize_choice(choice, blocked).is_err());
+        }
+    }
+
+    #[test]
+    fn range_expands_contiguous_band() {
+        let (count, selections, mask) =
+            derive_prediction_selections(Prediction::TYPE_RANGE, 47, 2).unwrap();
+        assert_eq!(count, 4);
+        assert_eq!(selections, [4, 5, 6, 7, 0, 0, 0, 0]);
+        assert_eq!(mask, (1 << 4) | (1 << 5) | (1 << 6) | (1 << 7));
+
+        // Single-number band
+        let (count, selections, _) =
+            derive_prediction_selections(Prediction::TYPE_RANGE, 33, 2).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(selections[0], 3);
+    }
+
+    #[test]
+    fn range_skips_blocked_number() {
+        // 4..=7 straddles blocked 5
+        let (count, selections, mask) =
+            derive_prediction_selections(Prediction::TYPE_RANGE, 47, 5).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(selections, [4, 6, 7, 0, 0, 0, 0, 0]);
+        assert_eq!(mask & (1 << 5), 0);
+
+        // The full band still fits in 8 selections
+        let (count, _, _) =
+            derive_prediction_selections(Prediction::TYPE_RANGE, 19, 9).unwrap();
+        assert_eq!(count, 8);
+    }
+
+    #[test]
+    fn range_rejects_invalid_bands() {
+        // Band is only the blocked number
+        assert!(derive_prediction_selections(Prediction::TYPE_RANGE, 55, 5).is_err());
+        // low > high
+        assert!(derive_prediction_selections(Prediction::TYPE_RANGE, 74, 2).is_err());
+        // 0 is never selectable
+        assert!(derive_prediction_selections(Prediction::TYPE_RANGE, 5, 2).is_err());
+        assert!(derive_prediction_selections(Prediction::TYPE_RANGE, 10, 2).is_err());
+        // Not a two-digit pair
+        assert!(derive_prediction_selections(Prediction::TYPE_RANGE, 147, 2).is_err());
+    }
+
+    #[test]
+    fn all_except_excludes_one_or_two_numbers() {
+        // Blocked 9, exclude 4 => 1,2,3,5,6,7,8
+        let (count, selections, mask) =
+            derive_prediction_selections(Prediction::TYPE_ALL_EXCEPT, 4, 9).unwrap();
+        assert_eq!(count, 7);
+        assert_eq!(selections, [1, 2, 3, 5, 6, 7, 8, 0]);
+        assert_eq!(mask & ((1 << 4) | (1 << 9)), 0);
+
+        // Exclude 2 and 8 (any digit order)
+        let (count, selections, _) =
+            derive_prediction_selections(Prediction::TYPE_ALL_EXCEPT, 82, 9).unwrap();
+        assert_eq!(count, 6);
+        assert_eq!(selections, [1, 3, 4, 5, 6, 7, 0, 0]);
+    }
+
+    #[test]
+    fn all_except_rejects_excluding_everything() {
+        // Blocked 9, excluding 1..=8 leaves nothing
+        assert!(derive_prediction_selections(Prediction::TYPE_ALL_EXCEPT, 12_345_678, 9).is_err());
+
+        // Excluding the blocked number itself is not a valid exclusion
+        assert!(derive_prediction_selections(Prediction::TYPE_ALL_EXCEPT, 9, 9).is_err());
+        assert!(derive_prediction_selections(Prediction::TYPE_ALL_EXCEPT, 0, 9).is_err());
+    }
+
+    #[test]
+    fn zero_number_cap_is_disabled() {
+        let per_number = [u64::MAX; 10];
+        let selections = [1u8, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(find_number_over_cap(&per_number, 1, &selections, 1, 0), None);
+    }
+    #[test]
+    fn reroll_picks_valid_selections() {
+        for blocked in 1u8..=9 {
+            for count in 1u8..=8 {
+                let seed = reroll_seed(&[blocked; 32], &Pubkey::new_unique(), count);
+                let (selections, mask) = reroll_selections(&seed, count, blocked).unwrap();
+
+                assert_eq!(mask.count_ones(), count as u32);
+                assert_eq!(mask & ((1 << 0) | (1 << blocked)), 0);
+                assert!(selections[..count as usize].windows(2).all(|w| w[0] < w[1]));
+                assert!(selections[count as usize..].iter().all(|&n| n == 0));
+
+                // The stored selections re-encode under the rerolled type
+                let choice = selections[..count as usize]
+                    .iter()
+                    .fold(0u32, |c, &n| c * 10 + n as u32);
+                assert!(choice_matches_selections(reroll_prediction_type(count), choice, mask));
+            }
+        }
+    }
+
+    #[test]
+    fn reroll_is_deterministic_per_seed() {
+        let pred = Pubkey::new_unique();
+        let seed = reroll_seed(&[7u8; 32], &pred, 0);
+
+        assert_eq!(reroll_selections(&seed, 3, 5).unwrap(), reroll_selections(&seed, 3, 5).unwrap());
+        assert_ne!(seed, reroll_seed(&[7u8; 32], &pred, 1));
+        assert!(reroll_selections(&seed, 0, 5).is_err());
+        assert!(reroll_selections(&seed, 9, 5).is_err());
+    }
+}
\ No newline at end of file
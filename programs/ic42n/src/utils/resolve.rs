@@ -1,3 +1,14 @@
+use anchor_lang::require;
+use sha2::{Digest, Sha256};
+use crate::errors::IC42NErrorCode;
+use crate::state::resolved_game::ResolvedGame;
+
+/// URI schemes accepted for `ResolvedGame.results_uri`.
+pub const RESULTS_URI_SCHEMES: [&[u8]; 3] = [b"ar://", b"ipfs://", b"https://"];
+
+/// Domain tag for `fallback_winning_number`.
+pub const FALLBACK_RNG_TAG: &[u8] = b"IC42N_FALLBACK";
+
 // If the winning number is 0 or is the current secondary rollover number,
 // then we keep the same rollover number. Else, we use the winning number as the new rollover number.
 pub fn get_next_rollover_number(winning_number: u8,current_rollover: u8) -> u8 {
@@ -9,6 +20,82 @@ pub fn get_next_rollover_number(winning_number: u8,current_rollover: u8) -> u8 {
     next_secondary_rollover
 }
 
+/// True if `winning_number` is a real draw (1..=9). 0 is reserved for the
+/// explicit rollover signal (`complete_rollover_game` with `is_rollover`).
+pub fn is_drawn_number(winning_number: u8) -> bool {
+    (1..=9).contains(&winning_number)
+}
+
+/// Winning number for `resolve_with_fallback_rng`, used when the regular
+/// RNG source is unavailable:
+///
+/// h = SHA256("IC42N_FALLBACK" || first_epoch_in_chain (u64 LE) || tier || rng_blockhash)
+/// winning_number = 1 + (u64 LE of h[0..8]) % 9
+///
+/// `rng_blockhash` must be the SlotHashes entry that ends the resolved epoch
+/// (see `verify_fallback_rng`), and anyone can recompute the number from
+/// the stored game fields, so the fallback leaves the resolver no choice of
+/// number. It may land on the chain's secondary rollover number, in which
+/// case the game rolls over as usual.
+pub fn fallback_winning_number(first_epoch_in_chain: u64, tier: u8, rng_blockhash: &[u8; 32]) -> u8 {
+    let mut hasher = Sha256::new();
+    hasher.update(FALLBACK_RNG_TAG);
+    hasher.update(first_epoch_in_chain.to_le_bytes());
+    hasher.update([tier]);
+    hasher.update(rng_blockhash);
+    let h: [u8; 32] = hasher.finalize().into();
+
+    let mut head = [0u8; 8];
+    head.copy_from_slice(&h[..8]);
+    1 + (u64::from_le_bytes(head) % 9) as u8
+}
+
+/// Newest entry of the SlotHashes sysvar data at or before `slot`.
+///
+/// Layout: u64 entry count, then (slot: u64, hash: [u8; 32]) entries, newest
+/// first. Fails with `StaleRngSlot` once every entry is newer than `slot`.
+pub fn slot_hash_at_or_before(data: &[u8], slot: u64) -> anchor_lang::Result<(u64, [u8; 32])> {
+    require!(data.len() >= 8, IC42NErrorCode::InvalidInput);
+    let entries = u64::from_le_bytes(data[..8].try_into().unwrap()) as usize;
+    require!(
+        entries > 0 && data.len() >= 8 + entries.saturating_mul(40),
+        IC42NErrorCode::InvalidInput
+    );
+
+    for entry in data[8..8 + entries * 40].chunks_exact(40) {
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if entry_slot <= slot {
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(&entry[8..]);
+            return Ok((entry_slot, hash));
+        }
+    }
+
+    Err(IC42NErrorCode::StaleRngSlot.into())
+}
+
+/// Checks the RNG inputs of `resolve_with_fallback_rng`: they must be the
+/// SlotHashes entry ending the resolved epoch (the newest one at or before
+/// `last_epoch_slot`), so the resolver can't shop for a blockhash.
+pub fn verify_fallback_rng(
+    slot_hashes: &[u8],
+    last_epoch_slot: u64,
+    rng_slot: u64,
+    rng_blockhash: &[u8; 32],
+) -> anchor_lang::Result<()> {
+    let (slot, hash) = slot_hash_at_or_before(slot_hashes, last_epoch_slot)?;
+    require!(
+        rng_slot == slot && *rng_blockhash == hash,
+        IC42NErrorCode::FallbackRngMismatch
+    );
+    Ok(())
+}
+
+/// True if `winning_number` forces a rollover: 0, or the chain's current
+/// secondary rollover number. Such games must go through the rollover path.
+pub fn is_rollover_number(winning_number: u8, secondary_rollover: u8) -> bool {
+    winning_number == 0 || winning_number == secondary_rollover
+}
 
 /// Calculates the next fee BPS after a rollover.
 ///
@@ -34,4 +121,208 @@ pub fn next_fee_bps_on_rollover(
     let current = current_fee_bps.max(min_fee_bps);
     let decreased = current.saturating_sub(rollover_step_bps);
     decreased.max(min_fee_bps)
+}
+
+/// Fee BPS after `n` consecutive rollover-number carries starting at `base`.
+///
+/// Equivalent to applying `next_fee_bps_on_rollover` `n` times:
+///   base = 500, step = 100, min = 300 → n=0: 500, n=1: 400, n=2+: 300
+pub fn fee_bps_after_rollovers(base: u16, step: u16, min: u16, n: u8) -> u16 {
+    let start = base.max(min) as u32;
+    let total_step = (step as u32) * (n as u32);
+    (start.saturating_sub(total_step) as u16).max(min)
+}
+
+
+/// Validates a zero-padded `results_uri` before it is committed on-chain.
+///
+/// - Trailing zero bytes are padding and are trimmed
+/// - The remaining prefix must be non-empty and valid UTF-8
+/// - It must start with one of `RESULTS_URI_SCHEMES`
+pub fn validate_results_uri(uri: &[u8; 128]) -> anchor_lang::Result<()> {
+    validate_results_uri_with_ext(uri, &[])
+}
+
+/// `validate_results_uri` for a URI that may continue past 128 bytes.
+///
+/// A non-empty `ext` is only allowed when the 128-byte field is full (no
+/// padding), holds no zero bytes, and is at most
+/// `ResolvedGame::MAX_RESULTS_URI_EXT_LEN` bytes. UTF-8 is checked on the
+/// whole URI, since a character may straddle the two parts.
+pub fn validate_results_uri_with_ext(uri: &[u8; 128], ext: &[u8]) -> anchor_lang::Result<()> {
+    let len = uri.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    require!(len > 0, IC42NErrorCode::EmptyResultsUri);
+
+    if !ext.is_empty() {
+        require!(
+            ext.len() <= ResolvedGame::MAX_RESULTS_URI_EXT_LEN,
+            IC42NErrorCode::ResultsUriTooLong
+        );
+        require!(
+            len == uri.len() && !ext.contains(&0),
+            IC42NErrorCode::InvalidResultsUri
+        );
+    }
+
+    let mut full = uri[..len].to_vec();
+    full.extend_from_slice(ext);
+    require!(
+        core::str::from_utf8(&full).is_ok(),
+        IC42NErrorCode::InvalidResultsUri
+    );
+    require!(
+        RESULTS_URI_SCHEMES.iter().any(|scheme| full.starts_with(scheme)),
+        IC42NErrorCode::InvalidResultsUri
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::error;
+
+    fn padded(s: &[u8]) -> [u8; 128] {
+        let mut out = [0u8; 128];
+        out[..s.len()].copy_from_slice(s);
+        out
+    }
+
+    #[test]
+    fn rollover_numbers_are_zero_and_secondary() {
+        assert!(is_rollover_number(0, 7));
+        assert!(is_rollover_number(7, 7));
+        assert!(!is_rollover_number(3, 7));
+        assert!(!is_rollover_number(9, 0));
+    }
+
+    #[test]
+    fn zero_is_not_a_drawn_number() {
+        assert!(!is_drawn_number(0));
+        assert!(!is_drawn_number(10));
+        assert!((1..=9).all(is_drawn_number));
+    }
+
+    /// SlotHashes sysvar data holding `entries`, newest first.
+    fn slot_hashes_data(entries: &[(u64, [u8; 32])]) -> Vec<u8> {
+        let mut data = (entries.len() as u64).to_le_bytes().to_vec();
+        for (slot, hash) in entries {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(hash);
+        }
+        data
+    }
+
+    #[test]
+    fn fallback_rng_must_be_the_epoch_end_entry() {
+        // Epoch ends at slot 1_000; slot 1_000 itself was skipped
+        let data = slot_hashes_data(&[(1_002, [3; 32]), (1_001, [2; 32]), (999, [1; 32]), (998, [9; 32])]);
+
+        verify_fallback_rng(&data, 1_000, 999, &[1; 32]).unwrap();
+
+        // A forged hash, or a real one from another slot, is rejected
+        for (slot, hash) in [(999, [4u8; 32]), (998, [9; 32]), (1_001, [2; 32])] {
+            assert_eq!(
+                verify_fallback_rng(&data, 1_000, slot, &hash).unwrap_err(),
+                error!(IC42NErrorCode::FallbackRngMismatch)
+            );
+        }
+
+        // The epoch end already scrolled out of the sysvar
+        assert_eq!(
+            verify_fallback_rng(&data, 900, 999, &[1; 32]).unwrap_err(),
+            error!(IC42NErrorCode::StaleRngSlot)
+        );
+        assert_eq!(
+            slot_hash_at_or_before(&data[..50], u64::MAX).unwrap_err(),
+            error!(IC42NErrorCode::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn fallback_number_is_deterministic() {
+        let hash = [7u8; 32];
+        let n = fallback_winning_number(700, 2, &hash);
+
+        assert!(is_drawn_number(n));
+        assert_eq!(fallback_winning_number(700, 2, &hash), n);
+
+        // Over a spread of hashes every number 1..=9 shows up
+        let mut seen = [false; 10];
+        for b in 0u8..=255 {
+            let n = fallback_winning_number(700, 2, &[b; 32]);
+            assert!(is_drawn_number(n));
+            seen[n as usize] = true;
+        }
+        assert!(seen[1..].iter().all(|s| *s));
+
+        // The chain start and tier are part of the preimage too
+        let draws = |epoch: u64, tier: u8| -> Vec<u8> {
+            (0u8..32).map(|b| fallback_winning_number(epoch, tier, &[b; 32])).collect()
+        };
+        assert_ne!(draws(700, 2), draws(701, 2));
+        assert_ne!(draws(700, 2), draws(700, 3));
+    }
+
+    #[test]
+    fn fee_schedule_matches_step_by_step() {
+        let mut fee = 500u16;
+        for n in 0u8..10 {
+            assert_eq!(fee_bps_after_rollovers(500, 100, 300, n), fee);
+            fee = next_fee_bps_on_rollover(fee, 100, 300);
+        }
+    }
+
+    #[test]
+    fn fee_schedule_decreases_monotonically_to_min() {
+        let mut prev = u16::MAX;
+        for n in 0u8..=u8::MAX {
+            let fee = fee_bps_after_rollovers(1_000, 75, 250, n);
+            assert!(fee <= prev);
+            assert!(fee >= 250);
+            prev = fee;
+        }
+        assert_eq!(fee_bps_after_rollovers(1_000, 75, 250, 10), 250);
+        assert_eq!(fee_bps_after_rollovers(1_000, u16::MAX, 250, u8::MAX), 250);
+
+        // No step, or base already below min
+        assert_eq!(fee_bps_after_rollovers(1_000, 0, 250, 9), 1_000);
+        assert_eq!(fee_bps_after_rollovers(100, 50, 250, 1), 250);
+    }
+
+    #[test]
+    fn accepts_known_schemes() {
+        assert!(validate_results_uri(&padded(b"ar://abc123")).is_ok());
+        assert!(validate_results_uri(&padded(b"ipfs://bafy")).is_ok());
+        assert!(validate_results_uri(&padded(b"https://results.iseefortune.com/1/2.json")).is_ok());
+    }
+
+    #[test]
+    fn rejects_empty_unknown_scheme_and_non_utf8() {
+        assert!(validate_results_uri(&[0u8; 128]).is_err());
+        assert!(validate_results_uri(&padded(b"http://insecure")).is_err());
+        assert!(validate_results_uri(&padded(&[b'a', b'r', b':', b'/', b'/', 0xff])).is_err());
+    }
+    #[test]
+    fn long_uri_continues_only_from_a_full_prefix() {
+        let long = [b"https://gateway.example/".as_slice(), &[b'a'; 200]].concat();
+        let mut prefix = [0u8; 128];
+        prefix.copy_from_slice(&long[..128]);
+        let ext = &long[128..];
+
+        assert!(validate_results_uri_with_ext(&prefix, ext).is_ok());
+
+        // Padded prefix, zero byte in the tail, tail too long
+        assert!(validate_results_uri_with_ext(&padded(b"ar://abc"), b"def").is_err());
+        assert!(validate_results_uri_with_ext(&prefix, b"ab\0c").is_err());
+        assert!(validate_results_uri_with_ext(&prefix, &[b'a'; 256]).is_err());
+
+        // A multi-byte character split across the boundary is still valid
+        let mut split = [b'a'; 128];
+        split[..8].copy_from_slice(b"https://");
+        split[127] = 0xc3;
+        assert!(validate_results_uri_with_ext(&split, &[0xa9]).is_ok());
+        assert!(validate_results_uri(&split).is_err());
+    }
 }
\ No newline at end of file
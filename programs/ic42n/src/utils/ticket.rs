@@ -2,13 +2,22 @@ use anchor_lang::prelude::Account;
 use crate::constants::MAX_TICKETS_PER_PLAYER;
 use crate::state::player_profile::{PlayerProfile};
 
+/// Credits up to `tickets` to the profile, capped at `MAX_TICKETS_PER_PLAYER`.
+/// Returns the number of tickets actually credited.
 pub fn award_tickets_to_profile(
     profile: &mut Account<PlayerProfile>,
     tickets: u32,
-) {
+) -> u32 {
     let new_total = profile
         .tickets_available
         .saturating_add(tickets)
         .min(MAX_TICKETS_PER_PLAYER); // or whatever cap
+    let credited = new_total.saturating_sub(profile.tickets_available);
+
     profile.tickets_available = new_total;
+    profile.tickets_awarded_total = profile
+        .tickets_awarded_total
+        .saturating_add(credited as u64);
+
+    credited
 }
\ No newline at end of file
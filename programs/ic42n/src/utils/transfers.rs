@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::errors::IC42NErrorCode;
+use crate::state::treasury::Treasury;
 
 pub fn transfer_lamports<'info>(
     from: &AccountInfo<'info>,
@@ -19,4 +20,149 @@ pub fn transfer_lamports<'info>(
         ),
         amount,
     )
+}
+
+/// Rent-exempt minimum for the treasury PDA (`8 + Treasury::SIZE`).
+///
+/// Falls back to the default rent parameters if the sysvar is unavailable.
+pub fn min_treasury_balance() -> u64 {
+    let space = 8 + Treasury::SIZE;
+    Rent::get()
+        .map(|rent| rent.minimum_balance(space))
+        .unwrap_or_else(|_| Rent::default().minimum_balance(space))
+}
+
+/// Checks that the treasury can pay `amount` out of `treasury_balance` and
+/// still stay rent-exempt, so a payout can never reap the PDA.
+pub fn require_treasury_can_pay(treasury_balance: u64, amount: u64) -> Result<()> {
+    let remaining = treasury_balance
+        .checked_sub(amount)
+        .ok_or(IC42NErrorCode::InsufficientTreasuryBalance)?;
+    require!(
+        remaining >= min_treasury_balance(),
+        IC42NErrorCode::TreasuryBelowRentExempt
+    );
+    Ok(())
+}
+
+/// Treasury lamports not backing any obligation or the rent-exempt reserve:
+/// dust that `sweep_treasury_dust` may move to the fee vault.
+pub fn treasury_surplus(treasury_balance: u64, total_obligations: u64) -> u64 {
+    treasury_balance
+        .saturating_sub(min_treasury_balance())
+        .saturating_sub(total_obligations)
+}
+
+/// Grows a program-owned account to `new_len` bytes (zero-filled), topping up
+/// rent from `payer`. No-op if the account is already large enough.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let required = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        transfer_lamports(payer, account, system_program, shortfall)?;
+    }
+
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Creates a program-owned PDA with `space` bytes, rent paid by `payer`.
+///
+/// Mirrors Anchor's `init`: if the address was pre-funded (which would make
+/// `create_account` fail), it tops up rent, then allocates and assigns instead.
+pub fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    use anchor_lang::system_program::{allocate, assign, create_account, Allocate, Assign, CreateAccount};
+
+    let required = Rent::get()?.minimum_balance(space);
+    let current = account.lamports();
+    let signer = &[signer_seeds];
+
+    if current == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: payer.clone(), to: account.clone() },
+                signer,
+            ),
+            required,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let shortfall = required.saturating_sub(current);
+    if shortfall > 0 {
+        transfer_lamports(payer, account, system_program, shortfall)?;
+    }
+
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate { account_to_allocate: account.clone() },
+            signer,
+        ),
+        space as u64,
+    )?;
+
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign { account_to_assign: account.clone() },
+            signer,
+        ),
+        &crate::ID,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payout_must_leave_rent_exempt_minimum() {
+        let min = min_treasury_balance();
+        assert!(min > 0);
+
+        assert!(require_treasury_can_pay(min + 100, 100).is_ok());
+        assert_eq!(
+            require_treasury_can_pay(min + 100, 101).unwrap_err(),
+            IC42NErrorCode::TreasuryBelowRentExempt.into()
+        );
+        assert_eq!(
+            require_treasury_can_pay(50, 100).unwrap_err(),
+            IC42NErrorCode::InsufficientTreasuryBalance.into()
+        );
+    }
+
+    #[test]
+    fn surplus_is_only_the_unowed_remainder() {
+        let min = min_treasury_balance();
+        let obligations = 1_000_000;
+        let dust = 37;
+
+        let balance = min + obligations + dust;
+        assert_eq!(treasury_surplus(balance, obligations), dust);
+
+        // Sweeping exactly the surplus leaves the reserve and obligations intact
+        assert!(require_treasury_can_pay(balance - obligations, dust).is_ok());
+        assert_eq!(treasury_surplus(balance - dust, obligations), 0);
+
+        // Underwater treasuries have nothing to sweep
+        assert_eq!(treasury_surplus(min + obligations - 1, obligations), 0);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+use crate::constants::{LEVEL_XP_THRESHOLDS, XP_BASE_WAGER_LAMPORTS, XP_PER_DOUBLING};
+
+/// XP earned for a single wager, log2-scaled on the lamports wagered.
+///
+/// Anything up to `XP_BASE_WAGER_LAMPORTS` earns `XP_PER_DOUBLING`, and every
+/// doubling above it earns another `XP_PER_DOUBLING`:
+/// 0.01 SOL → 10, 0.02 SOL → 20, 1 SOL → 70, 100 SOL → 140.
+pub fn xp_for_wager(lamports: u64) -> u32 {
+    let multiple = (lamports / XP_BASE_WAGER_LAMPORTS).max(1);
+    XP_PER_DOUBLING.saturating_mul(1 + multiple.ilog2())
+}
+
+/// Level for a given XP total (1-based, see `LEVEL_XP_THRESHOLDS`).
+pub fn level_for_xp(xp: u32) -> u16 {
+    LEVEL_XP_THRESHOLDS
+        .iter()
+        .take_while(|&&threshold| xp >= threshold)
+        .count() as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::LAMPORTS_PER_SOL;
+
+    #[test]
+    fn wager_xp_is_log2_scaled() {
+        assert_eq!(xp_for_wager(0), 10);
+        assert_eq!(xp_for_wager(XP_BASE_WAGER_LAMPORTS), 10);
+        assert_eq!(xp_for_wager(XP_BASE_WAGER_LAMPORTS * 2 - 1), 10);
+        assert_eq!(xp_for_wager(XP_BASE_WAGER_LAMPORTS * 2), 20);
+        assert_eq!(xp_for_wager(LAMPORTS_PER_SOL), 70);
+        assert_eq!(xp_for_wager(100 * LAMPORTS_PER_SOL), 140);
+        assert_eq!(xp_for_wager(u64::MAX), 410);
+    }
+
+    #[test]
+    fn level_boundaries() {
+        assert_eq!(level_for_xp(0), 1);
+        assert_eq!(level_for_xp(99), 1);
+        assert_eq!(level_for_xp(100), 2);
+        assert_eq!(level_for_xp(299), 2);
+        assert_eq!(level_for_xp(300), 3);
+        assert_eq!(level_for_xp(49_999), 9);
+        assert_eq!(level_for_xp(50_000), 10);
+        assert_eq!(level_for_xp(200_000), 12);
+        assert_eq!(level_for_xp(u32::MAX), LEVEL_XP_THRESHOLDS.len() as u16);
+    }
+}
\ No newline at end of file